@@ -0,0 +1,90 @@
+use crate::fetcher::Fetcher;
+use crate::metadata;
+use crate::metadata::Fetched;
+use redis::aio::MultiplexedConnection;
+use redis::AsyncCommands;
+use std::time::Duration;
+
+const KEY_PREFIX: &str = "metadata:";
+
+/// Caches what was fetched about the URLs, so that looking the same one up again does not hit
+/// the remote site. The failures are cached too, for a shorter time.
+#[derive(Clone)]
+pub struct MetadataCache {
+    connection: MultiplexedConnection,
+    ttl: Duration,
+    failure_ttl: Duration,
+}
+
+impl MetadataCache {
+    /// A `ttl` of zero disables the cache, a `failure_ttl` of zero the caching of failures.
+    pub fn new(connection: MultiplexedConnection, ttl: Duration, failure_ttl: Duration) -> Self {
+        Self {
+            connection,
+            ttl,
+            failure_ttl,
+        }
+    }
+
+    /// Fetches the page unless it was recently, successfully or not.
+    pub async fn fetch(&self, fetcher: &Fetcher, url: String) -> Fetched {
+        if let Some(fetched) = self.get(&url).await {
+            return fetched;
+        }
+
+        let fetched = metadata::fetch(fetcher, url.clone()).await;
+        self.put(&url, &fetched).await;
+        fetched
+    }
+
+    /// Returns what was fetched about the cleaned `url`, if it is still cached. The cache being
+    /// unavailable is not an error: the URL is just fetched again.
+    pub async fn get(&self, url: &str) -> Option<Fetched> {
+        if self.ttl.is_zero() {
+            return None;
+        }
+        let mut connection = self.connection.clone();
+        let json: Option<String> = connection
+            .get(key(url))
+            .await
+            .map_err(|e| log::warn!("Could not read metadata cache: {}", e))
+            .ok()?;
+
+        let fetched = serde_json::from_str::<Fetched>(&json?)
+            .map_err(|e| log::warn!("Invalid metadata cache entry for {}: {}", url, e))
+            .ok()?;
+        log::trace!("metadata of {} found in cache", url);
+
+        Some(fetched)
+    }
+
+    pub async fn put(&self, url: &str, fetched: &Fetched) {
+        let ttl = match fetched.failed {
+            true => self.failure_ttl,
+            false => self.ttl,
+        };
+        if ttl.is_zero() || self.ttl.is_zero() {
+            return;
+        }
+
+        let json = match serde_json::to_string(fetched) {
+            Ok(json) => json,
+            Err(e) => {
+                log::warn!("Could not serialize metadata of {}: {}", url, e);
+                return;
+            }
+        };
+
+        let mut connection = self.connection.clone();
+        if let Err(e) = connection
+            .set_ex::<_, _, ()>(key(url), json, ttl.as_secs() as usize)
+            .await
+        {
+            log::warn!("Could not write metadata cache: {}", e);
+        }
+    }
+}
+
+fn key(url: &str) -> String {
+    format!("{}{}", KEY_PREFIX, url)
+}
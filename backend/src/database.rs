@@ -1,8 +1,13 @@
 pub mod accounts;
 pub mod bookmarks;
 pub mod bookmarks_tags;
+pub mod domains;
 pub mod password_recoveries;
 pub mod pins;
+pub mod query_param_rules;
+pub mod rules;
+pub mod saved_searches;
+pub mod tag_aliases;
 pub mod tags;
 
 use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
@@ -15,6 +20,14 @@ pub struct Configuration {
     pub database: String,
 }
 
+/// Escapes the `LIKE` wildcards of `value`, so that it only matches itself.
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
 pub async fn connect(configuration: &Configuration) -> Result<DatabaseConnection, DbErr> {
     let mut connect_options = ConnectOptions::new(format!(
         "postgres://{}:{}@{}:{}/{}",
@@ -28,3 +41,16 @@ pub async fn connect(configuration: &Configuration) -> Result<DatabaseConnection
 
     Database::connect(connect_options).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::escape_like;
+
+    #[test]
+    fn like_wildcards_are_escaped() {
+        assert_eq!(escape_like("rust"), "rust");
+        assert_eq!(escape_like("100%"), "100\\%");
+        assert_eq!(escape_like("snake_case"), "snake\\_case");
+        assert_eq!(escape_like("c:\\"), "c:\\\\");
+    }
+}
@@ -1,9 +1,13 @@
-use crate::database::tags;
-use crate::domain::bookmark::Bookmark;
-use chrono::{DateTime, Utc};
+use crate::database::{escape_like, tags};
+use crate::domain::bookmark::{Bookmark, Metadata, MetadataStatus};
+use crate::domain::tag::Tag;
+use crate::metadata::PageMetadata;
+use crate::url;
+use chrono::{DateTime, NaiveDate, Utc};
+use common::TAG_SEPARATOR;
 use entity::bookmark::{ActiveModel, Entity};
 use entity::bookmark::{Column, Model};
-use entity::{bookmark_tag, pin, tag};
+use entity::{bookmark_tag, pin, tag, tag_alias};
 use sea_orm::prelude::DateTimeWithTimeZone;
 use sea_orm::sea_query::extension::postgres::PgExpr;
 use sea_orm::sea_query::{Expr, IntoCondition};
@@ -14,11 +18,22 @@ use sea_orm::{
     TryIntoModel, Value,
 };
 
+/// Marks the beginning of a highlighted fragment in snippets.
+pub const HIGHLIGHT_START: char = '\u{E000}';
+/// Marks the end of a highlighted fragment in snippets.
+pub const HIGHLIGHT_STOP: char = '\u{E001}';
+
 #[derive(Debug, Default)]
 pub struct SearchCriteria {
     pub tags: Vec<String>,
     pub search: Vec<String>,
     pub filter: Filter,
+    pub domain: Option<String>,
+    /// Whether filtering by a tag also matches its descendants (i.e. `dev` matches `dev/rust`).
+    pub descendants: bool,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub updated_after: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug)]
@@ -79,6 +94,33 @@ impl TryFrom<&str> for Filter {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+pub enum Granularity {
+    Month,
+    Day,
+}
+
+impl Granularity {
+    fn date_trunc_field(&self) -> &str {
+        match self {
+            Granularity::Month => "month",
+            Granularity::Day => "day",
+        }
+    }
+}
+
+impl TryFrom<&str> for Granularity {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "month" => Ok(Granularity::Month),
+            "day" => Ok(Granularity::Day),
+            _ => Err(format!("{} is not valid", value)),
+        }
+    }
+}
+
 pub struct Query;
 
 enum SearchBy<'a> {
@@ -88,6 +130,7 @@ enum SearchBy<'a> {
         &'a Pagination,
         &'a SortOrder,
         Option<i32>,
+        bool,
     ),
 }
 
@@ -115,14 +158,74 @@ impl<'a> SearchBy<'a> {
         }
     }
 
-    fn tags_condition(tags: &Vec<String>) -> Condition {
+    /// Highlights, in the title and description, the terms the search matched them on: the same
+    /// case-insensitive substrings as [`SearchBy::search_condition`].
+    fn add_snippets(select: Select<Entity>, search: Option<&Vec<String>>) -> Select<Entity> {
+        match search.and_then(|search| SearchBy::highlight_pattern(search)) {
+            Some(pattern) => {
+                let highlight = |column: Column| {
+                    Expr::cust_with_exprs(
+                        "regexp_replace($1, $2, $3, 'gi')",
+                        [
+                            Expr::col(column).into(),
+                            Expr::val(pattern.clone()).into(),
+                            Expr::val(format!("{}\\&{}", HIGHLIGHT_START, HIGHLIGHT_STOP)).into(),
+                        ],
+                    )
+                };
+                select
+                    .column_as(highlight(Column::Title), "title_snippet")
+                    .column_as(highlight(Column::Description), "description_snippet")
+            }
+            None => select
+                .column_as(Expr::value(Value::String(None)), "title_snippet")
+                .column_as(Expr::value(Value::String(None)), "description_snippet"),
+        }
+    }
+
+    /// Builds a regular expression matching any of the search terms literally.
+    fn highlight_pattern(search: &[String]) -> Option<String> {
+        search
+            .iter()
+            .filter(|term| !term.is_empty())
+            .map(|term| regex::escape(term))
+            .reduce(|a, b| format!("{}|{}", a, b))
+    }
+
+    fn tags_condition(tags: &Vec<String>, descendants: bool, user_id: Option<i32>) -> Condition {
         let mut tags_condition = Condition::all();
 
         if tags.is_empty() {
             return tags_condition;
         }
 
-        for expr in tags.iter().map(|t| {
+        for expr in tags.iter().map(|t| t.to_lowercase()).map(|t| {
+            let mut tag_condition =
+                Condition::any().add(Expr::expr(tags::lower_name()).eq(t.as_str()));
+            if let Some(user_id) = user_id {
+                tag_condition = tag_condition.add(
+                    tag::Column::Id.in_subquery(
+                        tag_alias::Entity::find()
+                            .select_only()
+                            .column(tag_alias::Column::TagId)
+                            .filter(tag_alias::Column::UserId.eq(user_id))
+                            .filter(tag_alias::Column::Alias.eq(t.as_str()))
+                            .into_query(),
+                    ),
+                );
+            }
+            if descendants {
+                let like_expr = format!(
+                    "{}{}%",
+                    t.replace('\\', "\\\\")
+                        .replace('%', "\\%")
+                        .replace('_', "\\_"),
+                    TAG_SEPARATOR
+                );
+                tag_condition =
+                    tag_condition.add(Expr::expr(tags::lower_name()).like(like_expr.as_str()));
+            }
+
             Column::Id.in_subquery(
                 bookmark_tag::Entity::find()
                     .select_only()
@@ -132,7 +235,7 @@ impl<'a> SearchBy<'a> {
                             tag::Entity::find()
                                 .select_only()
                                 .column(tag::Column::Id)
-                                .filter(tag::Column::Name.eq(t))
+                                .filter(tag_condition)
                                 .into_query(),
                         ),
                     )
@@ -145,6 +248,42 @@ impl<'a> SearchBy<'a> {
         tags_condition
     }
 
+    fn dates_condition(criteria: &SearchCriteria) -> Condition {
+        let mut dates_condition = Condition::all();
+
+        if let Some(date) = criteria.created_after {
+            dates_condition = dates_condition.add(Column::CreationDate.gte(date));
+        }
+        if let Some(date) = criteria.created_before {
+            dates_condition = dates_condition.add(Column::CreationDate.lt(date));
+        }
+        if let Some(date) = criteria.updated_after {
+            dates_condition = dates_condition.add(Column::UpdateDate.gte(date));
+        }
+
+        dates_condition
+    }
+
+    fn domain_condition(domain: &Option<String>) -> Condition {
+        match domain {
+            None => Condition::all(),
+            Some(domain) => Condition::all().add(Column::Domain.eq(domain.to_lowercase())),
+        }
+    }
+
+    fn criteria_condition(criteria: &SearchCriteria, user_id: Option<i32>) -> Condition {
+        Condition::all()
+            .add(Query::visible_condition(user_id, criteria.filter))
+            .add(SearchBy::tags_condition(
+                &criteria.tags,
+                criteria.descendants,
+                user_id,
+            ))
+            .add(SearchBy::search_condition(&criteria.search))
+            .add(SearchBy::dates_condition(criteria))
+            .add(SearchBy::domain_condition(&criteria.domain))
+    }
+
     fn search_condition(search: &Vec<String>) -> Condition {
         let mut search_condition = Condition::all();
 
@@ -153,7 +292,7 @@ impl<'a> SearchBy<'a> {
         }
 
         for expr in search.iter().map(|t| {
-            let like_expr = format!("%{}%", t);
+            let like_expr = format!("%{}%", escape_like(t));
             let inner = Condition::any();
             let inner = inner.add(Expr::col(Column::Title).ilike(like_expr.as_str()));
             let inner = inner.add(Expr::col(Column::Description).ilike(like_expr.as_str()));
@@ -170,22 +309,23 @@ impl<'a> From<&SearchBy<'a>> for Select<Entity> {
     fn from(value: &SearchBy<'a>) -> Self {
         let (select, user_id) = match value {
             SearchBy::Id(id, user_id) => (
-                Entity::find_by_id(id.to_owned())
-                    .filter(Query::visible_condition(user_id.to_owned(), Filter::All)),
+                SearchBy::add_snippets(
+                    Entity::find_by_id(id.to_owned())
+                        .filter(Query::visible_condition(user_id.to_owned(), Filter::All)),
+                    None,
+                ),
                 user_id,
             ),
-            SearchBy::Criteria(criteria, page, order, user_id) => (
-                order.add_clause(
-                    Entity::find()
-                        .filter(Query::visible_condition(
-                            user_id.to_owned(),
-                            criteria.filter,
-                        ))
-                        .filter(SearchBy::tags_condition(&criteria.tags))
-                        .filter(SearchBy::search_condition(&criteria.search))
-                        .offset(page.size * page.page)
-                        .limit(page.size),
-                    user_id,
+            SearchBy::Criteria(criteria, page, order, user_id, highlight) => (
+                SearchBy::add_snippets(
+                    order.add_clause(
+                        Entity::find()
+                            .filter(SearchBy::criteria_condition(criteria, user_id.to_owned()))
+                            .offset(page.size * page.page)
+                            .limit(page.size),
+                        user_id,
+                    ),
+                    highlight.then_some(&criteria.search),
                 ),
                 user_id,
             ),
@@ -203,11 +343,27 @@ impl From<(BookmarkModelWithPinned, Vec<tag::Model>)> for Bookmark {
             url: value.0.url,
             title: value.0.title,
             description: value.0.description,
-            tags: value.1.into_iter().map(|t| t.name).collect(),
+            tags: value.1.into_iter().map(Tag::from).collect(),
             creation_date: value.0.creation_date.with_timezone(&Utc),
             update_date: value.0.update_date.map(|d| d.with_timezone(&Utc)),
             private: value.0.private,
             pinned: value.0.pinned,
+            domain: value.0.domain,
+            metadata: Metadata {
+                site_name: value.0.site_name,
+                author: value.0.author,
+                published_date: value.0.published_date.map(|d| d.with_timezone(&Utc)),
+                language: value.0.language,
+                image_url: value.0.image_url,
+                favicon_url: value.0.favicon_url,
+            },
+            metadata_status: value
+                .0
+                .metadata_status
+                .and_then(|s| MetadataStatus::try_from(s.as_str()).ok()),
+            metadata_fetch_date: value.0.metadata_fetch_date.map(|d| d.with_timezone(&Utc)),
+            title_snippet: value.0.title_snippet,
+            description_snippet: value.0.description_snippet,
         }
     }
 }
@@ -223,6 +379,17 @@ struct BookmarkModelWithPinned {
     pub user_id: i32,
     pub private: bool,
     pub pinned: bool,
+    pub domain: Option<String>,
+    pub site_name: Option<String>,
+    pub author: Option<String>,
+    pub published_date: Option<DateTimeWithTimeZone>,
+    pub language: Option<String>,
+    pub image_url: Option<String>,
+    pub favicon_url: Option<String>,
+    pub metadata_status: Option<String>,
+    pub metadata_fetch_date: Option<DateTimeWithTimeZone>,
+    pub title_snippet: Option<String>,
+    pub description_snippet: Option<String>,
 }
 
 impl Query {
@@ -254,11 +421,26 @@ impl Query {
         page: &'a Pagination,
         order: &'a SortOrder,
         user_id: Option<i32>,
+        highlight: bool,
     ) -> Result<Vec<Bookmark>, DbErr>
     where
         C: ConnectionTrait,
     {
-        Self::find_by(db, &SearchBy::Criteria(criteria, page, order, user_id)).await
+        Self::find_by(
+            db,
+            &SearchBy::Criteria(criteria, page, order, user_id, highlight),
+        )
+        .await
+    }
+
+    /// The condition on the bookmarks having all the tags, or one of their descendants when
+    /// `descendants` is set.
+    pub fn tagged_condition(
+        tags: &Vec<String>,
+        descendants: bool,
+        user_id: Option<i32>,
+    ) -> Condition {
+        SearchBy::tags_condition(tags, descendants, user_id)
     }
 
     pub fn visible_condition(user_id: Option<i32>, filter: Filter) -> Condition {
@@ -296,15 +478,70 @@ impl Query {
         let r: Option<i64> = Entity::find()
             .select_only()
             .column_as(Expr::col(Column::Id).count(), "count")
-            .filter(Self::visible_condition(user_id, criteria.filter))
-            .filter(SearchBy::tags_condition(&criteria.tags))
-            .filter(SearchBy::search_condition(&criteria.search))
+            .filter(SearchBy::criteria_condition(criteria, user_id))
             .into_tuple()
             .one(db)
             .await?;
         Ok(r.unwrap_or_default())
     }
 
+    /// Counts the bookmarks matching the criteria, grouped by creation date truncated to the
+    /// granularity (dates are in UTC).
+    pub async fn timeline<C>(
+        db: &C,
+        user_id: Option<i32>,
+        criteria: &SearchCriteria,
+        granularity: Granularity,
+    ) -> Result<Vec<(NaiveDate, i64)>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let bucket = Expr::cust(&format!(
+            "date_trunc('{}', \"bookmark\".\"creation_date\" AT TIME ZONE 'UTC')::date",
+            granularity.date_trunc_field()
+        ));
+
+        Entity::find()
+            .select_only()
+            .column_as(bucket.clone(), "bucket")
+            .column_as(Expr::col(Column::Id).count(), "count")
+            .filter(SearchBy::criteria_condition(criteria, user_id))
+            .group_by(bucket.clone())
+            .order_by(bucket, Order::Asc)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// Returns distinct titles of visible bookmarks having a word starting with `prefix`.
+    pub async fn find_titles_by_prefix<C>(
+        db: &C,
+        user_id: Option<i32>,
+        prefix: &str,
+        limit: u64,
+    ) -> Result<Vec<String>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let prefix = escape_like(prefix);
+
+        Entity::find()
+            .select_only()
+            .column(Column::Title)
+            .filter(Self::visible_condition(user_id, Filter::All))
+            .filter(
+                Condition::any()
+                    .add(Expr::col(Column::Title).ilike(format!("{}%", prefix).as_str()))
+                    .add(Expr::col(Column::Title).ilike(format!("% {}%", prefix).as_str())),
+            )
+            .group_by(Column::Title)
+            .order_by_asc(Column::Title)
+            .limit(limit)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
     pub async fn find_visible_by_id<C>(
         db: &C,
         id: i32,
@@ -318,18 +555,141 @@ impl Query {
             .map(|mut r| r.pop())
     }
 
+    pub async fn find_by_user_id<C>(db: &C, user_id: i32) -> Result<Vec<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+    }
+
+    /// Returns the bookmarks whose metadata needs to be fetched: the ones a refresh was
+    /// requested for first, then the ones that were never fetched and have no title and,
+    /// when `older_than` is set, the ones last fetched (or created) before it.
+    pub async fn find_due_for_refresh<C>(
+        db: &C,
+        older_than: Option<DateTime<Utc>>,
+        limit: u64,
+    ) -> Result<Vec<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let mut due = Condition::any()
+            .add(Column::MetadataStatus.eq(MetadataStatus::Pending.as_str()))
+            .add(
+                Condition::all()
+                    .add(Column::Title.is_null())
+                    .add(Column::MetadataFetchDate.is_null()),
+            );
+        if let Some(date) = older_than {
+            due = due.add(Expr::cust_with_values(
+                "coalesce(\"bookmark\".\"metadata_fetch_date\", \"bookmark\".\"creation_date\") < $1",
+                [DateTimeWithTimeZone::from(date)],
+            ));
+        }
+
+        Entity::find()
+            .filter(due)
+            .order_by(
+                Expr::cust_with_values(
+                    "coalesce(\"bookmark\".\"metadata_status\" = $1, false)",
+                    [MetadataStatus::Pending.as_str()],
+                ),
+                Order::Desc,
+            )
+            .order_by(
+                Expr::cust(
+                    "coalesce(\"bookmark\".\"metadata_fetch_date\", \"bookmark\".\"creation_date\")",
+                ),
+                Order::Asc,
+            )
+            .limit(limit)
+            .all(db)
+            .await
+    }
+
+    /// Returns the oldest bookmark of the user pointing to the same page as `url`, see
+    /// [`url::key`].
     pub async fn find_by_url<C>(db: &C, user_id: i32, url: &str) -> Result<Option<i32>, DbErr>
     where
         C: ConnectionTrait,
     {
-        Ok(Entity::find()
-            // todo select only
-            .filter(Column::Url.eq(url))
+        Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .filter(Column::UrlKey.eq(url::key(url)))
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::CreationDate)
+            .into_tuple()
+            .one(db)
+            .await
+    }
+
+    /// Returns the oldest bookmark of the user, other than `bookmark_id`, pointing to the same
+    /// page as `url`.
+    pub async fn find_other_by_url<C>(
+        db: &C,
+        user_id: i32,
+        url: &str,
+        bookmark_id: i32,
+    ) -> Result<Option<i32>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .filter(Column::UrlKey.eq(url::key(url)))
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Id.ne(bookmark_id))
+            .order_by_asc(Column::CreationDate)
+            .into_tuple()
+            .one(db)
+            .await
+    }
+
+    /// Returns the ids of the bookmarks of the user pointing to the same page, grouped, the
+    /// oldest bookmark of each group first.
+    pub async fn find_duplicates<C>(db: &C, user_id: i32) -> Result<Vec<Vec<i32>>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let keys: Vec<String> = Entity::find()
+            .select_only()
+            .column(Column::UrlKey)
             .filter(Column::UserId.eq(user_id))
+            .group_by(Column::UrlKey)
+            .having(Expr::expr(Expr::col(Column::Id).count()).gt(1))
+            .order_by_asc(Column::UrlKey)
+            .into_tuple()
             .all(db)
-            .await?
-            .pop()
-            .map(|m| m.id))
+            .await?;
+
+        let bookmarks: Vec<(i32, String)> = Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .column(Column::UrlKey)
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::UrlKey.is_in(keys.clone()))
+            .order_by_asc(Column::CreationDate)
+            .order_by_asc(Column::Id)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(keys
+            .iter()
+            .map(|key| {
+                bookmarks
+                    .iter()
+                    .filter(|(_, k)| k == key)
+                    .map(|(id, _)| *id)
+                    .collect()
+            })
+            .collect())
     }
 }
 
@@ -343,16 +703,25 @@ impl Mutation {
         description: Option<String>,
         user_id: i32,
         private: bool,
+        metadata: Metadata,
     ) -> Result<Model, DbErr>
     where
         C: ConnectionTrait,
     {
         ActiveModel {
+            domain: Set(url::domain(&url)),
+            url_key: Set(url::key(&url)),
             url: Set(url),
             title: Set(title),
             description: Set(description),
             user_id: Set(user_id),
             private: Set(private),
+            site_name: Set(metadata.site_name),
+            author: Set(metadata.author),
+            published_date: Set(metadata.published_date.map(|d| d.into())),
+            language: Set(metadata.language),
+            image_url: Set(metadata.image_url),
+            favicon_url: Set(metadata.favicon_url),
             ..Default::default()
         }
         .save(db)
@@ -375,7 +744,8 @@ impl Mutation {
         C: ConnectionTrait,
     {
         ActiveModel {
-            id: Default::default(),
+            domain: Set(url::domain(&url)),
+            url_key: Set(url::key(&url)),
             url: Set(url),
             title: Set(title),
             description: Set(description),
@@ -383,6 +753,7 @@ impl Mutation {
             private: Set(private),
             creation_date: Set(creation_date.into()),
             update_date: Set(update_date.map(|d| d.into())),
+            ..Default::default()
         }
         .save(db)
         .await
@@ -405,6 +776,8 @@ impl Mutation {
             .await?
             .map(Into::<ActiveModel>::into);
         if let Some(mut model) = model {
+            model.domain = Set(url::domain(&url));
+            model.url_key = Set(url::key(&url));
             model.url = Set(url);
             model.title = Set(title);
             model.description = Set(description);
@@ -416,6 +789,150 @@ impl Mutation {
         }
     }
 
+    pub async fn make_private<C>(db: &C, id: i32) -> Result<(), DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::update_many()
+            .col_expr(Column::Private, Expr::value(true))
+            .filter(Column::Id.eq(id))
+            .exec(db)
+            .await
+            .map(|_| ())
+    }
+
+    /// Requests the metadata of the bookmark to be fetched again.
+    pub async fn request_refresh<C>(db: &C, id: i32) -> Result<(), DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::update_many()
+            .col_expr(
+                Column::MetadataStatus,
+                Expr::value(MetadataStatus::Pending.as_str()),
+            )
+            .filter(Column::Id.eq(id))
+            .exec(db)
+            .await
+            .map(|_| ())
+    }
+
+    /// Requests the metadata of the user's bookmarks matching the criteria to be fetched again,
+    /// returning their number.
+    pub async fn request_refresh_matching<C>(
+        db: &C,
+        user_id: i32,
+        criteria: &SearchCriteria,
+    ) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::update_many()
+            .col_expr(
+                Column::MetadataStatus,
+                Expr::value(MetadataStatus::Pending.as_str()),
+            )
+            .filter(SearchBy::criteria_condition(criteria, Some(user_id)))
+            .filter(Column::UserId.eq(user_id))
+            .exec(db)
+            .await
+            .map(|r| r.rows_affected)
+    }
+
+    /// Records the outcome of fetching the metadata of the bookmark. The title and description,
+    /// which the user may have edited, are only filled in if empty; the other metadata are
+    /// replaced by the ones that were found.
+    pub async fn update_fetched_metadata<C>(
+        db: &C,
+        id: i32,
+        fetched: Option<PageMetadata>,
+    ) -> Result<(), DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let model = match Entity::find_by_id(id).one(db).await? {
+            Some(model) => model,
+            None => return Ok(()),
+        };
+        let mut active_model = Into::<ActiveModel>::into(model.clone());
+
+        active_model.metadata_fetch_date = Set(Some(DateTimeWithTimeZone::from(Utc::now())));
+        match fetched {
+            None => {
+                active_model.metadata_status = Set(Some(MetadataStatus::Failure.as_str().into()))
+            }
+            Some(page) => {
+                let metadata = page.metadata;
+                active_model.metadata_status = Set(Some(MetadataStatus::Success.as_str().into()));
+                let fill = |current: Option<String>, fetched: Option<String>| {
+                    current.filter(|c| !c.trim().is_empty()).or(fetched)
+                };
+                active_model.title = Set(fill(model.title, page.title));
+                active_model.description = Set(fill(model.description, page.description));
+                active_model.site_name = Set(metadata.site_name.or(model.site_name));
+                active_model.author = Set(metadata.author.or(model.author));
+                active_model.published_date = Set(metadata
+                    .published_date
+                    .map(|d| d.into())
+                    .or(model.published_date));
+                active_model.language = Set(metadata.language.or(model.language));
+                active_model.image_url = Set(metadata.image_url.or(model.image_url));
+                active_model.favicon_url = Set(metadata.favicon_url.or(model.favicon_url));
+            }
+        }
+
+        active_model.update(db).await.map(|_| ())
+    }
+
+    /// Merges the bookmarks `duplicate_ids` into the bookmark `id`, which keeps the oldest
+    /// creation date and takes the title, description and metadata it lacks from them; it is
+    /// private if one of them is. Their tags and pins are expected to be moved already.
+    pub async fn merge<C>(db: &C, id: i32, duplicate_ids: &[i32]) -> Result<Option<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let model = match Entity::find_by_id(id).one(db).await? {
+            Some(model) => model,
+            None => return Ok(None),
+        };
+        let duplicates = Entity::find()
+            .filter(Column::Id.is_in(duplicate_ids.to_vec()))
+            .order_by_asc(Column::CreationDate)
+            .all(db)
+            .await?;
+
+        let mut merged = model.clone();
+        for duplicate in duplicates {
+            merged.creation_date = merged.creation_date.min(duplicate.creation_date);
+            merged.title = merged.title.or(duplicate.title);
+            merged.description = merged.description.or(duplicate.description);
+            merged.private |= duplicate.private;
+            merged.site_name = merged.site_name.or(duplicate.site_name);
+            merged.author = merged.author.or(duplicate.author);
+            merged.published_date = merged.published_date.or(duplicate.published_date);
+            merged.language = merged.language.or(duplicate.language);
+            merged.image_url = merged.image_url.or(duplicate.image_url);
+            merged.favicon_url = merged.favicon_url.or(duplicate.favicon_url);
+
+            Entity::delete_by_id(duplicate.id).exec(db).await?;
+        }
+
+        let mut active_model = ActiveModel::from(model);
+        active_model.creation_date = Set(merged.creation_date);
+        active_model.title = Set(merged.title);
+        active_model.description = Set(merged.description);
+        active_model.private = Set(merged.private);
+        active_model.site_name = Set(merged.site_name);
+        active_model.author = Set(merged.author);
+        active_model.published_date = Set(merged.published_date);
+        active_model.language = Set(merged.language);
+        active_model.image_url = Set(merged.image_url);
+        active_model.favicon_url = Set(merged.favicon_url);
+        active_model.update_date = Set(Some(DateTimeWithTimeZone::from(Utc::now())));
+
+        Ok(Some(active_model.update(db).await?))
+    }
+
     pub async fn delete_bookmark<C>(db: &C, id: i32) -> Result<Option<()>, DbErr>
     where
         C: ConnectionTrait,
@@ -1,9 +1,53 @@
 use entity::bookmark_tag::{ActiveModel, Column, Entity, Model};
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, TryIntoModel,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QuerySelect,
+    QueryTrait, TryIntoModel,
 };
 
+pub struct Query;
+
+impl Query {
+    pub async fn find_bookmark_ids_by_tag_id<C>(db: &C, tag_id: i32) -> Result<Vec<i32>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .select_only()
+            .column(Column::BookmarkId)
+            .filter(Column::TagId.eq(tag_id))
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// Returns the bookmarks linked to the tag and to no other tag.
+    pub async fn find_exclusive_bookmark_ids_by_tag_id<C>(
+        db: &C,
+        tag_id: i32,
+    ) -> Result<Vec<i32>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .select_only()
+            .column(Column::BookmarkId)
+            .filter(Column::TagId.eq(tag_id))
+            .filter(
+                Column::BookmarkId.not_in_subquery(
+                    Entity::find()
+                        .select_only()
+                        .column(Column::BookmarkId)
+                        .filter(Column::TagId.ne(tag_id))
+                        .into_query(),
+                ),
+            )
+            .into_tuple()
+            .all(db)
+            .await
+    }
+}
+
 pub struct Mutation;
 
 impl Mutation {
@@ -30,4 +74,15 @@ impl Mutation {
             .await
             .map(|r| r.rows_affected)
     }
+
+    pub async fn delete_all_tag_links<C>(db: &C, tag_id: i32) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::delete_many()
+            .filter(Column::TagId.eq(tag_id))
+            .exec(db)
+            .await
+            .map(|r| r.rows_affected)
+    }
 }
@@ -0,0 +1,59 @@
+use crate::database::bookmarks;
+use crate::database::bookmarks::Filter;
+use crate::database::escape_like;
+use entity::bookmark::{Column, Entity};
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+
+pub struct Query;
+
+impl Query {
+    pub async fn find_by_user_id<C>(
+        db: &C,
+        user_id: Option<i32>,
+    ) -> Result<Vec<(String, i64)>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .select_only()
+            .column(Column::Domain)
+            .column_as(Expr::col(Column::Id).count(), "count")
+            .filter(bookmarks::Query::visible_condition(user_id, Filter::All))
+            .filter(Column::Domain.is_not_null())
+            .group_by(Column::Domain)
+            .order_by_desc(SimpleExpr::Custom("\"count\"".to_owned()))
+            .order_by_asc(Column::Domain)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// Returns the domains starting with `prefix` having the most visible bookmarks.
+    pub async fn find_by_prefix<C>(
+        db: &C,
+        user_id: Option<i32>,
+        prefix: &str,
+        limit: u64,
+    ) -> Result<Vec<(String, i64)>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let like_expr = format!("{}%", escape_like(&prefix.to_lowercase()));
+        Entity::find()
+            .select_only()
+            .column(Column::Domain)
+            .column_as(Expr::col(Column::Id).count(), "count")
+            .filter(bookmarks::Query::visible_condition(user_id, Filter::All))
+            .filter(Column::Domain.like(like_expr.as_str()))
+            .group_by(Column::Domain)
+            .order_by_desc(SimpleExpr::Custom("\"count\"".to_owned()))
+            .order_by_asc(Column::Domain)
+            .limit(limit)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+}
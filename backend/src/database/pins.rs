@@ -49,4 +49,24 @@ impl Mutation {
         log::info!("already pinned");
         Ok(())
     }
+
+    /// Moves the pins of a bookmark to another one, e.g. when merging them.
+    pub async fn transfer<C>(
+        db: &C,
+        from_bookmark_id: i32,
+        to_bookmark_id: i32,
+    ) -> Result<(), DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        for pin in Entity::find()
+            .filter(Column::BookmarkId.eq(from_bookmark_id))
+            .all(db)
+            .await?
+        {
+            Self::pin(db, to_bookmark_id, pin.user_id).await?;
+            Self::unpin(db, from_bookmark_id, pin.user_id).await?;
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,72 @@
+use entity::query_param_rule::{ActiveModel, Column, Entity, Model};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, TryIntoModel,
+};
+
+pub struct Query;
+
+impl Query {
+    /// Returns the rules of the instance, then the ones of the user if any.
+    pub async fn find_applicable<C>(db: &C, user_id: Option<i32>) -> Result<Vec<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let mut condition = Condition::any().add(Column::UserId.is_null());
+        if let Some(user_id) = user_id {
+            condition = condition.add(Column::UserId.eq(user_id));
+        }
+
+        Entity::find()
+            .filter(condition)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+    }
+}
+
+/// The columns of a rule a user can set.
+pub struct QueryParamRuleData {
+    /// `None` for a rule of the instance.
+    pub user_id: Option<i32>,
+    pub pattern: String,
+    pub domain: Option<String>,
+    pub ignored: bool,
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    pub async fn create<C>(db: &C, data: QueryParamRuleData) -> Result<Model, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        ActiveModel {
+            user_id: Set(data.user_id),
+            pattern: Set(data.pattern),
+            domain: Set(data.domain),
+            ignored: Set(data.ignored),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+        .and_then(|m| m.try_into_model())
+    }
+
+    /// Deletes a rule of the user, or of the instance when `user_id` is `None`.
+    pub async fn delete<C>(db: &C, id: i32, user_id: Option<i32>) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(match user_id {
+                Some(user_id) => Column::UserId.eq(user_id),
+                None => Column::UserId.is_null(),
+            })
+            .exec(db)
+            .await
+            .map(|r| r.rows_affected)
+    }
+}
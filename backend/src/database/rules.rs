@@ -0,0 +1,104 @@
+use entity::rule::{ActiveModel, Column, Entity, Model};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    TryIntoModel,
+};
+
+pub struct Query;
+
+impl Query {
+    pub async fn find_by_user_id<C>(db: &C, user_id: i32) -> Result<Vec<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+    }
+
+    pub async fn find_by_id<C>(db: &C, id: i32, user_id: i32) -> Result<Option<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find_by_id(id)
+            .filter(Column::UserId.eq(user_id))
+            .one(db)
+            .await
+    }
+}
+
+/// The columns of a rule a user can change.
+pub struct RuleData {
+    pub url_pattern: Option<String>,
+    pub url_regex: bool,
+    pub title_pattern: Option<String>,
+    /// The tags to add, separated by spaces.
+    pub tags: String,
+    pub private: bool,
+    pub pin: bool,
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    pub async fn create<C>(db: &C, user_id: i32, data: RuleData) -> Result<Model, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        ActiveModel {
+            user_id: Set(user_id),
+            url_pattern: Set(data.url_pattern),
+            url_regex: Set(data.url_regex),
+            title_pattern: Set(data.title_pattern),
+            tags: Set(data.tags),
+            private: Set(data.private),
+            pin: Set(data.pin),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+        .and_then(|m| m.try_into_model())
+    }
+
+    pub async fn update<C>(
+        db: &C,
+        id: i32,
+        user_id: i32,
+        data: RuleData,
+    ) -> Result<Option<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let model = Query::find_by_id(db, id, user_id)
+            .await?
+            .map(Into::<ActiveModel>::into);
+
+        if let Some(mut model) = model {
+            model.url_pattern = Set(data.url_pattern);
+            model.url_regex = Set(data.url_regex);
+            model.title_pattern = Set(data.title_pattern);
+            model.tags = Set(data.tags);
+            model.private = Set(data.private);
+            model.pin = Set(data.pin);
+
+            return Ok(Some(model.update(db).await?));
+        }
+
+        Ok(None)
+    }
+
+    pub async fn delete<C>(db: &C, id: i32, user_id: i32) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(db)
+            .await
+            .map(|r| r.rows_affected)
+    }
+}
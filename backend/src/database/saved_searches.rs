@@ -0,0 +1,127 @@
+use entity::saved_search::{ActiveModel, Column, Entity, Model};
+use sea_orm::sea_query::Expr;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    TryIntoModel,
+};
+
+pub struct Query;
+
+impl Query {
+    pub async fn find_by_user_id<C>(db: &C, user_id: i32) -> Result<Vec<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Name)
+            .all(db)
+            .await
+    }
+
+    pub async fn find_by_id<C>(db: &C, id: i32, user_id: i32) -> Result<Option<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find_by_id(id)
+            .filter(Column::UserId.eq(user_id))
+            .one(db)
+            .await
+    }
+
+    pub async fn find_by_name<C>(db: &C, name: &str, user_id: i32) -> Result<Option<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .filter(Column::Name.eq(name))
+            .filter(Column::UserId.eq(user_id))
+            .one(db)
+            .await
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    pub async fn create<C>(
+        db: &C,
+        user_id: i32,
+        name: String,
+        query: String,
+        is_default: bool,
+    ) -> Result<Model, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if is_default {
+            Self::clear_default(db, user_id).await?;
+        }
+
+        ActiveModel {
+            user_id: Set(user_id),
+            name: Set(name),
+            query: Set(query),
+            is_default: Set(is_default),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+        .and_then(|m| m.try_into_model())
+    }
+
+    pub async fn update<C>(
+        db: &C,
+        id: i32,
+        user_id: i32,
+        name: String,
+        query: String,
+        is_default: bool,
+    ) -> Result<Option<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let model = Query::find_by_id(db, id, user_id)
+            .await?
+            .map(Into::<ActiveModel>::into);
+
+        if let Some(mut model) = model {
+            if is_default {
+                Self::clear_default(db, user_id).await?;
+            }
+
+            model.name = Set(name);
+            model.query = Set(query);
+            model.is_default = Set(is_default);
+
+            return Ok(Some(model.update(db).await?));
+        }
+
+        Ok(None)
+    }
+
+    pub async fn delete<C>(db: &C, id: i32, user_id: i32) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(db)
+            .await
+            .map(|r| r.rows_affected)
+    }
+
+    async fn clear_default<C>(db: &C, user_id: i32) -> Result<(), DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::update_many()
+            .col_expr(Column::IsDefault, Expr::value(false))
+            .filter(Column::UserId.eq(user_id))
+            .exec(db)
+            .await
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,95 @@
+use entity::tag;
+use entity::tag_alias::{ActiveModel, Column, Entity, Model};
+use sea_orm::sea_query::Expr;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, JoinType, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, TryIntoModel,
+};
+
+pub struct Query;
+
+impl Query {
+    /// Returns the user's aliases with the name of the tag they stand for.
+    pub async fn find_by_user_id<C>(db: &C, user_id: i32) -> Result<Vec<(String, String)>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .select_only()
+            .column(Column::Alias)
+            .column(tag::Column::Name)
+            .join(JoinType::Join, entity::tag_alias::Relation::Tag.def())
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Alias)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    pub async fn find_by_alias<C>(db: &C, user_id: i32, alias: &str) -> Result<Option<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Alias.eq(alias))
+            .one(db)
+            .await
+    }
+}
+
+pub struct Mutation;
+
+impl Mutation {
+    pub async fn create<C>(db: &C, user_id: i32, alias: String, tag_id: i32) -> Result<Model, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        ActiveModel {
+            user_id: Set(user_id),
+            alias: Set(alias),
+            tag_id: Set(tag_id),
+            ..Default::default()
+        }
+        .save(db)
+        .await
+        .and_then(|m| m.try_into_model())
+    }
+
+    pub async fn delete<C>(db: &C, user_id: i32, alias: &str) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Alias.eq(alias))
+            .exec(db)
+            .await
+            .map(|r| r.rows_affected)
+    }
+
+    /// Makes the aliases of a tag point to another one, e.g. when the tag is merged.
+    pub async fn move_all<C>(db: &C, from_tag_id: i32, to_tag_id: i32) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::update_many()
+            .col_expr(Column::TagId, Expr::value(to_tag_id))
+            .filter(Column::TagId.eq(from_tag_id))
+            .exec(db)
+            .await
+            .map(|r| r.rows_affected)
+    }
+
+    pub async fn delete_all<C>(db: &C, tag_id: i32) -> Result<u64, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::delete_many()
+            .filter(Column::TagId.eq(tag_id))
+            .exec(db)
+            .await
+            .map(|r| r.rows_affected)
+    }
+}
@@ -1,9 +1,13 @@
 use crate::database::bookmarks;
 use crate::database::bookmarks::Filter;
+use crate::database::bookmarks_tags;
+use crate::database::escape_like;
+use crate::database::tag_aliases;
+use crate::domain::tag::Tag;
 use entity::tag::{ActiveModel, Column, Entity, Model};
 use entity::{bookmark, bookmark_tag};
 use migration::JoinType;
-use sea_orm::sea_query::SimpleExpr;
+use sea_orm::sea_query::{Expr, Func, IntoCondition, SimpleExpr};
 use sea_orm::ActiveValue::Set;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DbErr, EntityTrait,
@@ -11,6 +15,12 @@ use sea_orm::{
 };
 use sea_orm::{FromQueryResult, Order, Select};
 
+/// `lower("tag"."name")`: tags keep the case they are written with but are matched and
+/// deduplicated regardless of it.
+pub fn lower_name() -> SimpleExpr {
+    Func::lower(Expr::col((Entity, Column::Name))).into()
+}
+
 pub enum SortOrder {
     Name,
     Count,
@@ -19,7 +29,7 @@ pub enum SortOrder {
 impl SortOrder {
     fn add_clause(&self, select: Select<Entity>) -> Select<Entity> {
         match self {
-            SortOrder::Name => select.order_by(Column::Name, Order::Asc),
+            SortOrder::Name => select.order_by(lower_name(), Order::Asc),
             SortOrder::Count => select.order_by_desc(SimpleExpr::Custom("\"count\"".to_owned())),
         }
     }
@@ -41,17 +51,38 @@ pub struct Query;
 
 #[derive(FromQueryResult)]
 pub struct TagsAndCount {
-    pub id: i32,
     pub name: String,
     pub count: i64,
+    pub color: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
 }
 
 impl Query {
-    pub async fn find_by_name<C>(db: &C, name: &str) -> Result<Option<Model>, DbErr>
+    pub async fn find_by_name<C>(db: &C, user_id: i32, name: &str) -> Result<Option<Model>, DbErr>
     where
         C: ConnectionTrait,
     {
-        Entity::find().filter(Column::Name.eq(name)).one(db).await
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Expr::expr(lower_name()).eq(name.to_lowercase()))
+            .one(db)
+            .await
+    }
+
+    pub async fn find_by_names<C>(
+        db: &C,
+        user_id: i32,
+        names: &[String],
+    ) -> Result<Vec<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Expr::expr(lower_name()).is_in(names.iter().map(|n| n.to_lowercase())))
+            .all(db)
+            .await
     }
 
     pub async fn find_by_user_id_order_by<C>(
@@ -62,8 +93,82 @@ impl Query {
     where
         C: ConnectionTrait,
     {
-        let mut select = Entity::find()
+        order
+            .add_clause(Self::select_by_user_id(user_id))
+            .into_model::<TagsAndCount>()
+            .all(db)
+            .await
+    }
+
+    /// Returns the most used tags starting with `prefix`, whatever its case.
+    pub async fn find_by_prefix<C>(
+        db: &C,
+        user_id: Option<i32>,
+        prefix: &str,
+        limit: u64,
+    ) -> Result<Vec<TagsAndCount>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let like_expr = format!("{}%", escape_like(&prefix.to_lowercase()));
+        let select = Self::select_by_user_id(user_id)
+            .filter(Expr::expr(lower_name()).like(like_expr.as_str()))
+            .limit(limit);
+
+        SortOrder::Count
+            .add_clause(select)
+            .into_model::<TagsAndCount>()
+            .all(db)
+            .await
+    }
+
+    /// The visible tags grouped regardless of their case, with their number of bookmarks.
+    fn select_by_user_id(user_id: Option<i32>) -> Select<Entity> {
+        Entity::find()
+            .select_only()
+            // the owners of a public tag may write it differently: any of the variants is picked
+            .column_as(Column::Name.max(), "name")
             .column_as(Column::Id.count(), "count")
+            // as well as any of their metadata
+            .column_as(Column::Color.max(), "color")
+            .column_as(Column::Description.max(), "description")
+            .column_as(Column::Icon.max(), "icon")
+            .join_rev(JoinType::Join, bookmark_tag::Relation::Tag.def())
+            .join_rev(
+                JoinType::Join,
+                bookmark::Entity::belongs_to(bookmark_tag::Entity)
+                    .from(bookmark::Column::Id)
+                    .to(bookmark_tag::Column::BookmarkId)
+                    .into(),
+            )
+            .filter(match user_id {
+                // the tags owned by the user
+                Some(user_id) => Column::UserId.eq(user_id).into_condition(),
+                // the tags of public bookmarks, whoever owns them
+                None => bookmarks::Query::visible_condition(None, Filter::Public),
+            })
+            .group_by(lower_name())
+    }
+
+    /// Returns the tags found on the visible bookmarks having all the `tags`, by decreasing
+    /// number of such bookmarks.
+    pub async fn find_related<C>(
+        db: &C,
+        user_id: Option<i32>,
+        tags: &Vec<String>,
+        descendants: bool,
+        limit: u64,
+    ) -> Result<Vec<TagsAndCount>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .select_only()
+            .column_as(Column::Name.max(), "name")
+            .column_as(bookmark::Column::Id.count(), "count")
+            .column_as(Column::Color.max(), "color")
+            .column_as(Column::Description.max(), "description")
+            .column_as(Column::Icon.max(), "icon")
             .join_rev(JoinType::Join, bookmark_tag::Relation::Tag.def())
             .join_rev(
                 JoinType::Join,
@@ -73,11 +178,56 @@ impl Query {
                     .into(),
             )
             .filter(bookmarks::Query::visible_condition(user_id, Filter::All))
-            .group_by(Column::Id)
-            .group_by(Column::Name);
-        select = order.add_clause(select);
+            .filter(bookmarks::Query::tagged_condition(
+                tags,
+                descendants,
+                user_id,
+            ))
+            .filter(Expr::expr(lower_name()).is_not_in(tags.iter().map(|t| t.to_lowercase())))
+            .group_by(lower_name())
+            .order_by_desc(SimpleExpr::Custom("\"count\"".to_owned()))
+            .order_by(lower_name(), Order::Asc)
+            .limit(limit)
+            .into_model::<TagsAndCount>()
+            .all(db)
+            .await
+    }
 
-        select.into_model::<TagsAndCount>().all(db).await
+    /// Returns the tags of the user found on their bookmarks of the `domain`, by decreasing
+    /// number of such bookmarks.
+    pub async fn find_by_domain<C>(
+        db: &C,
+        user_id: i32,
+        domain: &str,
+        limit: u64,
+    ) -> Result<Vec<TagsAndCount>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        Entity::find()
+            .select_only()
+            .column_as(Column::Name.max(), "name")
+            .column_as(bookmark::Column::Id.count(), "count")
+            .column_as(Column::Color.max(), "color")
+            .column_as(Column::Description.max(), "description")
+            .column_as(Column::Icon.max(), "icon")
+            .join_rev(JoinType::Join, bookmark_tag::Relation::Tag.def())
+            .join_rev(
+                JoinType::Join,
+                bookmark::Entity::belongs_to(bookmark_tag::Entity)
+                    .from(bookmark::Column::Id)
+                    .to(bookmark_tag::Column::BookmarkId)
+                    .into(),
+            )
+            .filter(Column::UserId.eq(user_id))
+            .filter(bookmark::Column::Domain.eq(domain.to_lowercase()))
+            .group_by(lower_name())
+            .order_by_desc(SimpleExpr::Custom("\"count\"".to_owned()))
+            .order_by(lower_name(), Order::Asc)
+            .limit(limit)
+            .into_model::<TagsAndCount>()
+            .all(db)
+            .await
     }
 
     pub async fn find_by_bookmark_id<C>(db: &C, bookmark_id: i32) -> Result<Vec<Model>, DbErr>
@@ -92,19 +242,40 @@ impl Query {
     }
 }
 
+impl From<Model> for Tag {
+    fn from(value: Model) -> Self {
+        Self {
+            name: value.name,
+            color: value.color,
+            description: value.description,
+            icon: value.icon,
+        }
+    }
+}
+
 pub struct Mutation;
 
 impl Mutation {
-    pub async fn create_tag<C>(db: &C, name: String) -> Result<Model, DbErr>
+    /// Returns the user's tag with that name whatever its case, or the one it is an alias of,
+    /// creating it if needed.
+    pub async fn create_tag<C>(db: &C, user_id: i32, name: String) -> Result<Model, DbErr>
     where
         C: ConnectionTrait,
     {
-        if let Some(tag) = Query::find_by_name(db, &name).await? {
+        if let Some(alias) =
+            tag_aliases::Query::find_by_alias(db, user_id, &name.to_lowercase()).await?
+        {
+            if let Some(tag) = Entity::find_by_id(alias.tag_id).one(db).await? {
+                return Ok(tag);
+            }
+        }
+        if let Some(tag) = Query::find_by_name(db, user_id, &name).await? {
             return Ok(tag);
         }
 
         ActiveModel {
             name: Set(name),
+            user_id: Set(user_id),
             ..Default::default()
         }
         .save(db)
@@ -112,13 +283,82 @@ impl Mutation {
         .and_then(|m| m.try_into_model())
     }
 
-    pub async fn delete_orphans<C>(db: &C) -> Result<u64, DbErr>
+    pub async fn update_metadata<C>(
+        db: &C,
+        user_id: i32,
+        name: &str,
+        color: Option<String>,
+        description: Option<String>,
+        icon: Option<String>,
+    ) -> Result<Option<Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let model = Query::find_by_name(db, user_id, name)
+            .await?
+            .map(Into::<ActiveModel>::into);
+
+        if let Some(mut model) = model {
+            model.color = Set(color);
+            model.description = Set(description);
+            model.icon = Set(icon);
+
+            return Ok(Some(model.update(db).await?));
+        }
+
+        Ok(None)
+    }
+
+    /// Moves the links and aliases of the user's tags named `names` to the tag named `into`,
+    /// creating it if needed. Returns `false` when none of the tags exist. The merged tags are left orphaned.
+    pub async fn merge<C>(
+        db: &C,
+        user_id: i32,
+        names: &[String],
+        into: String,
+    ) -> Result<bool, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let sources = Query::find_by_names(db, user_id, names).await?;
+        if sources.is_empty() {
+            return Ok(false);
+        }
+
+        let mut target = Self::create_tag(db, user_id, into.clone()).await?;
+        if target.name != into && target.name.to_lowercase() == into.to_lowercase() {
+            // only the case differs: the tag is renamed as written
+            let mut model = Into::<ActiveModel>::into(target);
+            model.name = Set(into);
+            target = model.update(db).await?;
+        }
+        let mut linked = bookmarks_tags::Query::find_bookmark_ids_by_tag_id(db, target.id).await?;
+
+        for source in sources.into_iter().filter(|t| t.id != target.id) {
+            for bookmark_id in
+                bookmarks_tags::Query::find_bookmark_ids_by_tag_id(db, source.id).await?
+            {
+                if !linked.contains(&bookmark_id) {
+                    bookmarks_tags::Mutation::create_link(db, bookmark_id, target.id).await?;
+                    linked.push(bookmark_id);
+                }
+            }
+            bookmarks_tags::Mutation::delete_all_tag_links(db, source.id).await?;
+            tag_aliases::Mutation::move_all(db, source.id, target.id).await?;
+        }
+
+        Ok(true)
+    }
+
+    pub async fn delete_orphans<C>(db: &C, user_id: i32) -> Result<u64, DbErr>
     where
         C: ConnectionTrait,
     {
-        db.execute(Statement::from_string(
+        db.execute(Statement::from_sql_and_values(
             DatabaseBackend::Postgres,
-            "delete from tag where id not in (select tag_id from bookmark_tag);".to_owned(),
+            "delete from tag where user_id = $1 and id not in (select tag_id from bookmark_tag) \
+                and id not in (select tag_id from tag_alias);",
+            [user_id.into()],
         ))
         .await
         .map(|r| r.rows_affected())
@@ -1,5 +1,6 @@
 use crate::domain::tag::Tag;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 pub struct Bookmark {
     pub id: i32,
@@ -12,4 +13,53 @@ pub struct Bookmark {
     pub update_date: Option<DateTime<Utc>>,
     pub private: bool,
     pub pinned: bool,
+    pub domain: Option<String>,
+    pub metadata: Metadata,
+    pub metadata_status: Option<MetadataStatus>,
+    pub metadata_fetch_date: Option<DateTime<Utc>>,
+    pub title_snippet: Option<String>,
+    pub description_snippet: Option<String>,
+}
+
+/// What the page of a bookmark says about itself.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Metadata {
+    pub site_name: Option<String>,
+    pub author: Option<String>,
+    pub published_date: Option<DateTime<Utc>>,
+    pub language: Option<String>,
+    pub image_url: Option<String>,
+    pub favicon_url: Option<String>,
+}
+
+/// Where the bookmark stands regarding the refresh of its metadata.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MetadataStatus {
+    /// A refresh was requested and has not happened yet.
+    Pending,
+    Success,
+    Failure,
+}
+
+impl MetadataStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetadataStatus::Pending => "pending",
+            MetadataStatus::Success => "success",
+            MetadataStatus::Failure => "failure",
+        }
+    }
+}
+
+impl TryFrom<&str> for MetadataStatus {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "pending" => Ok(MetadataStatus::Pending),
+            "success" => Ok(MetadataStatus::Success),
+            "failure" => Ok(MetadataStatus::Failure),
+            _ => Err(format!("{} is not valid", value)),
+        }
+    }
 }
@@ -1 +1,6 @@
-pub type Tag = String;
+pub struct Tag {
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+}
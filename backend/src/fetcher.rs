@@ -0,0 +1,426 @@
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::{header, redirect, Client, Url};
+use std::fmt::{Display, Formatter};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The content types (or their prefixes) whose body is worth reading to extract metadata from.
+const EXTRACTED_CONTENT_TYPES: [&str; 5] = [
+    "text/html",
+    "application/xhtml+xml",
+    "application/pdf",
+    "text/plain",
+    "image/",
+];
+
+/// The number of bytes of an image read at most: enough for the headers giving its dimensions,
+/// but for the JPEGs carrying large EXIF data.
+const MAX_IMAGE_HEADER_SIZE: usize = 16 * 1024;
+
+/// The number of bytes read from the end of the PDFs larger than the body size, where their
+/// trailer is.
+const PDF_TAIL_SIZE: usize = 64 * 1024;
+
+/// What the fetcher may connect to and how much it may read.
+#[derive(Clone, Debug)]
+pub struct FetchPolicy {
+    /// Whether loopback, private, link-local and other non-public addresses may be reached.
+    pub allow_private: bool,
+    /// Hosts (and their subdomains) reachable whatever the addresses they resolve to.
+    pub allowed_hosts: Vec<String>,
+    /// Hosts (and their subdomains) never reached.
+    pub denied_hosts: Vec<String>,
+    pub max_redirects: usize,
+    /// The number of bytes of the body read at most; the rest is ignored.
+    pub max_body_size: usize,
+    pub timeout: Duration,
+}
+
+impl Default for FetchPolicy {
+    fn default() -> Self {
+        Self {
+            allow_private: false,
+            allowed_hosts: vec![],
+            denied_hosts: vec![],
+            max_redirects: 5,
+            max_body_size: 2 * 1024 * 1024,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl FetchPolicy {
+    fn is_allowed_host(&self, host: &str) -> bool {
+        matches_any(&self.allowed_hosts, host)
+    }
+
+    /// Whether the URL may be requested, as far as it can be told without resolving its host.
+    fn check_url(&self, url: &Url) -> Result<(), FetchError> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(FetchError::Forbidden(format!(
+                "scheme {} is not allowed",
+                url.scheme()
+            )));
+        }
+        let host = url
+            .host_str()
+            .map(|h| {
+                h.trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .to_lowercase()
+            })
+            .ok_or_else(|| FetchError::Forbidden("the URL has no host".to_string()))?;
+        if matches_any(&self.denied_hosts, &host) {
+            return Err(FetchError::Forbidden(format!("host {} is denied", host)));
+        }
+        // the hosts given as names are checked once resolved, the addresses are checked here as
+        // they are connected to directly
+        match host.parse::<IpAddr>() {
+            Ok(ip) if !self.is_allowed_host(&host) && !self.is_allowed_ip(ip) => Err(
+                FetchError::Forbidden(format!("address {} is not public", ip)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn is_allowed_ip(&self, ip: IpAddr) -> bool {
+        self.allow_private || is_public(ip)
+    }
+}
+
+/// Whether the host is one of the `hosts` or a subdomain of one of them.
+fn matches_any(hosts: &[String], host: &str) -> bool {
+    hosts.iter().any(|h| {
+        host == h
+            || host
+                .strip_suffix(h.as_str())
+                .map(|prefix| prefix.ends_with('.'))
+                .unwrap_or_default()
+    })
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match embedded_v4(ip) {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+/// Returns the IPv4 address an IPv6 one routes to, if any.
+fn embedded_v4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let [a, b, c, d, e, f, g, h] = ip.segments();
+    let v4 = |high: u16, low: u16| Some(Ipv4Addr::from(((high as u32) << 16) | low as u32));
+    match (a, b, c, d, e, f) {
+        // IPv4-mapped, ::ffff:0:0/96
+        (0, 0, 0, 0, 0, 0xffff) => v4(g, h),
+        // IPv4-compatible, ::/96
+        (0, 0, 0, 0, 0, 0) => v4(g, h),
+        // NAT64, 64:ff9b::/96
+        (0x64, 0xff9b, 0, 0, 0, 0) => v4(g, h),
+        // 6to4, 2002::/16
+        (0x2002, ..) => v4(b, c),
+        _ => None,
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // "this network", 0.0.0.0/8
+        || a == 0
+        // shared address space, 100.64.0.0/10
+        || (a == 100 && (b & 0xc0) == 64)
+        // benchmarking, 198.18.0.0/15
+        || (a == 198 && (b & 0xfe) == 18)
+        // reserved, 240.0.0.0/4
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // unique local, fc00::/7
+        || (first & 0xfe00) == 0xfc00
+        // link-local, fe80::/10
+        || (first & 0xffc0) == 0xfe80
+        // site-local, fec0::/10
+        || (first & 0xffc0) == 0xfec0
+        // documentation, 2001:db8::/32
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+/// Resolves the hosts, leaving out the addresses the policy does not allow: checking them here
+/// rather than before sending the request covers the redirections and prevents DNS rebinding.
+struct PolicyResolver {
+    policy: Arc<FetchPolicy>,
+}
+
+impl Resolve for PolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = self.policy.clone();
+        Box::pin(async move {
+            let host = name.as_str().to_lowercase();
+            let addrs = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .collect::<Vec<SocketAddr>>();
+
+            let addrs = match policy.is_allowed_host(&host) {
+                true => addrs,
+                false => addrs
+                    .into_iter()
+                    .filter(|addr| policy.is_allowed_ip(addr.ip()))
+                    .collect(),
+            };
+            if addrs.is_empty() {
+                return Err(Box::new(FetchError::Forbidden(format!(
+                    "host {} does not resolve to a public address",
+                    host
+                )))
+                    as Box<dyn std::error::Error + Send + Sync>);
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Forbidden(String),
+    Http(reqwest::Error),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Forbidden(reason) => write!(f, "forbidden: {}", reason),
+            FetchError::Http(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(value: reqwest::Error) -> Self {
+        FetchError::Http(value)
+    }
+}
+
+/// A fetched page.
+pub struct Page {
+    /// The URL the page was found at, after the redirections.
+    pub url: String,
+    pub content_type: Option<String>,
+    /// The charset the `Content-Type` header declares, if any.
+    pub charset: Option<String>,
+    /// The beginning of the body, when metadata can be extracted from its content type.
+    pub body: Option<Vec<u8>>,
+}
+
+impl Page {
+    fn has_content_type(&self, prefix: &str) -> bool {
+        self.content_type
+            .as_deref()
+            .map(|t| t.starts_with(prefix))
+            .unwrap_or_default()
+    }
+
+    fn is_extracted(&self) -> bool {
+        self.content_type
+            .as_deref()
+            .map(|t| EXTRACTED_CONTENT_TYPES.iter().any(|e| t.starts_with(e)))
+            .unwrap_or_default()
+    }
+}
+
+/// Fetches the user-supplied URLs according to a [`FetchPolicy`].
+#[derive(Clone)]
+pub struct Fetcher {
+    client: Client,
+    policy: Arc<FetchPolicy>,
+}
+
+impl Fetcher {
+    pub fn new(policy: FetchPolicy) -> Result<Self, FetchError> {
+        let policy = Arc::new(policy);
+        let redirect_policy = {
+            let policy = policy.clone();
+            redirect::Policy::custom(move |attempt| {
+                if attempt.previous().len() > policy.max_redirects {
+                    return attempt.error(FetchError::Forbidden("too many redirects".to_string()));
+                }
+                match policy.check_url(attempt.url()) {
+                    Ok(()) => attempt.follow(),
+                    Err(e) => attempt.error(e),
+                }
+            })
+        };
+
+        let client = Client::builder()
+            .timeout(policy.timeout)
+            .connect_timeout(policy.timeout)
+            .redirect(redirect_policy)
+            .dns_resolver(Arc::new(PolicyResolver {
+                policy: policy.clone(),
+            }))
+            // a proxy would resolve the hosts itself, bypassing the policy
+            .no_proxy()
+            .build()?;
+
+        Ok(Self { client, policy })
+    }
+
+    pub async fn fetch(&self, url: &str) -> Result<Page, FetchError> {
+        let url = Url::parse(url).map_err(|e| FetchError::Forbidden(e.to_string()))?;
+        self.policy.check_url(&url)?;
+
+        let mut response = self
+            .client
+            .get(url)
+            .header(
+                header::ACCEPT,
+                "text/html,application/xhtml+xml;q=0.9,*/*;q=0.8",
+            )
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let mut parameters = content_type.split(';').map(|p| p.trim());
+
+        let mut page = Page {
+            url: response.url().to_string(),
+            content_type: parameters
+                .next()
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_lowercase()),
+            charset: parameters
+                .filter_map(|p| p.split_once('='))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+                .map(|(_, value)| value.trim().trim_matches('"').to_string()),
+            body: None,
+        };
+
+        if page.is_extracted() {
+            let limit = match page.has_content_type("image/") {
+                true => self.policy.max_body_size.min(MAX_IMAGE_HEADER_SIZE),
+                false => self.policy.max_body_size,
+            };
+            let (mut body, truncated) = read_body(&mut response, limit).await?;
+            if truncated && page.has_content_type("application/pdf") {
+                log::info!(
+                    "Body of {} truncated to {} bytes, reading its end",
+                    page.url,
+                    limit
+                );
+                match self.fetch_tail(&page.url).await {
+                    Ok(tail) => body.extend(tail),
+                    Err(e) => log::info!("Could not read the end of {}: {}", page.url, e),
+                }
+            } else if truncated && !page.has_content_type("image/") {
+                log::info!("Body of {} truncated to {} bytes", page.url, limit);
+            }
+            page.body = Some(body);
+        }
+
+        Ok(page)
+    }
+
+    /// Reads the last bytes of a document, when the server supports range requests.
+    async fn fetch_tail(&self, url: &str) -> Result<Vec<u8>, FetchError> {
+        let url = Url::parse(url).map_err(|e| FetchError::Forbidden(e.to_string()))?;
+        self.policy.check_url(&url)?;
+
+        let mut response = self
+            .client
+            .get(url)
+            .header(header::RANGE, format!("bytes=-{}", PDF_TAIL_SIZE))
+            .send()
+            .await?
+            .error_for_status()?;
+        // a server ignoring the range sends the beginning of the document again
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Ok(vec![]);
+        }
+
+        Ok(read_body(&mut response, PDF_TAIL_SIZE).await?.0)
+    }
+}
+
+/// Reads at most `limit` bytes of the body, telling whether it reached the limit.
+async fn read_body(
+    response: &mut reqwest::Response,
+    limit: usize,
+) -> Result<(Vec<u8>, bool), FetchError> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let remaining = limit - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        if body.len() >= limit {
+            return Ok((body, true));
+        }
+    }
+    Ok((body, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_public;
+    use std::net::IpAddr;
+
+    #[test]
+    fn public_addresses() {
+        let cases = [
+            ("93.184.216.34", true),
+            ("10.0.0.1", false),
+            ("127.0.0.1", false),
+            ("169.254.169.254", false),
+            ("100.64.0.1", false),
+            ("0.1.2.3", false),
+            ("2606:2800:220:1:248:1893:25c8:1946", true),
+            ("::1", false),
+            ("::", false),
+            ("fd00::1", false),
+            ("fe80::1", false),
+            ("fec0::1", false),
+            ("2001:db8::1", false),
+            // IPv4-mapped
+            ("::ffff:93.184.216.34", true),
+            ("::ffff:127.0.0.1", false),
+            // IPv4-compatible
+            ("::93.184.216.34", true),
+            ("::10.0.0.1", false),
+            // NAT64
+            ("64:ff9b::93.184.216.34", true),
+            ("64:ff9b::127.0.0.1", false),
+            ("64:ff9b::a9fe:a9fe", false),
+            // 6to4
+            ("2002:5db8:d822::1", true),
+            ("2002:7f00:1::1", false),
+            ("2002:c0a8:101::1", false),
+        ];
+
+        for (ip, expected) in cases {
+            assert_eq!(is_public(ip.parse::<IpAddr>().unwrap()), expected, "{}", ip);
+        }
+    }
+}
@@ -1,11 +1,21 @@
+use crate::cache::MetadataCache;
+use crate::fetcher::Fetcher;
 use crate::mailer::Mailer;
+use crate::query_params::Registry;
+use crate::refresher::Refresher;
 use reqwest::Client;
 use sea_orm::DatabaseConnection;
 
+pub mod cache;
 pub mod database;
 pub mod domain;
+pub mod fetcher;
 pub mod mailer;
+pub mod metadata;
+pub mod query_params;
+pub mod refresher;
 pub mod rest;
+pub mod rules;
 pub mod sessions;
 pub mod url;
 
@@ -13,7 +23,12 @@ pub mod url;
 pub struct AppState {
     pub database: DatabaseConnection,
     pub mailer: Mailer,
-    pub ignored_query_params: Vec<&'static str>,
+    pub query_params: Registry,
+    /// The usernames of the administrators of the instance.
+    pub admins: Vec<String>,
     pub http_client: Client,
+    pub fetcher: Fetcher,
+    pub metadata_cache: MetadataCache,
+    pub refresher: Refresher,
     pub demo: bool,
 }
@@ -4,8 +4,12 @@ use axum::routing::get;
 use axum::Router;
 use axum_sessions::async_session::base64;
 use axum_sessions::{PersistencePolicy, SameSite, SessionLayer};
+use backend::cache::MetadataCache;
 use backend::database::Configuration;
+use backend::fetcher::{FetchPolicy, Fetcher};
 use backend::mailer::{LogSender, MailSender, Mailer, Sendmail};
+use backend::query_params::Registry;
+use backend::refresher::{RefreshConfiguration, Refresher};
 use backend::rest::api_router;
 use backend::sessions::RedisStore;
 use backend::{database, AppState};
@@ -16,6 +20,7 @@ use reqwest::Client;
 use sea_orm_migration::MigratorTrait;
 use secrecy::{ExposeSecret, SecretVec};
 use std::env;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::thread::sleep;
 use std::time::Duration;
@@ -33,8 +38,6 @@ use tracing_subscriber::util::SubscriberInitExt;
 const STATIC_DIR: include_dir::Dir<'_> =
     include_dir::include_dir!("$CARGO_MANIFEST_DIR/../target/release/wasm");
 
-const IGNORED_GET_PARAMS: &str = include_str!("query-params-registry.txt");
-
 // todo better logging
 
 #[tokio::main]
@@ -99,6 +102,57 @@ async fn main() {
     let session_ttl = env::var("SESSION_TTL")
         .map(|s| Duration::from_secs(u64::from_str(&s).unwrap_or(60 * 60 * 24)))
         .unwrap_or(Duration::from_secs(60 * 60 * 24));
+    let metadata_cache_ttl = env::var("METADATA_CACHE_TTL")
+        .map(|s| Duration::from_secs(u64::from_str(&s).unwrap_or(60 * 60 * 24)))
+        .unwrap_or(Duration::from_secs(60 * 60 * 24));
+    let metadata_cache_failure_ttl = env::var("METADATA_CACHE_FAILURE_TTL")
+        .map(|s| Duration::from_secs(u64::from_str(&s).unwrap_or(60 * 5)))
+        .unwrap_or(Duration::from_secs(60 * 5));
+    let metadata_refresh_interval = env::var("METADATA_REFRESH_INTERVAL")
+        .map(|s| Duration::from_secs(u64::from_str(&s).unwrap_or(60 * 5)))
+        .unwrap_or(Duration::from_secs(60 * 5));
+    let metadata_refresh_max_age = env::var("METADATA_REFRESH_MAX_AGE")
+        .map(|s| Duration::from_secs(u64::from_str(&s).unwrap_or_default()))
+        .unwrap_or_default();
+    let fetch_policy = {
+        let default = FetchPolicy::default();
+        let hosts = |name: &str| {
+            env::var(name)
+                .map(|v| {
+                    v.split(',')
+                        .map(|h| h.trim().to_lowercase())
+                        .filter(|h| !h.is_empty())
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default()
+        };
+        FetchPolicy {
+            allow_private: env::var("FETCH_ALLOW_PRIVATE")
+                .map(|v| bool::from_str(&v).unwrap_or_default())
+                .unwrap_or(default.allow_private),
+            allowed_hosts: hosts("FETCH_ALLOWED_HOSTS"),
+            denied_hosts: hosts("FETCH_DENIED_HOSTS"),
+            max_redirects: env::var("FETCH_MAX_REDIRECTS")
+                .map(|v| usize::from_str(&v).unwrap_or(default.max_redirects))
+                .unwrap_or(default.max_redirects),
+            max_body_size: env::var("FETCH_MAX_BODY_SIZE")
+                .map(|v| usize::from_str(&v).unwrap_or(default.max_body_size))
+                .unwrap_or(default.max_body_size),
+            timeout: default.timeout,
+        }
+    };
+    let query_params_registry = env::var("QUERY_PARAMS_REGISTRY")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from);
+    let admins = env::var("ADMINS")
+        .map(|v| {
+            v.split(',')
+                .map(|u| u.trim().to_string())
+                .filter(|u| !u.is_empty())
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
     let demo = env::var("DEMO")
         .map(|v| bool::from_str(&v).unwrap_or_default())
         .unwrap_or_default();
@@ -130,16 +184,37 @@ async fn main() {
         .await
         .expect("Could not migrate database");
 
-    let session_store = RedisStore::new(
-        redis::Client::open(format!(
-            "redis://{}:{}/{}",
-            redis_host, redis_port, redis_db
-        ))
-        .unwrap()
-        .get_multiplexed_tokio_connection()
-        .await
-        .unwrap(),
-        session_ttl,
+    let redis_connection = redis::Client::open(format!(
+        "redis://{}:{}/{}",
+        redis_host, redis_port, redis_db
+    ))
+    .unwrap()
+    .get_multiplexed_tokio_connection()
+    .await
+    .unwrap();
+
+    let session_store = RedisStore::new(redis_connection.clone(), session_ttl);
+
+    let metadata_cache = MetadataCache::new(
+        redis_connection,
+        metadata_cache_ttl,
+        metadata_cache_failure_ttl,
+    );
+
+    let query_params =
+        Registry::load(query_params_registry).expect("Could not load query params registry");
+    tokio::spawn(reload_on_hangup(query_params.clone()));
+
+    let fetcher = Fetcher::new(fetch_policy).expect("Could not initialize the page fetcher");
+
+    let refresher = Refresher::start(
+        database.clone(),
+        fetcher.clone(),
+        metadata_cache.clone(),
+        RefreshConfiguration {
+            interval: metadata_refresh_interval,
+            max_age: (!metadata_refresh_max_age.is_zero()).then_some(metadata_refresh_max_age),
+        },
     );
 
     let configuration = backend::rest::Configuration {
@@ -178,15 +253,16 @@ async fn main() {
                 AppState {
                     database,
                     mailer: mailer.clone(),
-                    ignored_query_params: IGNORED_GET_PARAMS
-                        .split('\n')
-                        .filter(|s| !s.is_empty())
-                        .collect::<Vec<&str>>(),
+                    query_params,
+                    admins,
                     http_client: Client::builder()
                         .timeout(Duration::from_secs(5))
                         .connect_timeout(Duration::from_secs(5))
                         .build()
                         .expect("Could not initialize HTTP client"),
+                    fetcher,
+                    metadata_cache,
+                    refresher,
                     demo,
                 },
             )
@@ -352,6 +428,24 @@ async fn health() -> impl IntoResponse {
     )
 }
 
+/// Reloads the query params registry whenever the process receives `SIGHUP`.
+async fn reload_on_hangup(registry: Registry) {
+    #[cfg(unix)]
+    {
+        let mut hangup = signal::unix::signal(signal::unix::SignalKind::hangup())
+            .expect("failed to install signal handler");
+        while hangup.recv().await.is_some() {
+            match registry.reload() {
+                Ok(count) => log::info!("Query params registry reloaded: {} rules", count),
+                Err(e) => log::error!("Could not reload query params registry: {}", e),
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = registry;
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
@@ -0,0 +1,317 @@
+use crate::domain::bookmark::Metadata;
+use crate::fetcher::{Fetcher, Page};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+use webpage::HTML;
+
+mod charset;
+mod documents;
+
+/// The `<meta>` elements whose content is a comma-separated list of keywords.
+const KEYWORDS_META: [&str; 3] = ["keywords", "news_keywords", "article:tag"];
+
+/// The outcome of fetching an URL.
+#[derive(Serialize, Deserialize)]
+pub struct Fetched {
+    /// The URL the page was found at after the redirections, the one requested when it could
+    /// not be fetched.
+    pub url: String,
+    /// The metadata of the page, `None` when it could not be fetched or nothing could be
+    /// extracted from its content type.
+    pub page: Option<PageMetadata>,
+    /// Whether the page could not be fetched at all.
+    pub failed: bool,
+}
+
+/// Fetches the page and extracts its metadata.
+pub async fn fetch(fetcher: &Fetcher, url: String) -> Fetched {
+    let page = match fetcher.fetch(&url).await {
+        Ok(page) => page,
+        Err(e) => {
+            log::error!("Could not fetch {}: {}", url, e);
+            return Fetched {
+                url,
+                page: None,
+                failed: true,
+            };
+        }
+    };
+
+    let metadata = PageMetadata::from_page(&page);
+    if metadata.is_none() {
+        log::info!(
+            "No metadata extracted from {}: content type is {}",
+            page.url,
+            page.content_type.as_deref().unwrap_or("unknown")
+        );
+    }
+    Fetched {
+        url: page.url,
+        page: metadata,
+        failed: false,
+    }
+}
+
+/// What a page says about itself, merged from its HTML head, OpenGraph and Twitter-card
+/// properties and its JSON-LD data, in that order of preference.
+#[derive(Default, Serialize, Deserialize)]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub canonical_url: Option<String>,
+    pub kind: Option<String>,
+    pub keywords: Vec<String>,
+    pub metadata: Metadata,
+}
+
+impl PageMetadata {
+    /// Extracts the metadata of a fetched page according to its content type.
+    pub fn from_page(page: &Page) -> Option<Self> {
+        let body = page.body.as_deref()?;
+        match page.content_type.as_deref()? {
+            "application/pdf" => Some(documents::pdf(body)),
+            "text/plain" => Some(documents::text(&charset::decode(
+                body,
+                page.charset.as_deref(),
+                false,
+            ))),
+            image if image.starts_with("image/") => Some(documents::image(
+                crate::url::file_name(&page.url),
+                image,
+                body,
+            )),
+            _ => {
+                let raw = charset::decode(body, page.charset.as_deref(), true);
+                match HTML::from_string(raw.clone(), None) {
+                    Ok(html) => Some(Self::extract(&page.url, &raw, &html)),
+                    Err(e) => {
+                        log::error!("{:?}", e);
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    /// Extracts the metadata of the page found at `url`; `raw` is its unparsed content, where
+    /// the favicon is looked for.
+    pub fn extract(url: &str, raw: &str, html: &HTML) -> Self {
+        let base = Url::parse(url).ok();
+        let absolute = |href: String| match &base {
+            Some(base) => base.join(&href).map(|u| u.to_string()).unwrap_or(href),
+            None => href,
+        };
+        let meta = |name: &str| {
+            html.meta
+                .get(name)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let og = |name: &str| {
+            html.opengraph
+                .properties
+                .get(name)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let ld = |key: &str| {
+            html.schema_org
+                .iter()
+                .find_map(|schema| ld_string(&schema.value[key]))
+        };
+
+        let title = html
+            .title
+            .clone()
+            .or_else(|| og("title"))
+            .or_else(|| meta("twitter:title"))
+            .or_else(|| ld("headline"))
+            .or_else(|| ld("name"));
+        let description = html
+            .description
+            .clone()
+            .or_else(|| og("description"))
+            .or_else(|| meta("twitter:description"))
+            .or_else(|| ld("description"));
+        let canonical_url = html.url.clone().or_else(|| og("url")).map(absolute);
+
+        let site_name = og("site_name")
+            .or_else(|| {
+                html.schema_org
+                    .iter()
+                    .find_map(|schema| ld_string(&schema.value["publisher"]))
+            })
+            .or_else(|| meta("application-name"))
+            .or_else(|| meta("twitter:site"));
+        let author = meta("author")
+            .or_else(|| meta("article:author"))
+            .or_else(|| ld("author"))
+            .or_else(|| meta("twitter:creator"));
+        let published_date = meta("article:published_time")
+            .or_else(|| ld("datePublished"))
+            .or_else(|| meta("date"))
+            .or_else(|| meta("dc.date"))
+            .and_then(|d| parse_date(&d));
+        let language = html
+            .language
+            .clone()
+            .or_else(|| og("locale").map(|l| l.replace('_', "-")))
+            .or_else(|| ld("inLanguage"));
+        let image_url = html
+            .opengraph
+            .images
+            .first()
+            .map(|i| i.url.clone())
+            .or_else(|| meta("twitter:image"))
+            .or_else(|| meta("twitter:image:src"))
+            .or_else(|| ld("image"))
+            .map(absolute);
+        let favicon_url = favicon(raw)
+            .or_else(|| Some("/favicon.ico".to_string()))
+            .filter(|_| base.is_some())
+            .map(absolute);
+
+        Self {
+            title,
+            description,
+            canonical_url,
+            // webpage defaults the OpenGraph type to `website`: only the declared one is kept
+            kind: meta("og:type"),
+            keywords: keywords(html),
+            metadata: Metadata {
+                site_name,
+                author,
+                published_date,
+                language,
+                image_url,
+                favicon_url,
+            },
+        }
+    }
+}
+
+/// Returns the keywords the page is declared with.
+fn keywords(html: &HTML) -> Vec<String> {
+    KEYWORDS_META
+        .iter()
+        .filter_map(|name| html.meta.get(*name))
+        .chain(html.opengraph.properties.get("article:tag"))
+        .flat_map(|content| content.split(','))
+        .map(|keyword| keyword.trim().to_string())
+        .filter(|keyword| !keyword.is_empty())
+        .collect()
+}
+
+/// Reads a JSON-LD value that may be a string, an object with a name or an URL, or a list of
+/// those.
+fn ld_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Array(values) => values.iter().find_map(ld_string),
+        Value::Object(object) => object
+            .get("name")
+            .or_else(|| object.get("url"))
+            .and_then(ld_string),
+        _ => None,
+    }
+}
+
+/// Parses the dates pages use: RFC 3339 date times and ISO 8601 dates.
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+                .map(|d| Utc.from_utc_datetime(&d))
+        })
+}
+
+/// Returns the `href` of the first `<link rel="icon">` (or `shortcut icon`, `apple-touch-icon`)
+/// of the page.
+fn favicon(raw: &str) -> Option<String> {
+    static LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<link\s[^>]*>").unwrap());
+    static REL: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r#"(?i)\srel\s*=\s*["']?([^"'>]+)"#).unwrap());
+    static HREF: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r#"(?i)\shref\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).unwrap());
+
+    let icon = LINK
+        .find_iter(raw)
+        .map(|m| m.as_str())
+        .filter(|tag| {
+            REL.captures(tag)
+                .map(|c| {
+                    c[1].split_whitespace()
+                        .any(|r| r.eq_ignore_ascii_case("icon"))
+                        || c[1].eq_ignore_ascii_case("apple-touch-icon")
+                })
+                .unwrap_or_default()
+        })
+        .find_map(|tag| {
+            HREF.captures(tag).and_then(|c| {
+                c.get(1)
+                    .or_else(|| c.get(2))
+                    .or_else(|| c.get(3))
+                    .map(|m| m.as_str().trim().to_string())
+            })
+        })
+        .filter(|href| !href.is_empty());
+    icon
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PageMetadata;
+    use crate::fetcher::Page;
+
+    fn title(body: &[u8], charset: Option<&str>) -> Option<String> {
+        PageMetadata::from_page(&Page {
+            url: "https://example.com/".to_string(),
+            content_type: Some("text/html".to_string()),
+            charset: charset.map(|c| c.to_string()),
+            body: Some(body.to_vec()),
+        })
+        .and_then(|page| page.title)
+    }
+
+    #[test]
+    fn page_charsets() {
+        let cases: [(&str, &[u8], Option<&str>, &str); 4] = [
+            (
+                "meta charset",
+                include_bytes!("../test-data/charset/windows-1252-meta.html"),
+                None,
+                "Cr\u{e8}me br\u{fb}l\u{e9}e \u{2013} 5 \u{20ac}",
+            ),
+            (
+                "meta http-equiv",
+                include_bytes!("../test-data/charset/shift_jis-http-equiv.html"),
+                None,
+                "\u{65e5}\u{672c}\u{8a9e}\u{306e}\u{30da}\u{30fc}\u{30b8}",
+            ),
+            (
+                "header over meta",
+                include_bytes!("../test-data/charset/euc-kr-header.html"),
+                Some("EUC-KR"),
+                "\u{d55c}\u{ad6d}\u{c5b4} \u{d398}\u{c774}\u{c9c0}",
+            ),
+            (
+                "BOM over header and meta",
+                include_bytes!("../test-data/charset/utf-8-bom.html"),
+                Some("windows-1252"),
+                "\u{c7}a marche \u{2013} \u{fc}n\u{ef}c\u{f6}d\u{e9} \u{2713}",
+            ),
+        ];
+
+        for (case, body, charset, expected) in cases {
+            assert_eq!(title(body, charset).as_deref(), Some(expected), "{}", case);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use once_cell::sync::Lazy;
+use regex::bytes::Regex;
+
+/// The number of bytes looked at for a `<meta>` declaring the charset, as browsers do.
+const META_SNIFFING_LENGTH: usize = 1024;
+
+/// Decodes a document with the first charset found in its byte order mark, the `charset` of
+/// its `Content-Type` header and, for HTML, its `<meta>` elements. Documents declaring none are
+/// read as UTF-8, or as Windows-1252 when they are not valid UTF-8.
+pub fn decode(body: &[u8], charset: Option<&str>, html: bool) -> String {
+    let encoding = Encoding::for_bom(body)
+        .map(|(encoding, _)| encoding)
+        .or_else(|| charset.and_then(|c| Encoding::for_label(c.as_bytes())))
+        .or_else(|| html.then(|| meta_charset(body)).flatten())
+        .unwrap_or_else(|| match std::str::from_utf8(body) {
+            Ok(_) => UTF_8,
+            // the body may be truncated in the middle of a character
+            Err(e) if e.error_len().is_none() => UTF_8,
+            Err(_) => WINDOWS_1252,
+        });
+
+    // the BOM, if any, is removed
+    let (text, encoding, malformed) = encoding.decode(body);
+    if malformed {
+        log::info!("Some characters are not valid {}", encoding.name());
+    }
+    text.into_owned()
+}
+
+/// Returns the encoding declared by `<meta charset="…">` or
+/// `<meta http-equiv="Content-Type" content="…; charset=…">`.
+fn meta_charset(body: &[u8]) -> Option<&'static Encoding> {
+    let head = &body[..body.len().min(META_SNIFFING_LENGTH)];
+    static META_CHARSET: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r#"(?i)<meta\s[^>]*charset\s*=\s*["']?\s*([a-z0-9_:.\-]+)"#).unwrap()
+    });
+
+    META_CHARSET
+        .captures(head)
+        .and_then(|c| Encoding::for_label(&c[1]))
+        // a page declaring itself UTF-16 in ASCII-compatible bytes is not UTF-16
+        .map(|encoding| encoding.output_encoding())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode;
+
+    #[test]
+    fn meta_charset() {
+        let body = b"<html><head><meta charset=\"windows-1252\"></head><body>caf\xe9</body></html>";
+        assert!(decode(body, None, true).contains("caf\u{e9}"));
+    }
+
+    #[test]
+    fn meta_http_equiv() {
+        let body = b"<html><head>\
+            <meta http-equiv=\"Content-Type\" content=\"text/html; charset=Shift_JIS\">\
+            </head><body>\x93\xfa\x96\x7b</body></html>";
+        assert!(decode(body, None, true).contains("\u{65e5}\u{672c}"));
+    }
+
+    #[test]
+    fn header_charset_overrides_meta() {
+        let body = b"<html><head><meta charset=\"utf-8\"></head><body>\xa4</body></html>";
+        assert!(decode(body, Some("iso-8859-15"), true).contains("<body>\u{20ac}</body>"));
+    }
+
+    #[test]
+    fn utf8_bom() {
+        let body = b"\xef\xbb\xbfcaf\xc3\xa9";
+        assert_eq!(decode(body, Some("windows-1252"), false), "caf\u{e9}");
+    }
+
+    #[test]
+    fn utf16le_bom() {
+        let body = b"\xff\xfeh\x00\xe9\x00";
+        assert_eq!(decode(body, None, false), "h\u{e9}");
+    }
+
+    #[test]
+    fn invalid_utf8_falls_back_to_windows_1252() {
+        assert_eq!(
+            decode(b"caf\xe9 cr\xe8me", None, false),
+            "caf\u{e9} cr\u{e8}me"
+        );
+    }
+
+    #[test]
+    fn truncated_multibyte_character() {
+        assert_eq!(decode(b"caf\xc3", None, false), "caf\u{fffd}");
+    }
+}
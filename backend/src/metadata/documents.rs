@@ -0,0 +1,640 @@
+use super::{parse_date, PageMetadata};
+use crate::domain::bookmark::Metadata;
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use regex::bytes::Regex;
+
+/// The number of characters of a text document's first line kept as its title.
+const MAX_TEXT_TITLE_LENGTH: usize = 200;
+
+static XMP_LIST_ITEM: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<rdf:li[^>]*>(.*?)</rdf:li>").unwrap());
+static XMP_CREATE_DATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<xmp:CreateDate>([^<]+)</xmp:CreateDate>").unwrap());
+static PDF_LANGUAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r"/Lang\s*\(([^)]*)\)").unwrap());
+static PDF_INFO_REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"/Info\s+(\d+)\s+(\d+)\s+R").unwrap());
+static PDF_OBJECT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|\s)(\d+)\s+(\d+)\s+obj\b").unwrap());
+static PDF_END_OBJECT: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bendobj\b").unwrap());
+static PDF_STRING_ENTRY: Lazy<Regex> = Lazy::new(|| Regex::new(r"/([A-Za-z]+)\s*([(<])").unwrap());
+
+/// Reads the document information dictionary of a PDF, falling back to its XMP metadata.
+pub fn pdf(body: &[u8]) -> PageMetadata {
+    let info = pdf_info(body);
+    let info_string = |key: &str| info.and_then(|info| pdf_entry(info, key));
+    let xmp = |element: &str| {
+        let start = find(body, format!("<{}>", element).as_bytes())?;
+        let content = &body[start..];
+        let end = find(content, format!("</{}>", element).as_bytes())?;
+        XMP_LIST_ITEM
+            .captures(&content[..end])
+            .map(|c| xml_text(&c[1]))
+            .filter(|s| !s.is_empty())
+    };
+
+    let title = info_string("Title").or_else(|| xmp("dc:title"));
+    let author = info_string("Author").or_else(|| xmp("dc:creator"));
+    let description = info_string("Subject").or_else(|| xmp("dc:description"));
+    let published_date = info_string("CreationDate")
+        .and_then(|d| pdf_date(&d))
+        .or_else(|| {
+            XMP_CREATE_DATE
+                .captures(body)
+                .and_then(|c| parse_date(&String::from_utf8_lossy(&c[1])))
+        });
+    let language = PDF_LANGUAGE
+        .captures(body)
+        .map(|c| String::from_utf8_lossy(&c[1]).trim().to_string())
+        .filter(|l| !l.is_empty());
+
+    PageMetadata {
+        title,
+        description,
+        metadata: Metadata {
+            author,
+            published_date,
+            language,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Returns the content of the object the trailer designates as the document information
+/// dictionary. It cannot be found when it is stored in a compressed object stream, nor when the
+/// PDF is larger than the body the fetcher reads and the object is neither at its beginning nor
+/// in its last bytes.
+fn pdf_info(body: &[u8]) -> Option<&[u8]> {
+    // after incremental updates, the last trailer is the one in effect
+    let reference = PDF_INFO_REFERENCE.captures_iter(body).last()?;
+    let object = PDF_OBJECT
+        .captures_iter(body)
+        .find(|c| c[1] == reference[1] && c[2] == reference[2])?
+        .get(0)?;
+    let content = &body[object.end()..];
+    let end = PDF_END_OBJECT
+        .find(content)
+        .map(|m| m.start())
+        .unwrap_or(content.len());
+
+    Some(&content[..end])
+}
+
+/// Reads the string value of an entry of a PDF dictionary.
+fn pdf_entry(dictionary: &[u8], key: &str) -> Option<String> {
+    let start = PDF_STRING_ENTRY
+        .captures_iter(dictionary)
+        .find(|c| &c[1] == key.as_bytes())?
+        .get(2)?;
+    let value = &dictionary[start.end()..];
+    let bytes = match start.as_bytes() {
+        b"(" => pdf_literal_string(value),
+        _ => pdf_hex_string(value),
+    };
+
+    Some(pdf_text(&bytes).trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// Reads a literal string, `value` starting right after its opening parenthesis.
+fn pdf_literal_string(value: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut depth = 1;
+    let mut iter = value.iter().copied().peekable();
+
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'(' => {
+                depth += 1;
+                bytes.push(byte);
+            }
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                bytes.push(byte);
+            }
+            b'\\' => match iter.next() {
+                Some(b'n') => bytes.push(b'\n'),
+                Some(b'r') => bytes.push(b'\r'),
+                Some(b't') => bytes.push(b'\t'),
+                Some(b'b') => bytes.push(0x08),
+                Some(b'f') => bytes.push(0x0c),
+                Some(digit @ b'0'..=b'7') => {
+                    let mut code = u32::from(digit - b'0');
+                    for _ in 0..2 {
+                        match iter.peek() {
+                            Some(digit @ b'0'..=b'7') => {
+                                code = code * 8 + u32::from(digit - b'0');
+                                iter.next();
+                            }
+                            _ => break,
+                        }
+                    }
+                    bytes.push(code as u8);
+                }
+                // a backslash at the end of a line continues the string on the next one
+                Some(b'\r') => {
+                    if iter.peek() == Some(&b'\n') {
+                        iter.next();
+                    }
+                }
+                Some(b'\n') => {}
+                Some(other) => bytes.push(other),
+                None => break,
+            },
+            _ => bytes.push(byte),
+        }
+    }
+
+    bytes
+}
+
+/// Reads a hexadecimal string, `value` starting right after its opening angle bracket.
+fn pdf_hex_string(value: &[u8]) -> Vec<u8> {
+    let digits = value
+        .iter()
+        .take_while(|b| **b != b'>')
+        .filter_map(|b| (*b as char).to_digit(16))
+        .map(|d| d as u8)
+        .collect::<Vec<u8>>();
+
+    // a missing final digit is assumed to be 0
+    digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or_default())
+        .collect()
+}
+
+/// Decodes a PDF text string: UTF-16BE or UTF-8 when it starts with their byte order mark, and
+/// PDFDocEncoding, read as Latin-1, otherwise.
+fn pdf_text(bytes: &[u8]) -> String {
+    match bytes {
+        [0xfe, 0xff, rest @ ..] => String::from_utf16_lossy(
+            &rest
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect::<Vec<u16>>(),
+        ),
+        [0xef, 0xbb, 0xbf, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        _ => bytes.iter().map(|b| *b as char).collect(),
+    }
+}
+
+/// Parses a PDF date, `D:YYYYMMDDHHmmSSOHH'mm`, where everything after the year is optional.
+fn pdf_date(date: &str) -> Option<DateTime<Utc>> {
+    let date = date.trim().trim_start_matches("D:");
+    let digits = date
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    let field = |from: usize, default: u32| {
+        digits
+            .get(from..from + 2)
+            .and_then(|d| d.parse::<u32>().ok())
+            .unwrap_or(default)
+    };
+
+    let naive = NaiveDate::from_ymd_opt(digits.get(..4)?.parse().ok()?, field(4, 1), field(6, 1))?
+        .and_hms_opt(field(8, 0), field(10, 0), field(12, 0))?;
+
+    let offset = &date[digits.len()..];
+    let seconds = match offset.chars().next() {
+        Some(sign @ ('+' | '-')) => {
+            let numbers = offset[1..]
+                .split('\'')
+                .filter_map(|n| n.parse::<i32>().ok())
+                .collect::<Vec<i32>>();
+            let seconds = numbers.first().copied().unwrap_or_default() * 3600
+                + numbers.get(1).copied().unwrap_or_default() * 60;
+            match sign {
+                '-' => -seconds,
+                _ => seconds,
+            }
+        }
+        _ => 0,
+    };
+
+    FixedOffset::east_opt(seconds)?
+        .from_local_datetime(&naive)
+        .single()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Unescapes the text content of an XML element.
+fn xml_text(text: &[u8]) -> String {
+    String::from_utf8_lossy(text)
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Describes an image by its format and, when they can be read from its header, its
+/// dimensions; its title is its file name.
+pub fn image(file_name: Option<String>, content_type: &str, body: &[u8]) -> PageMetadata {
+    let format = content_type
+        .trim_start_matches("image/")
+        .split('+')
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("x-")
+        .to_uppercase();
+    let description = match image_dimensions(body) {
+        Some((width, height)) => format!("{} image, {} \u{00d7} {} pixels", format, width, height),
+        None => format!("{} image", format),
+    };
+
+    PageMetadata {
+        title: file_name,
+        description: Some(description),
+        ..Default::default()
+    }
+}
+
+/// Reads the width and height of PNG, GIF, JPEG, WebP and BMP images.
+fn image_dimensions(body: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes(body.get(at..at + 2)?.try_into().ok()?) as u32);
+    let le16 = |at: usize| Some(u16::from_le_bytes(body.get(at..at + 2)?.try_into().ok()?) as u32);
+    let be32 = |at: usize| Some(u32::from_be_bytes(body.get(at..at + 4)?.try_into().ok()?));
+    let le32 = |at: usize| Some(u32::from_le_bytes(body.get(at..at + 4)?.try_into().ok()?));
+    let le24 = |at: usize| {
+        let bytes = body.get(at..at + 3)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+    };
+
+    if body.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some((be32(16)?, be32(20)?));
+    }
+    if body.starts_with(b"GIF8") {
+        return Some((le16(6)?, le16(8)?));
+    }
+    if body.starts_with(b"BM") {
+        return Some((le32(18)?, (le32(22)? as i32).unsigned_abs()));
+    }
+    if body.starts_with(b"RIFF") && body.get(8..12) == Some(b"WEBP") {
+        return match body.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+            b"VP8L" => {
+                let bits = le32(21)?;
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        };
+    }
+    if body.starts_with(&[0xff, 0xd8]) {
+        // the dimensions are in the first start-of-frame segment
+        let mut at = 2;
+        while *body.get(at)? == 0xff {
+            let marker = *body.get(at + 1)?;
+            if (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker) {
+                return Some((be16(at + 7)?, be16(at + 5)?));
+            }
+            at += 2 + be16(at + 2)? as usize;
+        }
+    }
+
+    None
+}
+
+/// Uses the first non-empty line of a text document as its title.
+pub fn text(body: &str) -> PageMetadata {
+    let title = body
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.chars().take(MAX_TEXT_TITLE_LENGTH).collect());
+
+    PageMetadata {
+        title,
+        ..Default::default()
+    }
+}
+
+/// Returns the position of the first occurrence of `needle`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{image_dimensions, pdf, pdf_date, pdf_entry, pdf_hex_string, pdf_info};
+    use super::{pdf_literal_string, pdf_text};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn pdf_literal_strings() {
+        let cases: [(&[u8], &[u8]); 13] = [
+            (b"Hello) rest", b"Hello"),
+            (b"a (nested (twice)) b) rest", b"a (nested (twice)) b"),
+            (b"a\\(b\\)c)", b"a(b)c"),
+            (b"line\\nbreak\\ttab\\\\)", b"line\nbreak\ttab\\"),
+            (b"\\r\\b\\f)", b"\r\x08\x0c"),
+            (b"\\101\\102C)", b"ABC"),
+            (b"\\0531)", b"+1"),
+            (b"\\7)", b"\x07"),
+            (b"split\\\nline)", b"splitline"),
+            (b"split\\\r\nline)", b"splitline"),
+            (b"unknown \\q)", b"unknown q"),
+            (b"unterminated", b"unterminated"),
+            (b"trailing \\", b"trailing "),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(
+                pdf_literal_string(value),
+                expected,
+                "{}",
+                String::from_utf8_lossy(value)
+            );
+        }
+    }
+
+    #[test]
+    fn pdf_hex_strings() {
+        let cases: [(&[u8], &[u8]); 6] = [
+            (b"48656C6C6F> rest", b"Hello"),
+            (b"48 65\n6c> rest", b"Hel"),
+            // a missing final digit is 0
+            (b"414>", b"\x41\x40"),
+            (b"4x1>", b"\x41"),
+            (b">", b""),
+            (b"4142", b"AB"),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(
+                pdf_hex_string(value),
+                expected,
+                "{}",
+                String::from_utf8_lossy(value)
+            );
+        }
+    }
+
+    #[test]
+    fn pdf_text_strings() {
+        let cases: [(&[u8], &str); 6] = [
+            (b"\xfe\xff\x00H\x00i", "Hi"),
+            (b"\xfe\xff\x00H\x00", "H"),
+            (b"\xfe\xff\xd8\x3d\xde\x00", "\u{1f600}"),
+            (b"\xef\xbb\xbfcaf\xc3\xa9", "caf\u{e9}"),
+            (b"caf\xe9", "caf\u{e9}"),
+            (b"", ""),
+        ];
+
+        for (bytes, expected) in cases {
+            assert_eq!(pdf_text(bytes), expected, "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn pdf_entries() {
+        let dictionary = b"<< /Title <FEFF00480069> /Author (J\\351r\\364me) /Subject ( ) \
+            /Keywords <414> >>";
+
+        assert_eq!(pdf_entry(dictionary, "Title").as_deref(), Some("Hi"));
+        assert_eq!(
+            pdf_entry(dictionary, "Author").as_deref(),
+            Some("J\u{e9}r\u{f4}me")
+        );
+        assert_eq!(pdf_entry(dictionary, "Subject"), None);
+        assert_eq!(pdf_entry(dictionary, "Keywords").as_deref(), Some("A@"));
+        assert_eq!(pdf_entry(dictionary, "Creator"), None);
+    }
+
+    #[test]
+    fn pdf_dates() {
+        let date = |y, m, d, h, min, s| Some(Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap());
+        let cases = [
+            ("D:20230415123045+02'00'", date(2023, 4, 15, 10, 30, 45)),
+            ("D:20230415123045-05'30", date(2023, 4, 15, 18, 0, 45)),
+            ("D:20230415123045Z", date(2023, 4, 15, 12, 30, 45)),
+            ("D:20230415123045Z00'00'", date(2023, 4, 15, 12, 30, 45)),
+            ("D:20230415123045", date(2023, 4, 15, 12, 30, 45)),
+            ("D:20230415123045+", date(2023, 4, 15, 12, 30, 45)),
+            ("D:2023", date(2023, 1, 1, 0, 0, 0)),
+            (" 20230415 ", date(2023, 4, 15, 0, 0, 0)),
+            ("D:20231345", None),
+            ("D:20230415253045", None),
+            ("D:202", None),
+            ("D:", None),
+            ("", None),
+            ("garbage", None),
+            ("D:2023\u{e9}+\u{e9}", date(2023, 1, 1, 0, 0, 0)),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(pdf_date(value), expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn pdf_information_dictionaries() {
+        let body = b"%PDF-1.4\n\
+            12 0 obj\n<< /Title (Other) >>\nendobj\n\
+            2 0 obj\n<< /Title (First) >>\nendobj\n\
+            3 0 obj\n<< /Title (Updated) /CreationDate (D:20230415123045Z) >>\nendobj\n\
+            trailer\n<< /Root 1 0 R /Info 2 0 R >>\n%%EOF\n\
+            trailer\n<< /Info 3 0 R >>\n%%EOF\n";
+
+        // the last trailer is the one in effect
+        let info = pdf_info(body).unwrap();
+        assert_eq!(pdf_entry(info, "Title").as_deref(), Some("Updated"));
+
+        let metadata = pdf(body);
+        assert_eq!(metadata.title.as_deref(), Some("Updated"));
+        assert_eq!(
+            metadata.metadata.published_date,
+            Some(Utc.with_ymd_and_hms(2023, 4, 15, 12, 30, 45).unwrap())
+        );
+
+        // the object a trailer designates may be missing
+        assert!(
+            pdf_info(b"12 0 obj << /Title (Other) >> endobj trailer << /Info 2 0 R >>").is_none()
+        );
+        assert!(pdf_info(b"2 0 obj << /Title (First) >> endobj").is_none());
+        // an unterminated object extends to the end of the body
+        assert_eq!(
+            pdf_info(b"trailer << /Info 2 0 R >> 2 0 obj << /Title (T"),
+            Some(&b" << /Title (T"[..])
+        );
+    }
+
+    #[test]
+    fn pdf_xmp_metadata() {
+        let body = b"%PDF-1.7\n<x:xmpmeta><rdf:RDF><rdf:Description>\
+            <dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\">XMP &amp; title</rdf:li>\
+            </rdf:Alt></dc:title>\
+            <dc:creator><rdf:Seq><rdf:li>Jane Doe</rdf:li></rdf:Seq></dc:creator>\
+            <xmp:CreateDate>2023-04-15T12:30:45Z</xmp:CreateDate>\
+            </rdf:Description></rdf:RDF></x:xmpmeta>\n/Lang (en-US)";
+
+        let metadata = pdf(body);
+        assert_eq!(metadata.title.as_deref(), Some("XMP & title"));
+        assert_eq!(metadata.metadata.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(
+            metadata.metadata.published_date,
+            Some(Utc.with_ymd_and_hms(2023, 4, 15, 12, 30, 45).unwrap())
+        );
+        assert_eq!(metadata.metadata.language.as_deref(), Some("en-US"));
+    }
+
+    #[test]
+    fn pdf_garbage() {
+        let cases: [&[u8]; 7] = [
+            b"",
+            b"%PDF-1.4",
+            b"trailer << /Info 1 0 R >> 1 0 obj << /Title (",
+            b"trailer << /Info 1 0 R >> 1 0 obj << /Title <",
+            b"trailer << /Info 1 0 R >> 1 0 obj << /Title (\\",
+            b"<dc:title><rdf:li>",
+            b"<dc:title></dc:title><xmp:CreateDate>never</xmp:CreateDate>",
+        ];
+
+        for body in cases {
+            let metadata = pdf(body);
+            assert_eq!(metadata.title, None, "{}", String::from_utf8_lossy(body));
+            assert_eq!(metadata.metadata.published_date, None);
+        }
+    }
+
+    /// Headers ending right after the dimensions, so that none can be read from their prefixes.
+    fn images() -> [(&'static str, Vec<u8>, (u32, u32)); 8] {
+        [
+            (
+                "PNG",
+                [
+                    &b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"[..],
+                    &640u32.to_be_bytes(),
+                    &480u32.to_be_bytes(),
+                ]
+                .concat(),
+                (640, 480),
+            ),
+            (
+                "GIF",
+                [&b"GIF89a"[..], &320u16.to_le_bytes(), &200u16.to_le_bytes()].concat(),
+                (320, 200),
+            ),
+            (
+                "JPEG",
+                [
+                    &[0xff, 0xd8][..],
+                    // an APP0 segment
+                    &[0xff, 0xe0, 0x00, 0x10],
+                    b"JFIF\x00\x01\x01\x00\x00\x01\x00\x01\x00\x00",
+                    // a Huffman table segment, whose marker is in the start-of-frame range
+                    &[0xff, 0xc4, 0x00, 0x02],
+                    // a progressive start-of-frame segment: precision, height and width
+                    &[0xff, 0xc2, 0x00, 0x11, 0x08],
+                    &600u16.to_be_bytes(),
+                    &800u16.to_be_bytes(),
+                ]
+                .concat(),
+                (800, 600),
+            ),
+            (
+                "WebP VP8",
+                [
+                    &b"RIFF\x00\x00\x00\x00WEBPVP8 \x00\x00\x00\x00"[..],
+                    // the frame tag and the start code
+                    &[0x00, 0x00, 0x00, 0x9d, 0x01, 0x2a],
+                    // the scale is in the two upper bits
+                    &(0x4000u16 | 1024).to_le_bytes(),
+                    &768u16.to_le_bytes(),
+                ]
+                .concat(),
+                (1024, 768),
+            ),
+            (
+                "WebP VP8L",
+                [
+                    &b"RIFF\x00\x00\x00\x00WEBPVP8L\x00\x00\x00\x00\x2f"[..],
+                    &((1920u32 - 1) | (1080 - 1) << 14).to_le_bytes(),
+                ]
+                .concat(),
+                (1920, 1080),
+            ),
+            (
+                "WebP VP8X",
+                [
+                    &b"RIFF\x00\x00\x00\x00WEBPVP8X\x00\x00\x00\x00\x00\x00\x00\x00"[..],
+                    &(4000u32 - 1).to_le_bytes()[..3],
+                    &(3000u32 - 1).to_le_bytes()[..3],
+                ]
+                .concat(),
+                (4000, 3000),
+            ),
+            (
+                "BMP",
+                [
+                    &b"BM"[..],
+                    &[0; 16],
+                    &100u32.to_le_bytes(),
+                    &50i32.to_le_bytes(),
+                ]
+                .concat(),
+                (100, 50),
+            ),
+            (
+                "BMP top-down",
+                [
+                    &b"BM"[..],
+                    &[0; 16],
+                    &100u32.to_le_bytes(),
+                    &(-50i32).to_le_bytes(),
+                ]
+                .concat(),
+                (100, 50),
+            ),
+        ]
+    }
+
+    #[test]
+    fn image_formats() {
+        for (format, body, expected) in images() {
+            assert_eq!(image_dimensions(&body), Some(expected), "{}", format);
+        }
+    }
+
+    #[test]
+    fn truncated_images() {
+        for (format, body, _) in images() {
+            for length in 0..body.len() {
+                assert_eq!(
+                    image_dimensions(&body[..length]),
+                    None,
+                    "{} truncated to {} bytes",
+                    format,
+                    length
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn image_garbage() {
+        let cases: [&[u8]; 6] = [
+            b"",
+            b"not an image at all",
+            b"RIFF\x00\x00\x00\x00WEBPVP9 \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+            // segments whose lengths point past the end
+            &[0xff, 0xd8, 0xff, 0xe0, 0xff, 0xff, 0x00],
+            // a segment not starting with a marker
+            &[0xff, 0xd8, 0x00, 0xc0, 0x00, 0x11],
+            &[0xff; 64],
+        ];
+
+        for body in cases {
+            assert_eq!(image_dimensions(body), None, "{:?}", body);
+        }
+    }
+}
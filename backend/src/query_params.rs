@@ -0,0 +1,191 @@
+use crate::database;
+use crate::rules::glob;
+use entity::query_param_rule::Model;
+use regex::Regex;
+use sea_orm::{ConnectionTrait, DbErr};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// The registry used when no file is configured, see the `update-ignored-get-params` task.
+const BUILTIN_REGISTRY: &str = include_str!("query-params-registry.txt");
+
+/// Ignores, or keeps, the query parameters whose name matches a pattern, possibly only on a
+/// domain and its subdomains.
+pub struct QueryParamRule {
+    pattern: Regex,
+    domain: Option<String>,
+    ignored: bool,
+}
+
+impl QueryParamRule {
+    /// Compiles the pattern of a rule, returning a message describing why it is invalid.
+    pub fn compile(pattern: &str, domain: Option<&str>, ignored: bool) -> Result<Self, String> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() || pattern.chars().all(|c| c == '*') {
+            return Err("A pattern needs more than wildcards".to_string());
+        }
+        let domain = domain
+            .map(|d| d.trim().trim_start_matches("www.").to_lowercase())
+            .filter(|d| !d.is_empty());
+        if domain
+            .as_deref()
+            .map(|d| d.contains(|c: char| c.is_whitespace() || c == '/'))
+            .unwrap_or_default()
+        {
+            return Err("Invalid domain".to_string());
+        }
+
+        Ok(Self {
+            pattern: glob(pattern).map_err(|e| format!("Invalid pattern: {}", e))?,
+            domain,
+            ignored,
+        })
+    }
+
+    /// Whether the rule applies to the parameter `name` of an URL of `host`, `www.` removed.
+    fn matches(&self, host: Option<&str>, name: &str) -> bool {
+        let domain_matches = match (&self.domain, host) {
+            (None, _) => true,
+            (Some(domain), Some(host)) => {
+                host == domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .map(|prefix| prefix.ends_with('.'))
+                        .unwrap_or_default()
+            }
+            (Some(_), None) => false,
+        };
+        domain_matches && self.pattern.is_match(name)
+    }
+}
+
+/// Parses a registry: one pattern per line, optionally preceded by the domain it is restricted
+/// to, e.g. `amazon.com tag`. A pattern starting with `!` keeps the parameters it matches; `#`
+/// starts a comment.
+fn parse(registry: &str) -> Vec<QueryParamRule> {
+    registry
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let (domain, pattern) = match line.split_once(char::is_whitespace) {
+                Some((domain, pattern)) => (Some(domain), pattern.trim()),
+                None => (None, line),
+            };
+            if pattern.is_empty() {
+                return None;
+            }
+            let (pattern, ignored) = match pattern.strip_prefix('!') {
+                Some(pattern) => (pattern, false),
+                None => (pattern, true),
+            };
+
+            QueryParamRule::compile(pattern, domain, ignored)
+                .map_err(|e| log::warn!("Skipping line {} of the registry: {}", index + 1, e))
+                .ok()
+        })
+        .collect()
+}
+
+/// The query parameters ignored on the whole instance, read from a file that can be reloaded
+/// without restarting.
+#[derive(Clone)]
+pub struct Registry {
+    path: Option<PathBuf>,
+    rules: Arc<RwLock<Arc<Vec<QueryParamRule>>>>,
+}
+
+impl Registry {
+    /// Loads the registry from `path`, or the one built in when `None`.
+    pub fn load(path: Option<PathBuf>) -> std::io::Result<Self> {
+        let registry = Self {
+            path,
+            rules: Arc::default(),
+        };
+        registry.reload()?;
+        Ok(registry)
+    }
+
+    /// Reads the file again, keeping the current rules when it cannot be; returns the number of
+    /// rules loaded.
+    pub fn reload(&self) -> std::io::Result<usize> {
+        let rules = match &self.path {
+            Some(path) => parse(&std::fs::read_to_string(path)?),
+            None => parse(BUILTIN_REGISTRY),
+        };
+        let count = rules.len();
+        *self.rules.write().unwrap() = Arc::new(rules);
+        Ok(count)
+    }
+
+    fn rules(&self) -> Arc<Vec<QueryParamRule>> {
+        self.rules.read().unwrap().clone()
+    }
+}
+
+/// The rules applying to the URLs of a user: theirs first, then the ones of the instance, then
+/// the registry. In each of them, the rules restricted to a domain come first; the first rule
+/// matching a parameter decides whether it is kept.
+pub struct QueryParamRules {
+    user: Vec<QueryParamRule>,
+    instance: Vec<QueryParamRule>,
+    registry: Arc<Vec<QueryParamRule>>,
+}
+
+impl QueryParamRules {
+    pub async fn load<C>(db: &C, registry: &Registry, user_id: Option<i32>) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let (user, instance): (Vec<Model>, Vec<Model>) =
+            database::query_param_rules::Query::find_applicable(db, user_id)
+                .await?
+                .into_iter()
+                .partition(|model| model.user_id.is_some());
+
+        Ok(Self {
+            user: compile_all(user),
+            instance: compile_all(instance),
+            registry: registry.rules(),
+        })
+    }
+
+    /// The rules of the registry alone, as for a user who has none.
+    #[cfg(test)]
+    pub fn from_registry(registry: &Registry) -> Self {
+        Self {
+            user: vec![],
+            instance: vec![],
+            registry: registry.rules(),
+        }
+    }
+
+    /// Whether the parameter `name` is removed from the URLs of `host`, `www.` removed.
+    pub fn is_ignored(&self, host: Option<&str>, name: &str) -> bool {
+        [&self.user, &self.instance, self.registry.as_ref()]
+            .into_iter()
+            .find_map(|rules| {
+                let first_matching = |scoped: bool| {
+                    rules
+                        .iter()
+                        .filter(|rule| rule.domain.is_some() == scoped)
+                        .find(|rule| rule.matches(host, name))
+                        .map(|rule| rule.ignored)
+                };
+                first_matching(true).or_else(|| first_matching(false))
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn compile_all(models: Vec<Model>) -> Vec<QueryParamRule> {
+    models
+        .into_iter()
+        .filter_map(|model| {
+            QueryParamRule::compile(&model.pattern, model.domain.as_deref(), model.ignored)
+                // patterns are validated when saved
+                .map_err(|e| log::warn!("Skipping query param rule {}: {}", model.id, e))
+                .ok()
+        })
+        .collect()
+}
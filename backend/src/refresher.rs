@@ -0,0 +1,126 @@
+use crate::cache::MetadataCache;
+use crate::database::bookmarks;
+use crate::domain::bookmark::MetadataStatus;
+use crate::fetcher::Fetcher;
+use crate::metadata;
+use chrono::Utc;
+use sea_orm::{DatabaseConnection, DbErr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// The number of bookmarks refreshed before looking for the next ones.
+const BATCH_SIZE: u64 = 20;
+
+/// How long to wait between two full batches, not to fetch all the bookmarks at once.
+const BATCH_PAUSE: Duration = Duration::from_secs(10);
+
+pub struct RefreshConfiguration {
+    /// How long to wait before looking for bookmarks to refresh when there were none left.
+    pub interval: Duration,
+    /// The age after which the metadata of a bookmark are fetched again; never when `None`.
+    pub max_age: Option<Duration>,
+}
+
+/// Fetches, in the background, the metadata of the bookmarks missing a title, of the ones whose
+/// metadata are too old and of the ones a refresh was requested for.
+#[derive(Clone)]
+pub struct Refresher {
+    notify: Arc<Notify>,
+}
+
+impl Refresher {
+    pub fn start(
+        database: DatabaseConnection,
+        fetcher: Fetcher,
+        cache: MetadataCache,
+        configuration: RefreshConfiguration,
+    ) -> Self {
+        let notify = Arc::new(Notify::new());
+
+        {
+            let notify = notify.clone();
+            tokio::spawn(async move {
+                loop {
+                    let count = match refresh(&database, &fetcher, &cache, &configuration).await {
+                        Ok(count) => count,
+                        Err(e) => {
+                            log::error!("Could not refresh metadata: {}", e);
+                            0
+                        }
+                    };
+                    // a full batch means there may be more bookmarks to refresh
+                    let pause = match count < BATCH_SIZE {
+                        true => configuration.interval,
+                        false => BATCH_PAUSE,
+                    };
+                    tokio::select! {
+                        _ = tokio::time::sleep(pause) => {},
+                        _ = notify.notified() => {},
+                    }
+                }
+            });
+        }
+
+        Self { notify }
+    }
+
+    /// Looks for bookmarks to refresh right away, e.g. after a refresh was requested.
+    pub fn wake(&self) {
+        self.notify.notify_one();
+    }
+}
+
+/// Refreshes a batch of bookmarks, returning their number.
+async fn refresh(
+    database: &DatabaseConnection,
+    fetcher: &Fetcher,
+    cache: &MetadataCache,
+    configuration: &RefreshConfiguration,
+) -> Result<u64, DbErr> {
+    let older_than = configuration
+        .max_age
+        .and_then(|max_age| chrono::Duration::from_std(max_age).ok())
+        .map(|max_age| Utc::now() - max_age);
+    let bookmarks =
+        bookmarks::Query::find_due_for_refresh(database, older_than, BATCH_SIZE).await?;
+
+    for bookmark in &bookmarks {
+        // a refresh requested by the user fetches the page again, whereas a background one may
+        // use the cache
+        let requested =
+            bookmark.metadata_status.as_deref() == Some(MetadataStatus::Pending.as_str());
+        log::info!("Refreshing metadata of bookmark {}", bookmark.id);
+
+        let fetched = match requested {
+            true => {
+                let fetched = metadata::fetch(fetcher, bookmark.url.clone()).await;
+                cache.put(&bookmark.url, &fetched).await;
+                fetched
+            }
+            false => cache.fetch(fetcher, bookmark.url.clone()).await,
+        };
+
+        if let Err(e) =
+            bookmarks::Mutation::update_fetched_metadata(database, bookmark.id, fetched.page).await
+        {
+            log::error!(
+                "Could not update metadata of bookmark {}: {}",
+                bookmark.id,
+                e
+            );
+            // recorded as a failure, the bookmark does not come first in the next batches
+            if let Err(e) =
+                bookmarks::Mutation::update_fetched_metadata(database, bookmark.id, None).await
+            {
+                log::error!(
+                    "Could not record failure of bookmark {}: {}",
+                    bookmark.id,
+                    e
+                );
+            }
+        }
+    }
+
+    Ok(bookmarks.len() as u64)
+}
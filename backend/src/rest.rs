@@ -1,42 +1,65 @@
 mod application;
 mod bookmarks;
+mod domains;
 mod emails;
 mod json;
 mod password_recoveries;
+mod query_params;
+mod rules;
+mod saved_searches;
 mod sessions;
 mod shaarli_import_api;
+mod suggestions;
+mod tag_aliases;
 mod tags;
+mod urls;
 mod users;
 
 use crate::rest::application::get_application;
 use crate::rest::bookmarks::*;
+use crate::rest::domains::get_domains;
 use crate::rest::emails::update_email;
 use crate::rest::password_recoveries::{create_password_recovery, update_password_recovery};
+use crate::rest::query_params::{
+    create_query_param_rule, delete_query_param_rule, get_query_param_rules,
+};
+use crate::rest::rules::{apply_rules, create_rule, delete_rule, get_rules, update_rule};
+use crate::rest::saved_searches::*;
 use crate::rest::sessions::*;
 use crate::rest::shaarli_import_api::shaarli_import_api;
-use crate::rest::tags::get_tags;
+use crate::rest::suggestions::get_suggestions;
+use crate::rest::tag_aliases::{create_tag_alias, delete_tag_alias, get_tag_aliases};
+use crate::rest::tags::{
+    delete_tag, get_related_tags, get_tag, get_tags, merge_tags, rename_tag, update_tag_metadata,
+};
+use crate::rest::urls::get_url;
 use crate::rest::users::*;
-use crate::sessions::session::{SessionHint, UserInfo};
-use crate::url;
-use crate::{database, AppState};
-use axum::extract::{Path, State};
+use crate::sessions::session::SessionHint;
+use crate::AppState;
 use axum::middleware::from_fn;
 use axum::routing::{delete, get, post, put};
-use axum::{Extension, Router};
+use axum::Router;
 use axum_sessions::async_session::SessionStore;
 use axum_sessions::{PersistencePolicy, SessionLayer};
 use rest_api::application::URL_APPLICATION;
-use rest_api::bookmarks::{URL_BOOKMARK, URL_BOOKMARKS_STATS};
+use rest_api::bookmarks::{URL_BOOKMARK, URL_BOOKMARKS_STATS, URL_BOOKMARKS_TIMELINE};
 use rest_api::bookmarks::{URL_BOOKMARKS, URL_BOOKMARK_QRCODE};
+use rest_api::bookmarks::{URL_BOOKMARKS_DUPLICATES, URL_BOOKMARK_MERGE};
+use rest_api::bookmarks::{URL_BOOKMARKS_REFRESH, URL_BOOKMARK_REFRESH};
+use rest_api::domains::URL_DOMAINS;
 use rest_api::import_shaarli_api::URL_SHAARLI_IMPORT_API;
 use rest_api::password_recoveries::URL_PASSWORD_RECOVERIES;
+use rest_api::query_params::{URL_QUERY_PARAM_RULE, URL_QUERY_PARAM_RULES};
+use rest_api::rules::{URL_RULE, URL_RULES, URL_RULES_APPLY};
+use rest_api::saved_searches::{URL_SAVED_SEARCH, URL_SAVED_SEARCHES};
 use rest_api::sessions::{URL_SESSIONS, URL_SESSIONS_CURRENT};
-use rest_api::tags::URL_TAGS;
-use rest_api::urls::{GetUrlConflictResponse, GetUrlResponse, GetUrlResult, URL_URLS};
+use rest_api::suggestions::URL_SUGGESTIONS;
+use rest_api::tag_aliases::{URL_TAG_ALIAS, URL_TAG_ALIASES};
+use rest_api::tags::{URL_RELATED_TAGS, URL_TAG, URL_TAGS, URL_TAGS_MERGE, URL_TAG_METADATA};
+use rest_api::urls::URL_URLS;
 use rest_api::users::{URL_CURRENT_USER, URL_USERS};
 use rest_api::validate_email::URL_EMAIL;
 use secrecy::{ExposeSecret, SecretVec};
-use webpage::HTML;
 
 pub struct Configuration<S>
 where
@@ -62,8 +85,32 @@ where
                 .route(URL_BOOKMARKS, post(create_bookmark))
                 .route(URL_BOOKMARK, delete(delete_bookmark))
                 .route(URL_BOOKMARK, put(update_bookmark))
+                .route(URL_BOOKMARK_REFRESH, post(refresh_bookmark))
+                .route(URL_BOOKMARKS_REFRESH, post(refresh_bookmarks))
+                .route(URL_BOOKMARKS_DUPLICATES, get(get_bookmarks_duplicates))
+                .route(URL_BOOKMARK_MERGE, post(merge_bookmarks))
                 .route(URL_URLS, get(get_url))
                 .route(URL_SHAARLI_IMPORT_API, post(shaarli_import_api))
+                .route(URL_SAVED_SEARCHES, get(get_saved_searches))
+                .route(URL_SAVED_SEARCHES, post(create_saved_search))
+                .route(URL_SAVED_SEARCH, put(update_saved_search))
+                .route(URL_SAVED_SEARCH, delete(delete_saved_search))
+                .route(URL_TAG, get(get_tag))
+                .route(URL_TAG, put(rename_tag))
+                .route(URL_TAG, delete(delete_tag))
+                .route(URL_TAGS_MERGE, post(merge_tags))
+                .route(URL_TAG_METADATA, put(update_tag_metadata))
+                .route(URL_TAG_ALIASES, get(get_tag_aliases))
+                .route(URL_TAG_ALIASES, post(create_tag_alias))
+                .route(URL_TAG_ALIAS, delete(delete_tag_alias))
+                .route(URL_RULES, get(get_rules))
+                .route(URL_RULES, post(create_rule))
+                .route(URL_RULE, put(update_rule))
+                .route(URL_RULE, delete(delete_rule))
+                .route(URL_RULES_APPLY, post(apply_rules))
+                .route(URL_QUERY_PARAM_RULES, get(get_query_param_rules))
+                .route(URL_QUERY_PARAM_RULES, post(create_query_param_rule))
+                .route(URL_QUERY_PARAM_RULE, delete(delete_query_param_rule))
                 .layer(from_fn(SessionHint::required))
                 .layer(
                     SessionLayer::new(
@@ -81,7 +128,11 @@ where
                 .route(URL_USERS, post(create_user))
                 .route(URL_EMAIL, put(update_email))
                 .route(URL_TAGS, get(get_tags))
+                .route(URL_RELATED_TAGS, get(get_related_tags))
+                .route(URL_DOMAINS, get(get_domains))
+                .route(URL_SUGGESTIONS, get(get_suggestions))
                 .route(URL_BOOKMARKS_STATS, get(get_bookmarks_stats))
+                .route(URL_BOOKMARKS_TIMELINE, get(get_bookmarks_timeline))
                 .layer(from_fn(SessionHint::supported))
                 .layer(
                     SessionLayer::new(
@@ -120,53 +171,36 @@ where
         .with_state(state)
 }
 
-async fn get_url(
-    Extension(user_info): Extension<UserInfo>,
-    Path(url): Path<String>,
-    State(state): State<AppState>,
-) -> Result<GetUrlResult, GetUrlResult> {
-    if let Some(id) = database::bookmarks::Query::find_by_url(&state.database, user_info.id, &url)
-        .await
-        .map_err(|_| GetUrlResult::ServerError)?
-    {
-        return Ok(GetUrlResult::Conflict(GetUrlConflictResponse { id }));
-    }
-
-    let url = url::clean(url, &state.ignored_query_params).ok_or(GetUrlResult::InvalidUrl)?;
-    log::info!("Fetching metadata about {}", &url);
-
-    let response = state.http_client.get(&url).send().await.map_err(|e| {
-        log::error!("{:?}", e);
-        GetUrlResult::Success(GetUrlResponse {
-            url,
-            title: None,
-            description: None,
-        })
-    })?;
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::extract::Path;
+    use axum::http::{Method, Request, StatusCode};
+    use axum::routing::delete;
+    use axum::Router;
+    use rest_api::tags::URL_TAG;
+    use tower::ServiceExt;
 
-    let url = response.url().to_string();
+    #[tokio::test]
+    async fn tag_names_are_decoded() {
+        let router = Router::new().route(
+            URL_TAG,
+            delete(|Path(name): Path<String>| async move { name }),
+        );
 
-    let html = response.text().await.map_err(|e| {
-        log::error!("{:?}", e);
-        GetUrlResult::Success(GetUrlResponse {
-            url: url.clone(),
-            title: None,
-            description: None,
-        })
-    })?;
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(URL_TAG.replace(":name", &urlencoding::encode("c/c++")))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
 
-    let html = HTML::from_string(html, None).map_err(|e| {
-        log::error!("{:?}", e);
-        GetUrlResult::Success(GetUrlResponse {
-            url: url.clone(),
-            title: None,
-            description: None,
-        })
-    })?;
-
-    Ok(GetUrlResult::Success(GetUrlResponse {
-        url,
-        title: html.title,
-        description: html.description,
-    }))
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], b"c/c++");
+    }
 }
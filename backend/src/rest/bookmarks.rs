@@ -1,20 +1,36 @@
-use crate::database::bookmarks::{Filter, Pagination, SearchCriteria, SortOrder};
+use crate::database::bookmarks::{
+    Filter, Granularity, Pagination, SearchCriteria, SortOrder, HIGHLIGHT_START, HIGHLIGHT_STOP,
+};
 use crate::database::pins;
-use crate::domain::bookmark::Bookmark;
+use crate::domain::bookmark::{Bookmark, Metadata, MetadataStatus};
+use crate::rules::Rules;
 use crate::sessions::session::UserInfo;
-use crate::{database, AppState};
+use crate::{database, url, AppState};
 use axum::body::Body;
 use axum::extract::{Path, Query, State};
 use axum::http::{header, Response, StatusCode};
 use axum::response::IntoResponse;
 use axum::{Extension, Json};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use qrcode_generator::QrCodeEcc;
 use rest_api::bookmarks::create::{CreateBookmarkRequest, CreateBookmarkResult};
 use rest_api::bookmarks::delete::DeleteBookmarkResult;
+use rest_api::bookmarks::duplicates::{GetDuplicatesResponse, GetDuplicatesResult};
 use rest_api::bookmarks::get_many::{GetBookmarksResponse, GetBookmarksResult};
-use rest_api::bookmarks::get_one::{GetBookmarkResponse, GetBookmarkResult};
+use rest_api::bookmarks::get_one::{GetBookmarkResponse, GetBookmarkResult, SnippetPart};
+use rest_api::bookmarks::merge::{MergeBookmarksRequest, MergeBookmarksResult};
+use rest_api::bookmarks::refresh::{
+    RefreshBookmarkResult, RefreshBookmarksResponse, RefreshBookmarksResult,
+};
+use rest_api::bookmarks::timeline::{
+    GetBookmarksTimelineResponse, GetBookmarksTimelineResult, TimelineBucket,
+};
 use rest_api::bookmarks::update::{UpdateBookmarkRequest, UpdateBookmarkResult};
-use rest_api::bookmarks::{Access, GetBookmarksStatsResponse, GetBookmarksStatsResult};
+use rest_api::bookmarks::{
+    Access, BookmarkMetadata, GetBookmarksStatsResponse, GetBookmarksStatsResult,
+};
+use rest_api::tags::TagMetadata;
+use rest_api::urls::GetUrlConflictResponse;
 use sea_orm::{DbErr, TransactionTrait};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -30,6 +46,125 @@ pub struct GetBookmarksQueryParams {
     tags: Option<String>,
     search: Option<String>,
     filter: Option<String>,
+    domain: Option<String>,
+    descendants: Option<bool>,
+    created_after: Option<String>,
+    created_before: Option<String>,
+    updated_after: Option<String>,
+    highlight: Option<bool>,
+}
+
+impl TryFrom<&GetBookmarksQueryParams> for SearchCriteria {
+    type Error = String;
+
+    fn try_from(query: &GetBookmarksQueryParams) -> Result<Self, Self::Error> {
+        Ok(SearchCriteria {
+            tags: query
+                .tags
+                .as_ref()
+                // todo: no manual deserialize
+                .map(|tags| {
+                    tags.split('+')
+                        .map(decode)
+                        .map(|t| t.unwrap_or_default())
+                        .filter(|t| !t.is_empty())
+                        .map(|t| t.to_string())
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default(),
+            search: query
+                .search
+                .as_ref()
+                // todo: no manual deserialize
+                .map(|tags| {
+                    tags.split('+')
+                        .map(decode)
+                        .map(|t| t.unwrap_or_default())
+                        .filter(|t| !t.is_empty())
+                        .map(|t| t.to_string())
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default(),
+            filter: query
+                .filter
+                .as_ref()
+                // todo: no manual deserialize
+                .map(|v| Filter::try_from(v.as_str()))
+                .unwrap_or(Ok(Filter::All))
+                .map_err(|_| {
+                    "Unsupported value provided for the 'filter' query parameter".to_string()
+                })?,
+            domain: query.domain.clone().filter(|d| !d.is_empty()),
+            descendants: query.descendants.unwrap_or_default(),
+            created_after: parse_date(&query.created_after, "created_after")?,
+            created_before: parse_date(&query.created_before, "created_before")?,
+            updated_after: parse_date(&query.updated_after, "updated_after")?,
+        })
+    }
+}
+
+/// Accepts either a RFC 3339 date-time or a `YYYY-MM-DD` date, the latter meaning midnight UTC.
+fn parse_date(value: &Option<String>, name: &str) -> Result<Option<DateTime<Utc>>, String> {
+    match value.as_deref() {
+        None | Some("") => Ok(None),
+        Some(value) => DateTime::parse_from_rfc3339(value)
+            .map(|d| d.with_timezone(&Utc))
+            .or_else(|_| {
+                NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map(|d| Utc.from_utc_datetime(&d.and_hms_opt(0, 0, 0).unwrap_or_default()))
+            })
+            .map(Some)
+            .map_err(|_| {
+                format!(
+                    "Unsupported value provided for the '{}' query parameter",
+                    name
+                )
+            }),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct GetBookmarksTimelineQueryParams {
+    granularity: Option<String>,
+}
+
+fn into_snippet(snippet: String) -> Vec<SnippetPart> {
+    let mut parts = Vec::new();
+    let mut highlight = false;
+    for fragment in snippet.split([HIGHLIGHT_START, HIGHLIGHT_STOP]) {
+        if !fragment.is_empty() {
+            parts.push(SnippetPart {
+                text: fragment.to_string(),
+                highlight,
+            });
+        }
+        highlight = !highlight;
+    }
+    parts
+}
+
+pub fn into_metadata(metadata: Metadata) -> BookmarkMetadata {
+    BookmarkMetadata {
+        site_name: metadata.site_name,
+        author: metadata.author,
+        published_date: metadata.published_date,
+        language: metadata.language,
+        image_url: metadata.image_url,
+        favicon_url: metadata.favicon_url,
+    }
+}
+
+/// The metadata sent by the client, usually as `get_url` returned it. The image URLs end up in
+/// `src` attributes, so only absolute http(s) ones are kept.
+fn from_metadata(metadata: BookmarkMetadata) -> Metadata {
+    Metadata {
+        site_name: metadata.site_name,
+        author: metadata.author,
+        published_date: metadata.published_date,
+        language: metadata.language,
+        image_url: metadata.image_url.filter(|u| url::is_http(u)),
+        favicon_url: metadata.favicon_url.filter(|u| url::is_http(u)),
+    }
 }
 
 fn into_response(bookmark: Bookmark, remote_user: Option<&UserInfo>) -> GetBookmarkResponse {
@@ -38,7 +173,22 @@ fn into_response(bookmark: Bookmark, remote_user: Option<&UserInfo>) -> GetBookm
         url: bookmark.url,
         title: bookmark.title,
         description: bookmark.description,
-        tags: bookmark.tags,
+        tags_metadata: bookmark
+            .tags
+            .iter()
+            .map(|t| {
+                (
+                    t.name.clone(),
+                    TagMetadata {
+                        color: t.color.clone(),
+                        description: t.description.clone(),
+                        icon: t.icon.clone(),
+                    },
+                )
+            })
+            .filter(|(_, m)| !m.is_empty())
+            .collect(),
+        tags: bookmark.tags.into_iter().map(|t| t.name).collect(),
         creation_date: bookmark.creation_date,
         update_date: bookmark.update_date,
         user_id: bookmark.user_id,
@@ -52,6 +202,16 @@ fn into_response(bookmark: Bookmark, remote_user: Option<&UserInfo>) -> GetBookm
         },
         private: bookmark.private,
         pinned: bookmark.pinned,
+        domain: bookmark.domain,
+        metadata: into_metadata(bookmark.metadata),
+        metadata_status: bookmark.metadata_status.map(|s| match s {
+            MetadataStatus::Pending => rest_api::bookmarks::MetadataStatus::Pending,
+            MetadataStatus::Success => rest_api::bookmarks::MetadataStatus::Success,
+            MetadataStatus::Failure => rest_api::bookmarks::MetadataStatus::Failure,
+        }),
+        metadata_fetch_date: bookmark.metadata_fetch_date,
+        title_snippet: bookmark.title_snippet.map(into_snippet),
+        description_snippet: bookmark.description_snippet.map(into_snippet),
     }
 }
 
@@ -60,42 +220,8 @@ pub async fn get_bookmarks(
     Extension(user_info): Extension<Option<UserInfo>>,
     State(state): State<AppState>,
 ) -> Result<GetBookmarksResult, GetBookmarksResult> {
-    let criteria = SearchCriteria {
-        tags: query
-            .tags
-            // todo: no manual deserialize
-            .map(|tags| {
-                tags.split('+')
-                    .map(decode)
-                    .map(|t| t.unwrap_or_default())
-                    .filter(|t| !t.is_empty())
-                    .map(|t| t.to_string())
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_default(),
-        search: query
-            .search
-            // todo: no manual deserialize
-            .map(|tags| {
-                tags.split('+')
-                    .map(decode)
-                    .map(|t| t.unwrap_or_default())
-                    .filter(|t| !t.is_empty())
-                    .map(|t| t.to_string())
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_default(),
-        filter: query
-            .filter
-            // todo: no manual deserialize
-            .map(|v| Filter::try_from(v.as_str()))
-            .unwrap_or(Ok(Filter::All))
-            .map_err(|_| {
-                GetBookmarksResult::InvalidParameter(
-                    "Unsupported value provided for the 'sort' query parameter".to_string(),
-                )
-            })?,
-    };
+    let criteria =
+        SearchCriteria::try_from(&query).map_err(GetBookmarksResult::InvalidParameter)?;
 
     let page = Pagination {
         page: query.page.unwrap_or_default(),
@@ -115,13 +241,19 @@ pub async fn get_bookmarks(
 
     let user_id = user_info.as_ref().map(|u| u.id);
     let page_size = page.size;
-    let bookmarks =
-        database::bookmarks::Query::find(&state.database, &criteria, &page, &order, user_id)
-            .await
-            .map_err(|_| GetBookmarksResult::ServerError)?
-            .into_iter()
-            .map(|bookmark| into_response(bookmark, user_info.as_ref()))
-            .collect::<Vec<GetBookmarkResponse>>();
+    let bookmarks = database::bookmarks::Query::find(
+        &state.database,
+        &criteria,
+        &page,
+        &order,
+        user_id,
+        query.highlight.unwrap_or_default(),
+    )
+    .await
+    .map_err(|_| GetBookmarksResult::ServerError)?
+    .into_iter()
+    .map(|bookmark| into_response(bookmark, user_info.as_ref()))
+    .collect::<Vec<GetBookmarkResponse>>();
 
     let bookmarks_count = database::bookmarks::Query::count(&state.database, user_id, &criteria)
         .await
@@ -151,7 +283,7 @@ pub async fn get_bookmark(
         format!("Bookmark '{}' not found", bookmark_id),
     ))?;
 
-    Ok(GetBookmarkResult::Success(bookmark))
+    Ok(GetBookmarkResult::Success(Box::new(bookmark)))
 }
 
 pub async fn get_bookmark_qrcode(
@@ -199,16 +331,38 @@ pub async fn create_bookmark(
     Extension(user_info): Extension<UserInfo>,
     Json(bookmark): Json<CreateBookmarkRequest>,
 ) -> Result<CreateBookmarkResult, CreateBookmarkResult> {
+    if let Some(id) =
+        database::bookmarks::Query::find_by_url(&state.database, user_info.id, &bookmark.url)
+            .await
+            .map_err(|_| CreateBookmarkResult::ServerError)?
+    {
+        return Err(CreateBookmarkResult::Conflict(GetUrlConflictResponse {
+            id,
+        }));
+    }
+
     let bookmark_id = state
         .database
         .transaction::<_, i32, DbErr>(|txn| {
             Box::pin(async move {
+                let actions = Rules::load(txn, user_info.id)
+                    .await?
+                    .apply(&bookmark.url, bookmark.title.as_deref());
+
                 let tags = {
                     let mut tags = Vec::new();
-                    for tag in bookmark.tags.unwrap_or_default() {
-                        tags.push(
-                            database::tags::Mutation::create_tag(txn, tag.to_lowercase()).await?,
-                        )
+                    for tag in bookmark
+                        .tags
+                        .unwrap_or_default()
+                        .into_iter()
+                        .chain(actions.tags)
+                    {
+                        let tag =
+                            database::tags::Mutation::create_tag(txn, user_info.id, tag).await?;
+                        // aliases and case variants may resolve to a tag that is already there
+                        if !tags.iter().any(|t: &entity::tag::Model| t.id == tag.id) {
+                            tags.push(tag)
+                        }
                     }
                     tags
                 };
@@ -219,7 +373,8 @@ pub async fn create_bookmark(
                     bookmark.title,
                     bookmark.description,
                     user_info.id,
-                    bookmark.private.unwrap_or(true),
+                    bookmark.private.unwrap_or(true) || actions.private,
+                    from_metadata(bookmark.metadata),
                 )
                 .await?;
 
@@ -228,6 +383,10 @@ pub async fn create_bookmark(
                         .await?;
                 }
 
+                if actions.pin {
+                    pins::Mutation::pin(txn, bookmark.id, user_info.id).await?;
+                }
+
                 Ok(bookmark.id)
             })
         })
@@ -244,7 +403,7 @@ pub async fn create_bookmark(
     .map(|bookmark| into_response(bookmark, Some(user_info).as_ref()))
     .ok_or(CreateBookmarkResult::ServerError)?;
 
-    Ok(CreateBookmarkResult::Success(bookmark))
+    Ok(CreateBookmarkResult::Success(Box::new(bookmark)))
 }
 
 pub async fn update_bookmark(
@@ -253,7 +412,7 @@ pub async fn update_bookmark(
     Extension(user_info): Extension<UserInfo>,
     Json(bookmark): Json<UpdateBookmarkRequest>,
 ) -> Result<UpdateBookmarkResult, UpdateBookmarkResult> {
-    if database::bookmarks::Query::find_visible_by_id(
+    let existing = database::bookmarks::Query::find_visible_by_id(
         &state.database,
         bookmark_id,
         Some(user_info.id),
@@ -263,23 +422,48 @@ pub async fn update_bookmark(
     .ok_or(UpdateBookmarkResult::NotFound(
         bookmark_id,
         format!("Bookmark '{}' not found", bookmark_id),
-    ))?
-    .user_id
-        != user_info.id
-    {
+    ))?;
+    if existing.user_id != user_info.id {
         return Err(UpdateBookmarkResult::Forbidden);
     }
 
+    // a bookmark already having duplicates can still be edited, as long as its URL is kept
+    if url::key(&bookmark.url) != url::key(&existing.url) {
+        if let Some(id) = database::bookmarks::Query::find_other_by_url(
+            &state.database,
+            user_info.id,
+            &bookmark.url,
+            bookmark_id,
+        )
+        .await
+        .map_err(|_| UpdateBookmarkResult::ServerError)?
+        {
+            return Err(UpdateBookmarkResult::Conflict(GetUrlConflictResponse {
+                id,
+            }));
+        }
+    }
+
     state
         .database
         .transaction::<_, (), DbErr>(|txn| {
             Box::pin(async move {
+                let actions = Rules::load(txn, user_info.id).await?.apply_on_edit(
+                    &bookmark.url,
+                    bookmark.title.as_deref(),
+                    &existing.url,
+                    existing.title.as_deref(),
+                );
+
                 let tags = {
                     let mut tags = Vec::new();
-                    for tag in bookmark.tags {
-                        tags.push(
-                            database::tags::Mutation::create_tag(txn, tag.to_lowercase()).await?,
-                        )
+                    for tag in bookmark.tags.into_iter().chain(actions.tags) {
+                        let tag =
+                            database::tags::Mutation::create_tag(txn, user_info.id, tag).await?;
+                        // aliases and case variants may resolve to a tag that is already there
+                        if !tags.iter().any(|t: &entity::tag::Model| t.id == tag.id) {
+                            tags.push(tag)
+                        }
                     }
                     tags
                 };
@@ -291,7 +475,7 @@ pub async fn update_bookmark(
                         .await?;
                 }
 
-                database::tags::Mutation::delete_orphans(txn).await?;
+                database::tags::Mutation::delete_orphans(txn, user_info.id).await?;
 
                 database::bookmarks::Mutation::update_bookmark(
                     txn,
@@ -299,11 +483,11 @@ pub async fn update_bookmark(
                     bookmark.url,
                     bookmark.title,
                     bookmark.description,
-                    bookmark.private,
+                    bookmark.private || actions.private,
                 )
                 .await?;
 
-                if !bookmark.pinned {
+                if !(bookmark.pinned || actions.pin) {
                     pins::Mutation::unpin(txn, bookmark_id, user_info.id).await?;
                 } else {
                     pins::Mutation::pin(txn, bookmark_id, user_info.id).await?;
@@ -328,7 +512,7 @@ pub async fn update_bookmark(
         format!("Bookmark '{}' not found", bookmark_id),
     ))?;
 
-    Ok(UpdateBookmarkResult::Success(bookmark))
+    Ok(UpdateBookmarkResult::Success(Box::new(bookmark)))
 }
 
 pub async fn delete_bookmark(
@@ -358,7 +542,7 @@ pub async fn delete_bookmark(
         .transaction::<_, (), DbErr>(|txn| {
             Box::pin(async move {
                 database::bookmarks_tags::Mutation::delete_all_links(txn, bookmark_id).await?;
-                database::tags::Mutation::delete_orphans(txn).await?;
+                database::tags::Mutation::delete_orphans(txn, user_info.id).await?;
                 database::bookmarks::Mutation::delete_bookmark(txn, bookmark_id).await?;
                 Ok(())
             })
@@ -369,6 +553,183 @@ pub async fn delete_bookmark(
     Ok(DeleteBookmarkResult::Success)
 }
 
+pub async fn get_bookmarks_duplicates(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+) -> Result<GetDuplicatesResult, GetDuplicatesResult> {
+    let groups = database::bookmarks::Query::find_duplicates(&state.database, user_info.id)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            GetDuplicatesResult::ServerError
+        })?;
+
+    let mut duplicates = GetDuplicatesResponse::with_capacity(groups.len());
+    for ids in groups {
+        let mut group = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(bookmark) = database::bookmarks::Query::find_visible_by_id(
+                &state.database,
+                id,
+                Some(user_info.id),
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("{}", e);
+                GetDuplicatesResult::ServerError
+            })? {
+                group.push(into_response(bookmark, Some(&user_info)));
+            }
+        }
+        duplicates.push(group);
+    }
+
+    Ok(GetDuplicatesResult::Success(duplicates))
+}
+
+/// Merges bookmarks into the one of the path, moving their tags and pins to it.
+pub async fn merge_bookmarks(
+    State(state): State<AppState>,
+    Path(bookmark_id): Path<i32>,
+    Extension(user_info): Extension<UserInfo>,
+    Json(request): Json<MergeBookmarksRequest>,
+) -> Result<MergeBookmarksResult, MergeBookmarksResult> {
+    let mut duplicate_ids = request.ids;
+    duplicate_ids.sort_unstable();
+    duplicate_ids.dedup();
+    if duplicate_ids.is_empty() || duplicate_ids.contains(&bookmark_id) {
+        return Err(MergeBookmarksResult::InvalidParameter(
+            "The bookmarks to merge must be other bookmarks".to_string(),
+        ));
+    }
+
+    for id in duplicate_ids.iter().chain([&bookmark_id]) {
+        if database::bookmarks::Query::find_visible_by_id(&state.database, *id, Some(user_info.id))
+            .await
+            .map_err(|_| MergeBookmarksResult::ServerError)?
+            .ok_or(MergeBookmarksResult::NotFound(
+                *id,
+                format!("Bookmark '{}' not found", id),
+            ))?
+            .user_id
+            != user_info.id
+        {
+            return Err(MergeBookmarksResult::Forbidden);
+        }
+    }
+
+    state
+        .database
+        .transaction::<_, (), DbErr>(|txn| {
+            Box::pin(async move {
+                let mut tag_ids = database::tags::Query::find_by_bookmark_id(txn, bookmark_id)
+                    .await?
+                    .into_iter()
+                    .map(|t| t.id)
+                    .collect::<Vec<i32>>();
+                for duplicate_id in &duplicate_ids {
+                    for tag in
+                        database::tags::Query::find_by_bookmark_id(txn, *duplicate_id).await?
+                    {
+                        if !tag_ids.contains(&tag.id) {
+                            database::bookmarks_tags::Mutation::create_link(
+                                txn,
+                                bookmark_id,
+                                tag.id,
+                            )
+                            .await?;
+                            tag_ids.push(tag.id);
+                        }
+                    }
+                    database::bookmarks_tags::Mutation::delete_all_links(txn, *duplicate_id)
+                        .await?;
+                    pins::Mutation::transfer(txn, *duplicate_id, bookmark_id).await?;
+                }
+
+                database::bookmarks::Mutation::merge(txn, bookmark_id, &duplicate_ids).await?;
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            MergeBookmarksResult::ServerError
+        })?;
+
+    let bookmark = database::bookmarks::Query::find_visible_by_id(
+        &state.database,
+        bookmark_id,
+        Some(user_info.id),
+    )
+    .await
+    .map_err(|_| MergeBookmarksResult::ServerError)?
+    .ok_or(MergeBookmarksResult::ServerError)?;
+
+    Ok(MergeBookmarksResult::Success(Box::new(into_response(
+        bookmark,
+        Some(&user_info),
+    ))))
+}
+
+pub async fn refresh_bookmark(
+    State(state): State<AppState>,
+    Path(bookmark_id): Path<i32>,
+    Extension(user_info): Extension<UserInfo>,
+) -> Result<RefreshBookmarkResult, RefreshBookmarkResult> {
+    if database::bookmarks::Query::find_visible_by_id(
+        &state.database,
+        bookmark_id,
+        Some(user_info.id),
+    )
+    .await
+    .map_err(|_| RefreshBookmarkResult::ServerError)?
+    .ok_or(RefreshBookmarkResult::NotFound(
+        bookmark_id,
+        format!("Bookmark '{}' not found", bookmark_id),
+    ))?
+    .user_id
+        != user_info.id
+    {
+        return Err(RefreshBookmarkResult::Forbidden);
+    };
+
+    database::bookmarks::Mutation::request_refresh(&state.database, bookmark_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            RefreshBookmarkResult::ServerError
+        })?;
+    state.refresher.wake();
+
+    Ok(RefreshBookmarkResult::Success)
+}
+
+/// Requests the refresh of the metadata of the user's bookmarks matching the search criteria.
+pub async fn refresh_bookmarks(
+    Query(query): Query<GetBookmarksQueryParams>,
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+) -> Result<RefreshBookmarksResult, RefreshBookmarksResult> {
+    let criteria =
+        SearchCriteria::try_from(&query).map_err(RefreshBookmarksResult::InvalidParameter)?;
+
+    let count = database::bookmarks::Mutation::request_refresh_matching(
+        &state.database,
+        user_info.id,
+        &criteria,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("{}", e);
+        RefreshBookmarksResult::ServerError
+    })?;
+    state.refresher.wake();
+
+    Ok(RefreshBookmarksResult::Success(RefreshBookmarksResponse {
+        count,
+    }))
+}
+
 pub async fn get_bookmarks_stats(
     Extension(user_info): Extension<Option<UserInfo>>,
     State(state): State<AppState>,
@@ -403,3 +764,43 @@ pub async fn get_bookmarks_stats(
         },
     ))
 }
+
+pub async fn get_bookmarks_timeline(
+    Query(query): Query<GetBookmarksQueryParams>,
+    Query(timeline_query): Query<GetBookmarksTimelineQueryParams>,
+    Extension(user_info): Extension<Option<UserInfo>>,
+    State(state): State<AppState>,
+) -> Result<GetBookmarksTimelineResult, GetBookmarksTimelineResult> {
+    let criteria =
+        SearchCriteria::try_from(&query).map_err(GetBookmarksTimelineResult::InvalidParameter)?;
+
+    let granularity = timeline_query
+        .granularity
+        // todo: no manual deserialize
+        .map(|v| Granularity::try_from(v.as_str()))
+        .unwrap_or(Ok(Granularity::Month))
+        .map_err(|_| {
+            GetBookmarksTimelineResult::InvalidParameter(
+                "Unsupported value provided for the 'granularity' query parameter".to_string(),
+            )
+        })?;
+
+    let buckets = database::bookmarks::Query::timeline(
+        &state.database,
+        user_info.map(|u| u.id),
+        &criteria,
+        granularity,
+    )
+    .await
+    .map_err(|_| GetBookmarksTimelineResult::ServerError)?
+    .into_iter()
+    .map(|(date, count)| TimelineBucket {
+        date,
+        count: count as u64,
+    })
+    .collect::<Vec<TimelineBucket>>();
+
+    Ok(GetBookmarksTimelineResult::Success(
+        GetBookmarksTimelineResponse { buckets },
+    ))
+}
@@ -0,0 +1,26 @@
+use crate::database::domains;
+use crate::sessions::session::UserInfo;
+use crate::AppState;
+use axum::extract::State;
+use axum::Extension;
+use rest_api::domains::{Domain, GetDomainsResult};
+
+pub async fn get_domains(
+    Extension(user_info): Extension<Option<UserInfo>>,
+    State(state): State<AppState>,
+) -> Result<GetDomainsResult, GetDomainsResult> {
+    let domains = domains::Query::find_by_user_id(&state.database, user_info.map(|u| u.id))
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            GetDomainsResult::ServerError
+        })?
+        .into_iter()
+        .map(|(name, count)| Domain {
+            name,
+            count: count as i32,
+        })
+        .collect::<Vec<Domain>>();
+
+    Ok(GetDomainsResult::Success(domains))
+}
@@ -0,0 +1,105 @@
+use crate::database::query_param_rules;
+use crate::query_params::QueryParamRule as CompiledRule;
+use crate::sessions::session::UserInfo;
+use crate::AppState;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use entity::query_param_rule::Model;
+use rest_api::query_params::{
+    DeleteQueryParamRuleResult, GetQueryParamRulesResult, QueryParamRule,
+    SaveQueryParamRuleRequest, SaveQueryParamRuleResult,
+};
+
+fn into_response(model: Model) -> QueryParamRule {
+    QueryParamRule {
+        id: model.id,
+        pattern: model.pattern,
+        domain: model.domain,
+        ignored: model.ignored,
+        instance: model.user_id.is_none(),
+    }
+}
+
+fn is_admin(state: &AppState, user_info: &UserInfo) -> bool {
+    state.admins.contains(&user_info.username)
+}
+
+pub async fn get_query_param_rules(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+) -> Result<GetQueryParamRulesResult, GetQueryParamRulesResult> {
+    let mut rules = query_param_rules::Query::find_applicable(&state.database, Some(user_info.id))
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            GetQueryParamRulesResult::ServerError
+        })?
+        .into_iter()
+        .map(into_response)
+        .collect::<Vec<QueryParamRule>>();
+    rules.sort_by_key(|rule| !rule.instance);
+
+    Ok(GetQueryParamRulesResult::Success(rules))
+}
+
+pub async fn create_query_param_rule(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Json(request): Json<SaveQueryParamRuleRequest>,
+) -> Result<SaveQueryParamRuleResult, SaveQueryParamRuleResult> {
+    if request.instance && !is_admin(&state, &user_info) {
+        return Err(SaveQueryParamRuleResult::Forbidden);
+    }
+
+    let pattern = request.pattern.trim().to_string();
+    let domain = request
+        .domain
+        .map(|d| d.trim().trim_start_matches("www.").to_lowercase())
+        .filter(|d| !d.is_empty());
+    CompiledRule::compile(&pattern, domain.as_deref(), request.ignored)
+        .map_err(SaveQueryParamRuleResult::InvalidParameter)?;
+
+    let rule = query_param_rules::Mutation::create(
+        &state.database,
+        query_param_rules::QueryParamRuleData {
+            user_id: (!request.instance).then_some(user_info.id),
+            pattern,
+            domain,
+            ignored: request.ignored,
+        },
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("{}", e);
+        SaveQueryParamRuleResult::ServerError
+    })?;
+
+    Ok(SaveQueryParamRuleResult::Success(into_response(rule)))
+}
+
+/// Deletes a rule of the user or, for the administrators, of the instance.
+pub async fn delete_query_param_rule(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<DeleteQueryParamRuleResult, DeleteQueryParamRuleResult> {
+    let mut owners = vec![Some(user_info.id)];
+    if is_admin(&state, &user_info) {
+        owners.push(None);
+    }
+
+    for owner in owners {
+        if query_param_rules::Mutation::delete(&state.database, id, owner)
+            .await
+            .map_err(|e| {
+                tracing::error!("{}", e);
+                DeleteQueryParamRuleResult::ServerError
+            })?
+            > 0
+        {
+            return Ok(DeleteQueryParamRuleResult::Success);
+        }
+    }
+
+    Err(DeleteQueryParamRuleResult::NotFound)
+}
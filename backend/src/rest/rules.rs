@@ -0,0 +1,188 @@
+use crate::database::{bookmarks, bookmarks_tags, pins, rules, tags};
+use crate::rules::{split_tags, Rule as CompiledRule, Rules};
+use crate::sessions::session::UserInfo;
+use crate::AppState;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use entity::rule::Model;
+use rest_api::rules::{
+    ApplyRulesResponse, ApplyRulesResult, DeleteRuleResult, GetRulesResult, Rule, SaveRuleRequest,
+    SaveRuleResult,
+};
+use sea_orm::{DbErr, TransactionTrait};
+
+fn into_response(model: Model) -> Rule {
+    Rule {
+        id: model.id,
+        url_pattern: model.url_pattern,
+        url_regex: model.url_regex,
+        title_pattern: model.title_pattern,
+        tags: split_tags(&model.tags),
+        private: model.private,
+        pin: model.pin,
+    }
+}
+
+/// Normalizes the request, making sure the rule can be compiled.
+fn validate(request: SaveRuleRequest) -> Result<rules::RuleData, SaveRuleResult> {
+    fn pattern(pattern: Option<String>) -> Option<String> {
+        pattern
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+    }
+
+    let url_pattern = pattern(request.url_pattern);
+    let title_pattern = pattern(request.title_pattern);
+    let tags = request
+        .tags
+        .iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<String>>();
+    if tags.iter().any(|t| t.contains(char::is_whitespace)) {
+        return Err(SaveRuleResult::InvalidParameter(
+            "Tags cannot contain spaces".to_string(),
+        ));
+    }
+
+    CompiledRule::compile(
+        url_pattern.as_deref(),
+        request.url_regex,
+        title_pattern.as_deref(),
+        &tags,
+        request.private,
+        request.pin,
+    )
+    .map_err(SaveRuleResult::InvalidParameter)?;
+
+    Ok(rules::RuleData {
+        url_pattern,
+        url_regex: request.url_regex,
+        title_pattern,
+        tags: tags.join(" "),
+        private: request.private,
+        pin: request.pin,
+    })
+}
+
+pub async fn get_rules(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+) -> Result<GetRulesResult, GetRulesResult> {
+    let rules = rules::Query::find_by_user_id(&state.database, user_info.id)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            GetRulesResult::ServerError
+        })?
+        .into_iter()
+        .map(into_response)
+        .collect::<Vec<Rule>>();
+
+    Ok(GetRulesResult::Success(rules))
+}
+
+pub async fn create_rule(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Json(request): Json<SaveRuleRequest>,
+) -> Result<SaveRuleResult, SaveRuleResult> {
+    let data = validate(request)?;
+
+    let rule = rules::Mutation::create(&state.database, user_info.id, data)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            SaveRuleResult::ServerError
+        })?;
+
+    Ok(SaveRuleResult::Success(into_response(rule)))
+}
+
+pub async fn update_rule(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<SaveRuleRequest>,
+) -> Result<SaveRuleResult, SaveRuleResult> {
+    let data = validate(request)?;
+
+    let rule = rules::Mutation::update(&state.database, id, user_info.id, data)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            SaveRuleResult::ServerError
+        })?
+        .ok_or(SaveRuleResult::NotFound)?;
+
+    Ok(SaveRuleResult::Success(into_response(rule)))
+}
+
+pub async fn delete_rule(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<DeleteRuleResult, DeleteRuleResult> {
+    match rules::Mutation::delete(&state.database, id, user_info.id)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            DeleteRuleResult::ServerError
+        })? {
+        0 => Err(DeleteRuleResult::NotFound),
+        _ => Ok(DeleteRuleResult::Success),
+    }
+}
+
+/// Applies the rules to all the bookmarks of the user. Rules only ever add: tags a rule would
+/// add are kept, as well as the privacy and the pins of the bookmarks no rule matches.
+pub async fn apply_rules(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+) -> Result<ApplyRulesResult, ApplyRulesResult> {
+    let count = state
+        .database
+        .transaction::<_, u64, DbErr>(|txn| {
+            Box::pin(async move {
+                let rules = Rules::load(txn, user_info.id).await?;
+
+                let mut count = 0;
+                for bookmark in bookmarks::Query::find_by_user_id(txn, user_info.id).await? {
+                    let actions = rules.apply(&bookmark.url, bookmark.title.as_deref());
+                    if actions.is_empty() {
+                        continue;
+                    }
+                    count += 1;
+
+                    let mut tag_ids = tags::Query::find_by_bookmark_id(txn, bookmark.id)
+                        .await?
+                        .into_iter()
+                        .map(|t| t.id)
+                        .collect::<Vec<i32>>();
+                    for tag in actions.tags {
+                        let tag_id = tags::Mutation::create_tag(txn, user_info.id, tag).await?.id;
+                        if !tag_ids.contains(&tag_id) {
+                            bookmarks_tags::Mutation::create_link(txn, bookmark.id, tag_id).await?;
+                            tag_ids.push(tag_id);
+                        }
+                    }
+
+                    if actions.private && !bookmark.private {
+                        bookmarks::Mutation::make_private(txn, bookmark.id).await?;
+                    }
+                    if actions.pin {
+                        pins::Mutation::pin(txn, bookmark.id, user_info.id).await?;
+                    }
+                }
+
+                Ok(count)
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            ApplyRulesResult::ServerError
+        })?;
+
+    Ok(ApplyRulesResult::Success(ApplyRulesResponse { count }))
+}
@@ -0,0 +1,133 @@
+use crate::database::saved_searches;
+use crate::sessions::session::UserInfo;
+use crate::AppState;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use entity::saved_search::Model;
+use rest_api::saved_searches::{
+    DeleteSavedSearchResult, GetSavedSearchesResult, SaveSearchRequest, SaveSearchResult,
+    SavedSearch,
+};
+use sea_orm::{DbErr, TransactionTrait};
+
+fn into_response(model: Model) -> SavedSearch {
+    SavedSearch {
+        id: model.id,
+        name: model.name,
+        query: model.query,
+        is_default: model.is_default,
+    }
+}
+
+/// The saved searches names are limited to the length of their column.
+const MAX_NAME_LENGTH: usize = 255;
+
+fn name(name: &str) -> Result<String, SaveSearchResult> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(SaveSearchResult::InvalidName);
+    }
+    Ok(name.to_string())
+}
+
+pub async fn get_saved_searches(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+) -> Result<GetSavedSearchesResult, GetSavedSearchesResult> {
+    let saved_searches = saved_searches::Query::find_by_user_id(&state.database, user_info.id)
+        .await
+        .map_err(|_| GetSavedSearchesResult::ServerError)?
+        .into_iter()
+        .map(into_response)
+        .collect::<Vec<SavedSearch>>();
+
+    Ok(GetSavedSearchesResult::Success(saved_searches))
+}
+
+pub async fn create_saved_search(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Json(request): Json<SaveSearchRequest>,
+) -> Result<SaveSearchResult, SaveSearchResult> {
+    let name = name(&request.name)?;
+
+    if saved_searches::Query::find_by_name(&state.database, &name, user_info.id)
+        .await
+        .map_err(|_| SaveSearchResult::ServerError)?
+        .is_some()
+    {
+        return Err(SaveSearchResult::Conflict);
+    }
+
+    let saved_search = state
+        .database
+        .transaction::<_, Model, DbErr>(|txn| {
+            Box::pin(async move {
+                saved_searches::Mutation::create(
+                    txn,
+                    user_info.id,
+                    name,
+                    request.query,
+                    request.is_default,
+                )
+                .await
+            })
+        })
+        .await
+        .map_err(|_| SaveSearchResult::ServerError)?;
+
+    Ok(SaveSearchResult::Success(into_response(saved_search)))
+}
+
+pub async fn update_saved_search(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(request): Json<SaveSearchRequest>,
+) -> Result<SaveSearchResult, SaveSearchResult> {
+    let name = name(&request.name)?;
+
+    if saved_searches::Query::find_by_name(&state.database, &name, user_info.id)
+        .await
+        .map_err(|_| SaveSearchResult::ServerError)?
+        .map(|s| s.id != id)
+        .unwrap_or_default()
+    {
+        return Err(SaveSearchResult::Conflict);
+    }
+
+    let saved_search = state
+        .database
+        .transaction::<_, Option<Model>, DbErr>(|txn| {
+            Box::pin(async move {
+                saved_searches::Mutation::update(
+                    txn,
+                    id,
+                    user_info.id,
+                    name,
+                    request.query,
+                    request.is_default,
+                )
+                .await
+            })
+        })
+        .await
+        .map_err(|_| SaveSearchResult::ServerError)?
+        .ok_or(SaveSearchResult::NotFound)?;
+
+    Ok(SaveSearchResult::Success(into_response(saved_search)))
+}
+
+pub async fn delete_saved_search(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> Result<DeleteSavedSearchResult, DeleteSavedSearchResult> {
+    match saved_searches::Mutation::delete(&state.database, id, user_info.id)
+        .await
+        .map_err(|_| DeleteSavedSearchResult::ServerError)?
+    {
+        0 => Err(DeleteSavedSearchResult::NotFound),
+        _ => Ok(DeleteSavedSearchResult::Success),
+    }
+}
@@ -1,5 +1,6 @@
-use crate::database::{bookmarks, bookmarks_tags, tags};
+use crate::database::{bookmarks, bookmarks_tags, pins, tags};
 use crate::rest::json::Json;
+use crate::rules::Rules;
 use crate::sessions::session::UserInfo;
 use crate::AppState;
 use axum::extract::State;
@@ -70,7 +71,19 @@ pub async fn shaarli_import_api(
         .database
         .transaction::<_, (), DbErr>(|txn| {
             Box::pin(async move {
+                let rules = Rules::load(txn, user_info.id).await?;
+
                 for bookmark in bookmarks {
+                    // the pages already bookmarked, or appearing twice in the import, are skipped
+                    if bookmarks::Query::find_by_url(txn, user_info.id, &bookmark.url)
+                        .await?
+                        .is_some()
+                    {
+                        log::info!("Skipping {}: already bookmarked", bookmark.url);
+                        continue;
+                    }
+                    let actions = rules.apply(&bookmark.url, Some(bookmark.title.as_str()));
+
                     let bookmark_id = bookmarks::Mutation::import_bookmark(
                         txn,
                         bookmark.url.clone(),
@@ -79,23 +92,31 @@ pub async fn shaarli_import_api(
                         bookmark.created,
                         (bookmark.updated != bookmark.created).then_some(bookmark.updated),
                         user_info.id,
-                        bookmark.private,
+                        bookmark.private || actions.private,
                     )
                     .await?
                     .id;
 
                     let unique_tags = {
                         let mut tags = bookmark.tags;
+                        tags.extend(actions.tags);
                         tags.sort();
                         tags.dedup();
                         tags
                     };
+                    let mut tag_ids = Vec::new();
                     for tag in unique_tags {
-                        let tag_id = tags::Mutation::create_tag(txn, tag.to_lowercase())
-                            .await?
-                            .id;
+                        let tag_id = tags::Mutation::create_tag(txn, user_info.id, tag).await?.id;
+
+                        // aliases and case variants may resolve to a tag that is already linked
+                        if !tag_ids.contains(&tag_id) {
+                            bookmarks_tags::Mutation::create_link(txn, bookmark_id, tag_id).await?;
+                            tag_ids.push(tag_id);
+                        }
+                    }
 
-                        bookmarks_tags::Mutation::create_link(txn, bookmark_id, tag_id).await?;
+                    if actions.pin {
+                        pins::Mutation::pin(txn, bookmark_id, user_info.id).await?;
                     }
                 }
 
@@ -0,0 +1,76 @@
+use crate::database::{bookmarks, domains, tags};
+use crate::rest::tags::into_tag;
+use crate::sessions::session::UserInfo;
+use crate::AppState;
+use axum::extract::{Query, State};
+use axum::Extension;
+use rest_api::domains::Domain;
+use rest_api::suggestions::{GetSuggestionsResponse, GetSuggestionsResult};
+use rest_api::tags::Tag;
+use serde::Deserialize;
+
+const MAX_SUGGESTIONS: usize = 5;
+
+#[derive(Deserialize)]
+pub struct GetSuggestionsQueryParams {
+    prefix: Option<String>,
+}
+
+pub async fn get_suggestions(
+    Query(query): Query<GetSuggestionsQueryParams>,
+    Extension(user_info): Extension<Option<UserInfo>>,
+    State(state): State<AppState>,
+) -> Result<GetSuggestionsResult, GetSuggestionsResult> {
+    let prefix = query.prefix.unwrap_or_default().trim().to_lowercase();
+    if prefix.is_empty() {
+        return Ok(GetSuggestionsResult::Success(GetSuggestionsResponse {
+            tags: vec![],
+            titles: vec![],
+            domains: vec![],
+        }));
+    }
+    let user_id = user_info.map(|u| u.id);
+
+    let tags =
+        tags::Query::find_by_prefix(&state.database, user_id, &prefix, MAX_SUGGESTIONS as u64)
+            .await
+            .map_err(|e| {
+                tracing::error!("{}", e);
+                GetSuggestionsResult::ServerError
+            })?
+            .into_iter()
+            .map(into_tag)
+            .collect::<Vec<Tag>>();
+
+    let titles = bookmarks::Query::find_titles_by_prefix(
+        &state.database,
+        user_id,
+        &prefix,
+        MAX_SUGGESTIONS as u64,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("{}", e);
+        GetSuggestionsResult::ServerError
+    })?;
+
+    let domains =
+        domains::Query::find_by_prefix(&state.database, user_id, &prefix, MAX_SUGGESTIONS as u64)
+            .await
+            .map_err(|e| {
+                tracing::error!("{}", e);
+                GetSuggestionsResult::ServerError
+            })?
+            .into_iter()
+            .map(|(name, count)| Domain {
+                name,
+                count: count as i32,
+            })
+            .collect::<Vec<Domain>>();
+
+    Ok(GetSuggestionsResult::Success(GetSuggestionsResponse {
+        tags,
+        titles,
+        domains,
+    }))
+}
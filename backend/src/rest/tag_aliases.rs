@@ -0,0 +1,85 @@
+use crate::database::{tag_aliases, tags};
+use crate::sessions::session::UserInfo;
+use crate::AppState;
+use axum::extract::{Path, State};
+use axum::{Extension, Json};
+use rest_api::tag_aliases::{
+    CreateTagAliasResult, DeleteTagAliasResult, GetTagAliasesResult, TagAlias,
+};
+
+pub async fn get_tag_aliases(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+) -> Result<GetTagAliasesResult, GetTagAliasesResult> {
+    let aliases = tag_aliases::Query::find_by_user_id(&state.database, user_info.id)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            GetTagAliasesResult::ServerError
+        })?
+        .into_iter()
+        .map(|(alias, tag)| TagAlias { alias, tag })
+        .collect::<Vec<TagAlias>>();
+
+    Ok(GetTagAliasesResult::Success(aliases))
+}
+
+pub async fn create_tag_alias(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Json(request): Json<TagAlias>,
+) -> Result<CreateTagAliasResult, CreateTagAliasResult> {
+    let alias = request.alias.trim().to_lowercase();
+    if alias.is_empty() || alias.contains(char::is_whitespace) {
+        return Err(CreateTagAliasResult::InvalidName);
+    }
+
+    let tag = tags::Query::find_by_name(&state.database, user_info.id, &request.tag)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            CreateTagAliasResult::ServerError
+        })?
+        .ok_or(CreateTagAliasResult::NotFound)?;
+
+    // an alias cannot shadow an existing tag nor another alias
+    if tags::Query::find_by_name(&state.database, user_info.id, &alias)
+        .await
+        .map_err(|_| CreateTagAliasResult::ServerError)?
+        .is_some()
+        || tag_aliases::Query::find_by_alias(&state.database, user_info.id, &alias)
+            .await
+            .map_err(|_| CreateTagAliasResult::ServerError)?
+            .is_some()
+    {
+        return Err(CreateTagAliasResult::Conflict);
+    }
+
+    let alias = tag_aliases::Mutation::create(&state.database, user_info.id, alias, tag.id)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            CreateTagAliasResult::ServerError
+        })?;
+
+    Ok(CreateTagAliasResult::Success(TagAlias {
+        alias: alias.alias,
+        tag: tag.name,
+    }))
+}
+
+pub async fn delete_tag_alias(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(alias): Path<String>,
+) -> Result<DeleteTagAliasResult, DeleteTagAliasResult> {
+    match tag_aliases::Mutation::delete(&state.database, user_info.id, &alias.to_lowercase())
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            DeleteTagAliasResult::ServerError
+        })? {
+        0 => Err(DeleteTagAliasResult::NotFound),
+        _ => Ok(DeleteTagAliasResult::Success),
+    }
+}
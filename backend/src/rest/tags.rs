@@ -1,16 +1,24 @@
-use crate::database::tags;
-use crate::database::tags::SortOrder;
+use crate::database::tags::{SortOrder, TagsAndCount};
+use crate::database::{bookmarks, bookmarks_tags, pins, tag_aliases, tags};
 use crate::sessions::session::UserInfo;
 use crate::AppState;
-use axum::extract::{Query, State};
-use axum::Extension;
-use rest_api::tags::{GetTagsResult, Tag};
+use axum::extract::{Path, Query, State};
+use axum::{Extension, Json};
+use common::TAG_SEPARATOR;
+use rest_api::tags::{
+    DeleteTagResult, GetTagResponse, GetTagResult, GetTagsResult, MergeTagsRequest,
+    RenameTagRequest, Tag, TagMetadata, UpdateTagMetadataResult, UpdateTagsResult,
+};
+use sea_orm::{DbErr, TransactionTrait};
 use serde::Deserialize;
+use std::collections::BTreeMap;
+use urlencoding::decode;
 
 // todo review query param serialization and struct shared with API
 #[derive(Deserialize)]
 pub struct GetTagsQueryParams {
     order: Option<String>,
+    tree: Option<bool>,
 }
 
 pub async fn get_tags(
@@ -28,6 +36,7 @@ pub async fn get_tags(
                 "Unsupported value provided for the 'sort' query parameter".to_string(),
             )
         })?;
+    let by_name = matches!(order, SortOrder::Name);
 
     let tags =
         tags::Query::find_by_user_id_order_by(&state.database, user_info.map(|u| u.id), order)
@@ -37,11 +46,356 @@ pub async fn get_tags(
                 GetTagsResult::ServerError
             })?
             .into_iter()
-            .map(|t| Tag {
-                name: t.name.to_lowercase(),
-                count: t.count as i32,
-            })
+            .map(into_tag)
             .collect::<Vec<Tag>>();
 
+    if query.tree.unwrap_or_default() {
+        return Ok(GetTagsResult::Success(into_tree(tags, by_name)));
+    }
+
     Ok(GetTagsResult::Success(tags))
 }
+
+const MAX_RELATED_TAGS: u64 = 20;
+
+// todo review query param serialization and struct shared with API
+#[derive(Deserialize)]
+pub struct GetRelatedTagsQueryParams {
+    tags: Option<String>,
+    descendants: Option<bool>,
+    count: Option<u64>,
+}
+
+pub async fn get_related_tags(
+    Query(query): Query<GetRelatedTagsQueryParams>,
+    Extension(user_info): Extension<Option<UserInfo>>,
+    State(state): State<AppState>,
+) -> Result<GetTagsResult, GetTagsResult> {
+    let tags = query
+        .tags
+        .as_ref()
+        // todo: no manual deserialize
+        .map(|tags| {
+            tags.split('+')
+                .map(decode)
+                .map(|t| t.unwrap_or_default())
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string())
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+    if tags.is_empty() {
+        return Err(GetTagsResult::InvalidParameter(
+            "At least one tag must be provided in the 'tags' query parameter".to_string(),
+        ));
+    }
+
+    let related = tags::Query::find_related(
+        &state.database,
+        user_info.map(|u| u.id),
+        &tags,
+        query.descendants.unwrap_or_default(),
+        query.count.unwrap_or(10).min(MAX_RELATED_TAGS),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("{}", e);
+        GetTagsResult::ServerError
+    })?
+    .into_iter()
+    .map(into_tag)
+    .collect::<Vec<Tag>>();
+
+    Ok(GetTagsResult::Success(related))
+}
+
+pub fn into_tag(tag: TagsAndCount) -> Tag {
+    Tag {
+        name: tag.name,
+        count: tag.count as i32,
+        total_count: None,
+        children: vec![],
+        metadata: TagMetadata {
+            color: tag.color,
+            description: tag.description,
+            icon: tag.icon,
+        },
+    }
+}
+
+fn parent(name: &str) -> Option<&str> {
+    name.rsplit_once(TAG_SEPARATOR).map(|(parent, _)| parent)
+}
+
+/// Arranges the tags in a forest according to their names, compared case-insensitively, adding
+/// the missing ancestors with a count of 0. Siblings are sorted by name or by decreasing total
+/// count.
+fn into_tree(tags: Vec<Tag>, by_name: bool) -> Vec<Tag> {
+    // keyed by the lowercase name, the value holding the name as displayed
+    let mut counts = BTreeMap::<String, (String, i32, TagMetadata)>::new();
+    for tag in tags {
+        counts
+            .entry(tag.name.to_lowercase())
+            .and_modify(|(_, count, _)| *count += tag.count)
+            .or_insert((tag.name, tag.count, tag.metadata));
+    }
+
+    let names = counts
+        .values()
+        .map(|(name, _, _)| name.clone())
+        .collect::<Vec<String>>();
+    for name in names {
+        let mut ancestor = parent(&name);
+        while let Some(name) = ancestor {
+            counts
+                .entry(name.to_lowercase())
+                .or_insert_with(|| (name.to_string(), 0, TagMetadata::default()));
+            ancestor = parent(name);
+        }
+    }
+
+    fn children(
+        of: Option<&str>,
+        counts: &BTreeMap<String, (String, i32, TagMetadata)>,
+        by_name: bool,
+    ) -> Vec<Tag> {
+        let mut children = counts
+            .iter()
+            .filter(|(key, _)| parent(key) == of)
+            .map(|(key, (name, count, metadata))| {
+                let children = children(Some(key), counts, by_name);
+                Tag {
+                    name: name.clone(),
+                    count: *count,
+                    total_count: Some(
+                        count
+                            + children
+                                .iter()
+                                .map(|c| c.total_count.unwrap_or_default())
+                                .sum::<i32>(),
+                    ),
+                    children,
+                    metadata: metadata.clone(),
+                }
+            })
+            .collect::<Vec<Tag>>();
+        if !by_name {
+            children.sort_by_key(|c| std::cmp::Reverse(c.total_count));
+        }
+        children
+    }
+
+    children(None, &counts, by_name)
+}
+
+pub async fn rename_tag(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<RenameTagRequest>,
+) -> Result<UpdateTagsResult, UpdateTagsResult> {
+    merge(&state, user_info.id, vec![name], &request.name).await
+}
+
+pub async fn merge_tags(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Json(request): Json<MergeTagsRequest>,
+) -> Result<UpdateTagsResult, UpdateTagsResult> {
+    merge(&state, user_info.id, request.tags, &request.into).await
+}
+
+async fn merge(
+    state: &AppState,
+    user_id: i32,
+    tags: Vec<String>,
+    into: &str,
+) -> Result<UpdateTagsResult, UpdateTagsResult> {
+    let into = into.trim().to_string();
+    if into.is_empty() || into.contains(char::is_whitespace) {
+        return Err(UpdateTagsResult::InvalidName);
+    }
+    let found = state
+        .database
+        .transaction::<_, bool, DbErr>(|txn| {
+            Box::pin(async move {
+                if !tags::Mutation::merge(txn, user_id, &tags, into).await? {
+                    return Ok(false);
+                }
+                tags::Mutation::delete_orphans(txn, user_id).await?;
+                Ok(true)
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            UpdateTagsResult::ServerError
+        })?;
+
+    match found {
+        true => Ok(UpdateTagsResult::Success),
+        false => Err(UpdateTagsResult::NotFound),
+    }
+}
+
+pub async fn get_tag(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<GetTagResult, GetTagResult> {
+    let tag = tags::Query::find_by_name(&state.database, user_info.id, &name)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            GetTagResult::ServerError
+        })?
+        .ok_or(GetTagResult::NotFound)?;
+
+    let count = bookmarks_tags::Query::find_bookmark_ids_by_tag_id(&state.database, tag.id)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            GetTagResult::ServerError
+        })?
+        .len();
+    let exclusive_count =
+        bookmarks_tags::Query::find_exclusive_bookmark_ids_by_tag_id(&state.database, tag.id)
+            .await
+            .map_err(|e| {
+                tracing::error!("{}", e);
+                GetTagResult::ServerError
+            })?
+            .len();
+
+    Ok(GetTagResult::Success(GetTagResponse {
+        name: tag.name,
+        count: count as i32,
+        exclusive_count: exclusive_count as i32,
+        metadata: TagMetadata {
+            color: tag.color,
+            description: tag.description,
+            icon: tag.icon,
+        },
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct DeleteTagQueryParams {
+    delete_bookmarks: Option<bool>,
+}
+
+pub async fn delete_tag(
+    Query(query): Query<DeleteTagQueryParams>,
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<DeleteTagResult, DeleteTagResult> {
+    let tag = tags::Query::find_by_name(&state.database, user_info.id, &name)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            DeleteTagResult::ServerError
+        })?
+        .ok_or(DeleteTagResult::NotFound)?;
+
+    state
+        .database
+        .transaction::<_, (), DbErr>(|txn| {
+            Box::pin(async move {
+                let bookmark_ids = match query.delete_bookmarks {
+                    Some(true) => {
+                        bookmarks_tags::Query::find_exclusive_bookmark_ids_by_tag_id(txn, tag.id)
+                            .await?
+                    }
+                    _ => vec![],
+                };
+
+                bookmarks_tags::Mutation::delete_all_tag_links(txn, tag.id).await?;
+                tag_aliases::Mutation::delete_all(txn, tag.id).await?;
+                for bookmark_id in bookmark_ids {
+                    pins::Mutation::unpin(txn, bookmark_id, user_info.id).await?;
+                    bookmarks::Mutation::delete_bookmark(txn, bookmark_id).await?;
+                }
+                tags::Mutation::delete_orphans(txn, user_info.id).await?;
+
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            DeleteTagResult::ServerError
+        })?;
+
+    Ok(DeleteTagResult::Success)
+}
+
+pub async fn update_tag_metadata(
+    Extension(user_info): Extension<UserInfo>,
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<TagMetadata>,
+) -> Result<UpdateTagMetadataResult, UpdateTagMetadataResult> {
+    let color = request
+        .color
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty());
+    if let Some(color) = &color {
+        if !is_hex_color(color) {
+            return Err(UpdateTagMetadataResult::InvalidParameter(
+                "The color must be written as #rgb or #rrggbb".to_string(),
+            ));
+        }
+    }
+
+    let icon = request
+        .icon
+        .map(|i| i.trim().to_string())
+        .filter(|i| !i.is_empty());
+    if let Some(icon) = &icon {
+        if icon.len() > 64
+            || !icon
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(UpdateTagMetadataResult::InvalidParameter(
+                "The icon must be the name of a Material icon".to_string(),
+            ));
+        }
+    }
+
+    let description = request
+        .description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+
+    let tag = tags::Mutation::update_metadata(
+        &state.database,
+        user_info.id,
+        &name,
+        color,
+        description,
+        icon,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("{}", e);
+        UpdateTagMetadataResult::ServerError
+    })?
+    .ok_or(UpdateTagMetadataResult::NotFound)?;
+
+    Ok(UpdateTagMetadataResult::Success(TagMetadata {
+        color: tag.color,
+        description: tag.description,
+        icon: tag.icon,
+    }))
+}
+
+fn is_hex_color(color: &str) -> bool {
+    match color.strip_prefix('#') {
+        Some(hex) => {
+            (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,143 @@
+use crate::database::{bookmarks, tags};
+use crate::query_params::QueryParamRules;
+use crate::rest::bookmarks::into_metadata;
+use crate::sessions::session::UserInfo;
+use crate::url;
+use crate::AppState;
+use axum::extract::{Path, State};
+use axum::Extension;
+use rest_api::urls::{GetUrlConflictResponse, GetUrlResponse, GetUrlResult};
+use std::collections::{HashMap, HashSet};
+
+const MAX_SUGGESTED_TAGS: usize = 10;
+const MAX_HISTORY_TAGS: u64 = 5;
+
+pub async fn get_url(
+    Extension(user_info): Extension<UserInfo>,
+    Path(url): Path<String>,
+    State(state): State<AppState>,
+) -> Result<GetUrlResult, GetUrlResult> {
+    let query_param_rules =
+        QueryParamRules::load(&state.database, &state.query_params, Some(user_info.id))
+            .await
+            .map_err(|e| {
+                tracing::error!("{}", e);
+                GetUrlResult::ServerError
+            })?;
+    let url = url::clean(url, &query_param_rules).ok_or(GetUrlResult::InvalidUrl)?;
+    if let Some(conflict) = find_conflict(&state, user_info.id, &url).await? {
+        return Ok(conflict);
+    }
+    log::info!("Fetching metadata about {}", &url);
+
+    let fetched = state.metadata_cache.fetch(&state.fetcher, url).await;
+    let page = fetched.page.unwrap_or_default();
+    // AMP pages tell which page they are the AMP version of
+    let url = page
+        .canonical_url
+        .as_deref()
+        .and_then(|canonical| url::amp_canonical(&fetched.url, canonical))
+        .unwrap_or(fetched.url);
+    // shorteners, e.g. t.co, are only known once followed
+    let url = url::clean(url.clone(), &query_param_rules).unwrap_or(url);
+    if let Some(conflict) = find_conflict(&state, user_info.id, &url).await? {
+        return Ok(conflict);
+    }
+
+    // the tags already used on the same host come first, then the ones of the page
+    let domain = url::domain(&url);
+    let mut candidates = match &domain {
+        Some(domain) => {
+            tags::Query::find_by_domain(&state.database, user_info.id, domain, MAX_HISTORY_TAGS)
+                .await
+                .map_err(|e| {
+                    tracing::error!("{}", e);
+                    GetUrlResult::ServerError
+                })?
+                .into_iter()
+                .map(|t| t.name)
+                .collect::<Vec<String>>()
+        }
+        None => vec![],
+    };
+    candidates.extend(page.keywords.iter().filter_map(|k| as_tag(k)));
+    candidates.extend(domain.as_deref().and_then(domain_tag));
+
+    let suggested_tags = suggest_tags(&state, user_info.id, candidates).await?;
+    // documents without a title are often named after it, e.g. `report-2023.pdf`
+    let title = page.title.or_else(|| url::file_name(&url));
+
+    Ok(GetUrlResult::Success(Box::new(GetUrlResponse {
+        url,
+        title,
+        description: page.description,
+        canonical_url: page.canonical_url,
+        kind: page.kind,
+        metadata: into_metadata(page.metadata),
+        suggested_tags,
+    })))
+}
+
+/// The labels country code top-level domains register sites under, e.g. `co` in `bbc.co.uk`.
+const SECOND_LEVEL_LABELS: [&str; 14] = [
+    "ac", "co", "com", "edu", "gob", "gov", "gv", "ltd", "ne", "net", "or", "org", "plc", "sch",
+];
+
+/// Returns the name of the site a domain belongs to, e.g. `github` for `gist.github.com` or
+/// `bbc` for `www.bbc.co.uk`.
+fn domain_tag(domain: &str) -> Option<String> {
+    let labels = domain.rsplit('.').collect::<Vec<&str>>();
+    let index = match (labels.first(), labels.get(1)) {
+        (Some(tld), Some(label)) if tld.len() == 2 && SECOND_LEVEL_LABELS.contains(label) => 2,
+        _ => 1,
+    };
+    labels.get(index).and_then(|label| as_tag(label))
+}
+
+/// Turns a keyword into a tag name: tags can neither be empty nor contain spaces.
+fn as_tag(keyword: &str) -> Option<String> {
+    let tag = keyword.split_whitespace().collect::<Vec<&str>>().join("-");
+    match tag.is_empty() {
+        true => None,
+        false => Some(tag),
+    }
+}
+
+/// Deduplicates the candidates regardless of their case, writing the ones the user already
+/// has the way their tags are.
+async fn suggest_tags(
+    state: &AppState,
+    user_id: i32,
+    mut candidates: Vec<String>,
+) -> Result<Vec<String>, GetUrlResult> {
+    let mut seen = HashSet::new();
+    candidates.retain(|t| seen.insert(t.to_lowercase()));
+    candidates.truncate(MAX_SUGGESTED_TAGS);
+
+    let existing = tags::Query::find_by_names(&state.database, user_id, &candidates)
+        .await
+        .map_err(|e| {
+            tracing::error!("{}", e);
+            GetUrlResult::ServerError
+        })?
+        .into_iter()
+        .map(|t| (t.name.to_lowercase(), t.name))
+        .collect::<HashMap<String, String>>();
+
+    Ok(candidates
+        .into_iter()
+        .map(|t| existing.get(&t.to_lowercase()).cloned().unwrap_or(t))
+        .collect())
+}
+
+/// Returns the conflict with the bookmark of the user pointing to the same page, if any.
+async fn find_conflict(
+    state: &AppState,
+    user_id: i32,
+    url: &str,
+) -> Result<Option<GetUrlResult>, GetUrlResult> {
+    Ok(bookmarks::Query::find_by_url(&state.database, user_id, url)
+        .await
+        .map_err(|_| GetUrlResult::ServerError)?
+        .map(|id| GetUrlResult::Conflict(GetUrlConflictResponse { id })))
+}
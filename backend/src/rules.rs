@@ -0,0 +1,219 @@
+use crate::database;
+use entity::rule::Model;
+use regex::{Regex, RegexBuilder};
+use sea_orm::{ConnectionTrait, DbErr};
+
+/// A rule with its patterns compiled.
+pub struct Rule {
+    url: Option<Regex>,
+    title: Option<Regex>,
+    tags: Vec<String>,
+    private: bool,
+    pin: bool,
+}
+
+/// What the rules matching a bookmark do to it.
+#[derive(Default)]
+pub struct Actions {
+    pub tags: Vec<String>,
+    pub private: bool,
+    pub pin: bool,
+}
+
+impl Actions {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && !self.private && !self.pin
+    }
+}
+
+impl Rule {
+    /// Compiles the patterns of a rule, returning a message describing the first invalid one.
+    pub fn compile(
+        url_pattern: Option<&str>,
+        url_regex: bool,
+        title_pattern: Option<&str>,
+        tags: &[String],
+        private: bool,
+        pin: bool,
+    ) -> Result<Self, String> {
+        let url = url_pattern
+            .map(|pattern| match url_regex {
+                true => Regex::new(pattern),
+                false => glob(pattern),
+            })
+            .transpose()
+            .map_err(|e| format!("Invalid URL pattern: {}", e))?;
+        let title = title_pattern
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| format!("Invalid title pattern: {}", e))?;
+
+        if url.is_none() && title.is_none() {
+            return Err("A rule needs an URL or a title pattern".to_string());
+        }
+        if tags.is_empty() && !private && !pin {
+            return Err("A rule needs to add tags, make private or pin".to_string());
+        }
+
+        Ok(Self {
+            url,
+            title,
+            tags: tags.to_vec(),
+            private,
+            pin,
+        })
+    }
+
+    /// Whether the bookmark matches all the patterns of the rule.
+    pub fn matches(&self, url: &str, title: Option<&str>) -> bool {
+        self.url
+            .as_ref()
+            .map(|regex| regex.is_match(url) || regex.is_match(without_scheme(url)))
+            .unwrap_or(true)
+            && self
+                .title
+                .as_ref()
+                .map(|regex| title.map(|t| regex.is_match(t)).unwrap_or_default())
+                .unwrap_or(true)
+    }
+}
+
+/// The tags of a rule, as stored.
+pub fn split_tags(tags: &str) -> Vec<String> {
+    tags.split_whitespace().map(|t| t.to_string()).collect()
+}
+
+/// The rules of a user, in their order of creation.
+pub struct Rules(Vec<Rule>);
+
+impl Rules {
+    pub async fn load<C>(db: &C, user_id: i32) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        database::rules::Query::find_by_user_id(db, user_id)
+            .await
+            .map(Self::from)
+    }
+
+    pub fn apply(&self, url: &str, title: Option<&str>) -> Actions {
+        self.0.iter().filter(|rule| rule.matches(url, title)).fold(
+            Actions::default(),
+            |mut actions, rule| {
+                for tag in &rule.tags {
+                    if !actions
+                        .tags
+                        .iter()
+                        .any(|t| t.to_lowercase() == tag.to_lowercase())
+                    {
+                        actions.tags.push(tag.clone());
+                    }
+                }
+                actions.private |= rule.private;
+                actions.pin |= rule.pin;
+                actions
+            },
+        )
+    }
+
+    /// Applies the rules to an edited bookmark, only if its URL or title changed: the rules
+    /// already acted on it, and the user may have undone some of their actions since.
+    pub fn apply_on_edit(
+        &self,
+        url: &str,
+        title: Option<&str>,
+        previous_url: &str,
+        previous_title: Option<&str>,
+    ) -> Actions {
+        if url == previous_url && title == previous_title {
+            return Actions::default();
+        }
+        self.apply(url, title)
+    }
+}
+
+impl From<Vec<Model>> for Rules {
+    fn from(models: Vec<Model>) -> Self {
+        Self(
+            models
+                .into_iter()
+                .filter_map(|model| {
+                    Rule::compile(
+                        model.url_pattern.as_deref(),
+                        model.url_regex,
+                        model.title_pattern.as_deref(),
+                        &split_tags(&model.tags),
+                        model.private,
+                        model.pin,
+                    )
+                    // patterns are validated when saved, this only happens if the regex syntax
+                    // changes
+                    .map_err(|e| log::warn!("Skipping rule {}: {}", model.id, e))
+                    .ok()
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Turns a glob, where `*` stands for any sequence of characters and `?` for any single one,
+/// into a case-insensitive regex matching whole strings.
+pub fn glob(pattern: &str) -> Result<Regex, regex::Error> {
+    let regex = pattern
+        .split('*')
+        .map(|part| {
+            part.split('?')
+                .map(regex::escape)
+                .collect::<Vec<String>>()
+                .join(".")
+        })
+        .collect::<Vec<String>>()
+        .join(".*");
+    RegexBuilder::new(&format!("^{}$", regex))
+        .case_insensitive(true)
+        .build()
+}
+
+fn without_scheme(url: &str) -> &str {
+    url.split_once("://").map(|(_, rest)| rest).unwrap_or(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rule, Rules};
+
+    fn pin_rule() -> Rules {
+        Rules(vec![Rule::compile(
+            Some("example.com/*"),
+            false,
+            None,
+            &["example".to_string()],
+            false,
+            true,
+        )
+        .unwrap()])
+    }
+
+    #[test]
+    fn manual_unpin_survives_edits() {
+        let url = "https://example.com/a";
+
+        // the user unpinned the bookmark and only edits its description
+        let actions = pin_rule().apply_on_edit(url, Some("A"), url, Some("A"));
+        assert!(actions.is_empty());
+        let pinned = false;
+        assert!(!(pinned || actions.pin));
+    }
+
+    #[test]
+    fn rules_apply_to_edited_urls_and_titles() {
+        let url = "https://example.com/a";
+
+        let actions = pin_rule().apply_on_edit(url, Some("B"), url, Some("A"));
+        assert!(actions.pin);
+        assert_eq!(actions.tags, vec!["example".to_string()]);
+
+        let actions = pin_rule().apply_on_edit(url, Some("A"), "https://example.org/a", Some("A"));
+        assert!(actions.pin);
+    }
+}
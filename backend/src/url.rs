@@ -1,31 +1,515 @@
+use crate::query_params::QueryParamRules;
+use common::url::is_params_fragment;
+pub use common::url::{domain, key};
 use url::Url;
 
-pub fn clean<U>(url: U, ignored_query_params: &[&str]) -> Option<String>
+/// The number of nested redirect wrappers and AMP caches unwrapped at most.
+const MAX_UNWRAPPING: usize = 5;
+
+/// The redirect wrappers: their host, their path and the query param holding the target URL.
+/// A host ending with `.*` stands for `.com` or any country code top-level domain, and matches
+/// the subdomains.
+const REDIRECT_WRAPPERS: [(&str, &str, &str); 12] = [
+    ("google.*", "/url", "q"),
+    ("google.*", "/url", "url"),
+    ("l.facebook.com", "/l.php", "u"),
+    ("lm.facebook.com", "/l.php", "u"),
+    ("l.messenger.com", "/l.php", "u"),
+    ("l.instagram.com", "/", "u"),
+    ("youtube.com", "/redirect", "q"),
+    ("out.reddit.com", "/", "url"),
+    ("away.vk.com", "/away.php", "to"),
+    ("slack-redir.net", "/link", "url"),
+    ("steamcommunity.com", "/linkfilter/", "url"),
+    ("t.umblr.com", "/redirect", "z"),
+];
+
+/// The AMP caches serving pages under `/<prefix>/s/<host>/<path>`, `s` meaning HTTPS.
+const AMP_CACHES: [(&str, &str); 3] = [
+    ("google.*", "/amp/"),
+    ("cdn.ampproject.org", "/c/"),
+    ("cdn.ampproject.org", "/v/"),
+];
+
+/// The query params the AMP caches add to the pages they serve.
+const AMP_CACHE_QUERY_PARAMS: [&str; 4] = ["amp_gsa", "amp_js_v", "amp_tf", "usqp"];
+
+/// The path segments, by their prefix, only tracking where the visitor comes from.
+const PATH_TRACKERS: [(&str, &str); 2] = [("amazon.*", "ref="), ("amzn.*", "ref=")];
+
+/// The fragment params tracking campaigns, on top of the ignored query params.
+const FRAGMENT_TRACKERS: [&str; 5] = ["xtor", "echobox", "at_medium", "at_campaign", "ito"];
+
+/// Normalizes an URL before it is bookmarked: the redirect wrappers and AMP caches are unwrapped,
+/// the tracking path segments, query params and fragments removed. The AMP versions of pages
+/// are only known once fetched, see [`amp_canonical`].
+pub fn clean<U>(url: U, query_param_rules: &QueryParamRules) -> Option<String>
 where
     U: Into<String>,
 {
-    let mut url = url.into();
-    if !(url.starts_with("http://") || url.starts_with("https://")) {
-        url = format!("http://{}", url);
+    let mut url = parse(url.into())?;
+
+    for _ in 0..MAX_UNWRAPPING {
+        match unwrap_redirect(&url).or_else(|| unwrap_amp_cache(&url)) {
+            Some(target) => url = target,
+            None => break,
+        }
+    }
+
+    // the parser lowercases the host, the trailing dot of a fully qualified one is removed too
+    if let Some(host) = url.host_str().and_then(|h| h.strip_suffix('.')) {
+        let host = host.to_string();
+        url.set_host(Some(&host)).ok()?;
+    }
+    let host = url
+        .host_str()
+        .map(|host| host.trim_start_matches("www.").to_string());
+
+    strip_path(&mut url, host.as_deref());
+    strip_query(&mut url, host.as_deref(), query_param_rules);
+    strip_fragment(&mut url, host.as_deref(), query_param_rules);
+
+    Some(url.to_string())
+}
+
+/// Parses an URL, assuming HTTP when it has no scheme.
+fn parse(url: String) -> Option<Url> {
+    let scheme = url.get(..8).unwrap_or(&url).to_lowercase();
+    let url = match scheme.starts_with("http://") || scheme.starts_with("https://") {
+        true => url,
+        false => format!("http://{}", url),
+    };
+    Url::parse(&url).ok()
+}
+
+/// Whether `host` is `pattern` or one of its subdomains, a pattern ending with `.*` matching
+/// `.com` and the country code top-level domains, e.g. `google.*` matches `www.google.co.uk`
+/// but not `google.evil.com`.
+fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_suffix(".*") {
+        Some(name) => {
+            let labels = host.split('.').collect::<Vec<&str>>();
+            labels
+                .iter()
+                .enumerate()
+                .any(|(index, label)| *label == name && is_top_level_domain(&labels[index + 1..]))
+        }
+        None => {
+            host == pattern
+                || host
+                    .strip_suffix(pattern)
+                    .map(|prefix| prefix.ends_with('.'))
+                    .unwrap_or_default()
+        }
+    }
+}
+
+/// Whether the labels are `com`, a country code, or `co` or `com` followed by a country code,
+/// e.g. `co.uk` or `com.au`.
+fn is_top_level_domain(labels: &[&str]) -> bool {
+    let is_country_code =
+        |label: &str| label.len() == 2 && label.chars().all(|c| c.is_ascii_lowercase());
+    match labels {
+        ["com"] => true,
+        [label] => is_country_code(label),
+        ["co" | "com", label] => is_country_code(label),
+        _ => false,
+    }
+}
+
+/// Returns the target of a redirect wrapper, e.g. `https://www.google.com/url?q=<target>`.
+fn unwrap_redirect(url: &Url) -> Option<Url> {
+    let host = url.host_str()?;
+    REDIRECT_WRAPPERS
+        .iter()
+        .filter(|(pattern, path, _)| host_matches(pattern, host) && url.path() == *path)
+        .find_map(|(_, _, param)| {
+            url.query_pairs()
+                .find(|(name, _)| name == param)
+                .and_then(|(_, target)| Url::parse(&target).ok())
+                .filter(|target| matches!(target.scheme(), "http" | "https"))
+        })
+}
+
+/// Returns the page an AMP cache serves, e.g.
+/// `https://example-com.cdn.ampproject.org/c/s/example.com/article` for
+/// `https://example.com/article`.
+fn unwrap_amp_cache(url: &Url) -> Option<Url> {
+    let host = url.host_str()?;
+    let (_, prefix) = AMP_CACHES
+        .iter()
+        .find(|(pattern, prefix)| host_matches(pattern, host) && url.path().starts_with(prefix))?;
+
+    let rest = &url.path()[prefix.len()..];
+    let (scheme, rest) = match rest.strip_prefix("s/") {
+        Some(rest) => ("https", rest),
+        None => ("http", rest),
+    };
+    if rest.is_empty() || !rest.split('/').next()?.contains('.') {
+        return None;
     }
 
-    match Url::parse(&url) {
-        Err(_) => None,
-        Ok(mut url) => {
-            let filtered_query_params = url
-                .query_pairs()
-                .filter(|(name, _)| !ignored_query_params.contains(&name.as_ref()))
-                .map(|(name, value)| (name.into_owned(), value.into_owned()))
-                .collect::<Vec<(String, String)>>();
+    let mut target = Url::parse(&format!("{}://{}", scheme, rest)).ok()?;
+    let query_params = url
+        .query_pairs()
+        .filter(|(name, _)| !AMP_CACHE_QUERY_PARAMS.contains(&name.to_lowercase().as_str()))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect::<Vec<(String, String)>>();
+    if !query_params.is_empty() {
+        target.query_pairs_mut().extend_pairs(&query_params);
+    }
+    target.set_fragment(url.fragment());
+    Some(target)
+}
+
+/// Removes the tracking segments of the path, e.g. Amazon's `/dp/B00X/ref=sr_1_1`, and the
+/// session ids, e.g. `;jsessionid=...`.
+fn strip_path(url: &mut Url, host: Option<&str>) {
+    let segments = match url.path_segments() {
+        Some(segments) => segments.map(|s| s.to_string()).collect::<Vec<String>>(),
+        None => return,
+    };
+
+    let cleaned = segments
+        .into_iter()
+        .filter_map(|segment| {
+            let segment = match segment.to_lowercase().find(";jsessionid=") {
+                Some(position) => segment[..position].to_string(),
+                None => segment,
+            };
+            let is_tracker = host
+                .map(|host| {
+                    PATH_TRACKERS.iter().any(|(pattern, prefix)| {
+                        host_matches(pattern, host) && segment.starts_with(prefix)
+                    })
+                })
+                .unwrap_or_default();
+            (!is_tracker).then_some(segment)
+        })
+        .collect::<Vec<String>>();
 
+    url.set_path(&format!("/{}", cleaned.join("/").trim_start_matches('/')));
+}
+
+/// Removes the ignored query params.
+fn strip_query(url: &mut Url, host: Option<&str>, query_param_rules: &QueryParamRules) {
+    let filtered_query_params = url
+        .query_pairs()
+        .filter(|(name, _)| !query_param_rules.is_ignored(host, name))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect::<Vec<(String, String)>>();
+
+    match filtered_query_params.is_empty() {
+        true => url.set_query(None),
+        false => {
             url.query_pairs_mut()
                 .clear()
                 .extend_pairs(&filtered_query_params);
-            let mut url = url.to_string();
-            if url.ends_with('?') {
-                url.remove(url.len() - 1);
-            }
-            Some(url)
         }
     }
 }
+
+/// Removes the text fragments, e.g. `#:~:text=...`, and the tracking params some sites put in
+/// the fragment rather than in the query, e.g. `#xtor=RSS-1`.
+fn strip_fragment(url: &mut Url, host: Option<&str>, query_param_rules: &QueryParamRules) {
+    let fragment = match url.fragment() {
+        Some(fragment) => fragment.to_string(),
+        None => return,
+    };
+    let fragment = fragment.split(":~:").next().unwrap_or_default();
+
+    // fragments that are not made of params, e.g. anchors or the routes of web applications,
+    // are kept as is
+    let fragment = match is_params_fragment(fragment) {
+        true => fragment
+            .split('&')
+            .filter(|param| {
+                let name = param.split('=').next().unwrap_or_default();
+                !query_param_rules.is_ignored(host, name)
+                    && !FRAGMENT_TRACKERS.contains(&name.to_lowercase().as_str())
+            })
+            .collect::<Vec<&str>>()
+            .join("&"),
+        false => fragment.to_string(),
+    };
+
+    url.set_fragment(Some(fragment.as_str()).filter(|f| !f.is_empty()));
+}
+
+/// Returns the page an AMP page is the AMP version of, i.e. its canonical URL when it is on the
+/// same site, e.g. `https://example.com/article` for `https://example.com/article/amp`,
+/// `https://example.com/article.amp.html` or `https://amp.example.com/article?amp=1`.
+pub fn amp_canonical(url: &str, canonical: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    let segments = parsed
+        .path_segments()
+        .map(|s| s.filter(|s| !s.is_empty()).collect::<Vec<&str>>())
+        .unwrap_or_default();
+    let is_amp = segments.last() == Some(&"amp")
+        || parsed.path().ends_with(".amp.html")
+        || parsed.query_pairs().any(|(name, value)| {
+            name.eq_ignore_ascii_case("amp")
+                || (name.eq_ignore_ascii_case("outputtype") && value.eq_ignore_ascii_case("amp"))
+        })
+        || parsed
+            .host_str()
+            .map(|h| h.starts_with("amp."))
+            .unwrap_or_default();
+
+    let site = domain(url)?;
+    let site = site.strip_prefix("amp.").unwrap_or(&site);
+    (is_amp && domain(canonical).as_deref() == Some(site) && canonical != url)
+        .then(|| canonical.to_string())
+}
+
+/// Returns the last non-empty segment of the URL's path, decoded, e.g. `report.pdf`.
+pub fn file_name(url: &str) -> Option<String> {
+    Url::parse(url)
+        .ok()?
+        .path_segments()?
+        .rev()
+        .find(|segment| !segment.is_empty())
+        .map(|segment| {
+            urlencoding::decode(segment)
+                .map(|s| s.into_owned())
+                .unwrap_or_else(|_| segment.to_string())
+        })
+}
+
+/// Whether `url` is an absolute http(s) URL.
+pub fn is_http(url: &str) -> bool {
+    Url::parse(url)
+        .map(|url| matches!(url.scheme(), "http" | "https"))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{amp_canonical, clean, is_http, MAX_UNWRAPPING};
+    use crate::query_params::{QueryParamRules, Registry};
+
+    fn rules() -> QueryParamRules {
+        QueryParamRules::from_registry(&Registry::load(None).unwrap())
+    }
+
+    #[test]
+    fn clean_urls() {
+        let cases = [
+            // redirect wrappers
+            (
+                "https://www.google.com/url?q=https://example.com/a",
+                "https://example.com/a",
+            ),
+            (
+                "https://www.google.co.uk/url?url=https%3A%2F%2Fexample.com%2Fa&sa=D",
+                "https://example.com/a",
+            ),
+            (
+                "https://l.facebook.com/l.php?u=https%3A%2F%2Fexample.com%2Fa&h=AT0",
+                "https://example.com/a",
+            ),
+            (
+                "https://lm.facebook.com/l.php?u=https%3A%2F%2Fexample.com%2Fa",
+                "https://example.com/a",
+            ),
+            (
+                "https://l.messenger.com/l.php?u=https%3A%2F%2Fexample.com%2Fa",
+                "https://example.com/a",
+            ),
+            (
+                "https://l.instagram.com/?u=https%3A%2F%2Fexample.com%2Fa&e=AT0",
+                "https://example.com/a",
+            ),
+            (
+                "https://www.youtube.com/redirect?q=https%3A%2F%2Fexample.com%2Fa&v=x",
+                "https://example.com/a",
+            ),
+            (
+                "https://out.reddit.com/?url=https%3A%2F%2Fexample.com%2Fa&token=x",
+                "https://example.com/a",
+            ),
+            (
+                "https://away.vk.com/away.php?to=https%3A%2F%2Fexample.com%2Fa",
+                "https://example.com/a",
+            ),
+            (
+                "https://slack-redir.net/link?url=https%3A%2F%2Fexample.com%2Fa",
+                "https://example.com/a",
+            ),
+            (
+                "https://steamcommunity.com/linkfilter/?url=https://example.com/a",
+                "https://example.com/a",
+            ),
+            (
+                "https://t.umblr.com/redirect?z=https%3A%2F%2Fexample.com%2Fa&t=x",
+                "https://example.com/a",
+            ),
+            (
+                "https://www.google.com/url?q=https%3A%2F%2Fl.facebook.com%2Fl.php%3Fu%3Dhttps%253A%252F%252Fexample.com%252Fa",
+                "https://example.com/a",
+            ),
+            (
+                "https://www.google.com/url?q=javascript:alert(1)",
+                "https://www.google.com/url?q=javascript%3Aalert%281%29",
+            ),
+            (
+                "https://google.evil.com/url?q=https://example.com/a",
+                "https://google.evil.com/url?q=https%3A%2F%2Fexample.com%2Fa",
+            ),
+            // AMP caches
+            (
+                "https://www.google.com/amp/s/example.com/a",
+                "https://example.com/a",
+            ),
+            ("https://www.google.fr/amp/example.com/a", "http://example.com/a"),
+            (
+                "https://example-com.cdn.ampproject.org/c/s/example.com/a?amp_js_v=0.1&usqp=mq331AQ",
+                "https://example.com/a",
+            ),
+            (
+                "https://example-com.cdn.ampproject.org/v/s/example.com/a?id=1&amp_gsa=1#xtor=RSS-1",
+                "https://example.com/a?id=1",
+            ),
+            (
+                "https://www.google.com/url?q=https%3A%2F%2Fwww.google.com%2Famp%2Fs%2Fexample.com%2Fa",
+                "https://example.com/a",
+            ),
+            // paths
+            (
+                "https://www.amazon.co.uk/dp/B00X/ref=sr_1_1",
+                "https://www.amazon.co.uk/dp/B00X",
+            ),
+            (
+                "https://www.amazon.com/gp/product/B00X/ref=ppx_yo_dt",
+                "https://www.amazon.com/gp/product/B00X",
+            ),
+            ("https://example.com/ref=home", "https://example.com/ref=home"),
+            (
+                "https://amazon.evil.com/dp/B00X/ref=x",
+                "https://amazon.evil.com/dp/B00X/ref=x",
+            ),
+            (
+                "https://example.com/shop;jsessionid=A1B2/item",
+                "https://example.com/shop/item",
+            ),
+            ("https://example.com/page;JSESSIONID=A1B2", "https://example.com/page"),
+            ("https://example.com/a.amp.html", "https://example.com/a.amp.html"),
+            (
+                "https://github.com/ampproject/amp",
+                "https://github.com/ampproject/amp",
+            ),
+            // query params
+            (
+                "https://example.com/a?utm_source=x&id=3&fbclid=y",
+                "https://example.com/a?id=3",
+            ),
+            (
+                "https://example.com/api?outputType=json",
+                "https://example.com/api?outputType=json",
+            ),
+            (
+                "https://www.washingtonpost.com/a?outputType=amp",
+                "https://www.washingtonpost.com/a",
+            ),
+            ("https://example.com/a?amp=1", "https://example.com/a?amp=1"),
+            // fragments
+            ("https://example.com/a#xtor=RSS-1", "https://example.com/a"),
+            (
+                "https://example.com/a#xtor=RSS-1&page=2",
+                "https://example.com/a#page=2",
+            ),
+            ("https://example.com/a#:~:text=hello", "https://example.com/a"),
+            (
+                "https://example.com/a#section:~:text=hello",
+                "https://example.com/a#section",
+            ),
+            ("https://example.com/app#/inbox", "https://example.com/app#/inbox"),
+            // hosts and schemes
+            ("https://EXAMPLE.com./A", "https://example.com/A"),
+            ("HTTPS://Example.COM", "https://example.com/"),
+            ("example.com/a", "http://example.com/a"),
+        ];
+
+        let rules = rules();
+        for (url, expected) in cases {
+            assert_eq!(clean(url, &rules).as_deref(), Some(expected), "{}", url);
+        }
+    }
+
+    #[test]
+    fn unwrapping_is_limited() {
+        let mut url = "https://example.com/a".to_string();
+        for _ in 0..=MAX_UNWRAPPING {
+            url = format!("https://www.google.com/url?q={}", urlencoding::encode(&url));
+        }
+
+        let cleaned = clean(url, &rules()).unwrap();
+        assert!(cleaned.starts_with("https://www.google.com/url?q="));
+        assert_eq!(
+            clean(cleaned, &rules()).as_deref(),
+            Some("https://example.com/a")
+        );
+    }
+
+    #[test]
+    fn amp_canonical_urls() {
+        let cases = [
+            (
+                "https://example.com/a/amp",
+                "https://example.com/a",
+                Some("https://example.com/a"),
+            ),
+            (
+                "https://www.example.com/a/amp/",
+                "https://example.com/a",
+                Some("https://example.com/a"),
+            ),
+            (
+                "https://example.com/a.amp.html",
+                "https://example.com/a.html",
+                Some("https://example.com/a.html"),
+            ),
+            (
+                "https://amp.example.com/a",
+                "https://example.com/a",
+                Some("https://example.com/a"),
+            ),
+            (
+                "https://example.com/a?amp=1",
+                "https://example.com/a",
+                Some("https://example.com/a"),
+            ),
+            (
+                "https://www.washingtonpost.com/a?outputType=amp",
+                "https://www.washingtonpost.com/a",
+                Some("https://www.washingtonpost.com/a"),
+            ),
+            ("https://example.com/a", "https://example.com/b", None),
+            (
+                "https://github.com/ampproject/amp",
+                "https://github.com/ampproject/amp",
+                None,
+            ),
+            ("https://example.com/a/amp", "https://evil.com/a", None),
+        ];
+
+        for (url, canonical, expected) in cases {
+            assert_eq!(
+                amp_canonical(url, canonical).as_deref(),
+                expected,
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn http_urls() {
+        assert!(is_http("https://example.com/favicon.ico"));
+        assert!(is_http("http://example.com/image.png"));
+        assert!(!is_http("/favicon.ico"));
+        assert!(!is_http("javascript:alert(1)"));
+        assert!(!is_http("data:image/png;base64,AAAA"));
+        assert!(!is_http("file:///etc/passwd"));
+    }
+}
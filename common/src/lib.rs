@@ -1,5 +1,10 @@
 use regex::Regex;
 
+pub mod url;
+
+/// Separates the levels of hierarchical tags, e.g. `dev/rust/async`.
+pub const TAG_SEPARATOR: char = '/';
+
 #[derive(Clone, PartialEq)]
 pub struct PasswordFlags {
     pub same: bool,
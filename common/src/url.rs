@@ -0,0 +1,56 @@
+use ::url::Url;
+
+/// Returns the key telling whether two URLs point to the same page, whatever their scheme,
+/// `www.`, trailing slashes, query params order and fragment params, e.g.
+/// `example.com/path?a=1&b=2`. The fragments that are not made of params, e.g. the routes of web
+/// applications, are part of the key. An URL that cannot be parsed is its own key.
+pub fn key(url: &str) -> String {
+    let parsed = match Url::parse(url) {
+        Ok(parsed) => parsed,
+        Err(_) => return url.to_string(),
+    };
+
+    let host = parsed.host_str().unwrap_or_default().to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let port = parsed.port().map(|p| format!(":{}", p)).unwrap_or_default();
+    let path = parsed.path().trim_end_matches('/');
+    let mut params = parsed
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<&str>>();
+    params.sort_unstable();
+    let query = match params.is_empty() {
+        true => String::new(),
+        false => format!("?{}", params.join("&")),
+    };
+    let fragment = parsed
+        .fragment()
+        .map(|f| f.split(":~:").next().unwrap_or_default())
+        .filter(|f| !f.is_empty() && !is_params_fragment(f))
+        .map(|f| format!("#{}", f))
+        .unwrap_or_default();
+
+    format!("{}{}{}{}{}", host, port, path, query, fragment)
+}
+
+/// Whether the fragment is made of params, e.g. `page=2&xtor=RSS-1`, rather than being an anchor
+/// or the route of a web application.
+pub fn is_params_fragment(fragment: &str) -> bool {
+    !fragment.is_empty()
+        && fragment
+            .split('&')
+            .all(|param| param.contains('=') && !param.contains('/'))
+}
+
+/// Returns the normalized host of the URL: lowercase, without the leading `www.`.
+pub fn domain(url: &str) -> Option<String> {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+        .map(|host| match host.strip_prefix("www.") {
+            Some(host) => host.to_string(),
+            None => host,
+        })
+}
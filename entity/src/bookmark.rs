@@ -14,6 +14,22 @@ pub struct Model {
     pub update_date: Option<DateTimeWithTimeZone>,
     pub user_id: i32,
     pub private: bool,
+    pub domain: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub site_name: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub author: Option<String>,
+    pub published_date: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub language: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub image_url: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub favicon_url: Option<String>,
+    pub metadata_status: Option<String>,
+    pub metadata_fetch_date: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_type = "Text")]
+    pub url_key: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
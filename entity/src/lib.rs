@@ -7,4 +7,8 @@ pub mod bookmark;
 pub mod bookmark_tag;
 pub mod password_recovery;
 pub mod pin;
+pub mod query_param_rule;
+pub mod rule;
+pub mod saved_search;
 pub mod tag;
+pub mod tag_alias;
@@ -5,4 +5,8 @@ pub use super::bookmark::Entity as Bookmark;
 pub use super::bookmark_tag::Entity as BookmarkTag;
 pub use super::password_recovery::Entity as PasswordRecovery;
 pub use super::pin::Entity as Pin;
+pub use super::query_param_rule::Entity as QueryParamRule;
+pub use super::rule::Entity as Rule;
+pub use super::saved_search::Entity as SavedSearch;
 pub use super::tag::Entity as Tag;
+pub use super::tag_alias::Entity as TagAlias;
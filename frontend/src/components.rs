@@ -2,5 +2,7 @@ pub mod highlight;
 pub mod nav;
 pub mod page_size;
 pub mod protected;
+pub mod saved_searches_provider;
+pub mod search_input;
 pub mod tag_input;
 pub mod tags_provider;
@@ -1,3 +1,4 @@
+use crate::data::Snippet as SnippetData;
 use std::cmp::Reverse;
 use std::ops::Deref;
 use std::rc::Rc;
@@ -42,6 +43,25 @@ pub fn highlight(props: &Props) -> Html {
     }
 }
 
+#[derive(Properties, Clone, PartialEq, Debug)]
+pub struct SnippetProps {
+    pub snippet: SnippetData,
+}
+
+#[function_component(Snippet)]
+pub fn snippet(props: &SnippetProps) -> Html {
+    html! {
+        props.snippet.0
+        .iter()
+        .map(|part| if part.highlight {
+            html!{ <span class="highlight">{part.text.clone()}</span> }
+        } else {
+            html! { part.text.clone() }
+        })
+        .collect::<Html>()
+    }
+}
+
 #[derive(Debug)]
 enum Zone<'a> {
     Highlight(&'a str),
@@ -0,0 +1,75 @@
+use super::super::data::SavedSearch;
+use gloo_net::http::Request;
+use rest_api::saved_searches::{GetSavedSearchesResult, URL_SAVED_SEARCHES};
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct Props {
+    pub children: Children,
+    pub logged_in: bool,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct SavedSearches {
+    pub enabled: bool,
+    pub loaded: bool,
+    pub searches: Rc<Vec<SavedSearch>>,
+    pub reload: Callback<()>,
+}
+
+impl SavedSearches {
+    pub fn default_search(&self) -> Option<&SavedSearch> {
+        self.searches.iter().find(|s| s.is_default)
+    }
+}
+
+#[function_component(SavedSearchesProvider)]
+pub fn saved_searches_provider(props: &Props) -> Html {
+    let searches = use_state(|| None::<Rc<Vec<SavedSearch>>>);
+    let generation = use_state(|| 0u32);
+
+    {
+        let searches = searches.clone();
+        use_effect_with_deps(
+            move |(logged_in, _)| {
+                if *logged_in {
+                    spawn_local(async move {
+                        searches.set(Some(Rc::new(fetch_saved_searches().await)));
+                    });
+                } else {
+                    searches.set(Some(Rc::new(Vec::new())));
+                }
+                || {}
+            },
+            (props.logged_in, *generation),
+        );
+    }
+
+    let context = SavedSearches {
+        enabled: props.logged_in,
+        loaded: searches.is_some(),
+        searches: (*searches).clone().unwrap_or_default(),
+        reload: Callback::from(move |_| generation.set(*generation + 1)),
+    };
+
+    html! {
+        <ContextProvider<SavedSearches> {context}>
+            { props.children.clone() }
+        </ContextProvider<SavedSearches>>
+    }
+}
+
+async fn fetch_saved_searches() -> Vec<SavedSearch> {
+    match GetSavedSearchesResult::from(Request::get(URL_SAVED_SEARCHES).send().await).await {
+        Some(GetSavedSearchesResult::Success(searches)) => searches
+            .into_iter()
+            .map(SavedSearch::from)
+            .collect::<Vec<SavedSearch>>(),
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use gloo_net::http::Request;
+use rest_api::suggestions::{GetSuggestionsResult, URL_SUGGESTIONS};
+use web_sys::HtmlInputElement;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct Props {
+    pub placeholder: Option<AttrValue>,
+    pub value: AttrValue,
+    pub onsearch: Callback<String>,
+    pub on_select_tag: Callback<AttrValue>,
+    pub on_select_domain: Callback<AttrValue>,
+}
+
+#[derive(Clone, PartialEq)]
+enum Suggestion {
+    Tag(AttrValue, i32),
+    Title(AttrValue),
+    Domain(AttrValue, i32),
+}
+
+impl Suggestion {
+    fn icon(&self) -> &str {
+        match self {
+            Suggestion::Tag(_, _) => "sell",
+            Suggestion::Title(_) => "title",
+            Suggestion::Domain(_, _) => "language",
+        }
+    }
+
+    fn text(&self) -> &AttrValue {
+        match self {
+            Suggestion::Tag(text, _) | Suggestion::Title(text) | Suggestion::Domain(text, _) => {
+                text
+            }
+        }
+    }
+}
+
+#[derive(Default, Clone, PartialEq)]
+struct State {
+    string: AttrValue,
+    suggestions: Vec<Suggestion>,
+    selected_suggestion: Option<usize>,
+    focus: bool,
+}
+
+#[function_component(SearchInput)]
+pub fn search_input(props: &Props) -> Html {
+    let state = use_state(|| State {
+        string: props.value.clone(),
+        ..State::default()
+    });
+    // incremented on each keystroke so that outdated suggestions are discarded
+    let generation = use_mut_ref(|| 0_u32);
+
+    {
+        let state = state.clone();
+        use_effect_with_deps(
+            move |value| {
+                let mut new_state = (*state).clone();
+                new_state.string = value.clone();
+                new_state.suggestions = Vec::default();
+                new_state.selected_suggestion = None;
+                state.set(new_state);
+            },
+            props.value.clone(),
+        );
+    }
+
+    let select = {
+        let state = state.clone();
+        let props = props.clone();
+        Callback::from(move |suggestion: Suggestion| {
+            let mut new_state = (*state).clone();
+            let remaining = without_last_word(&state.string);
+            match suggestion {
+                Suggestion::Tag(name, _) => {
+                    new_state.string = AttrValue::from(remaining);
+                    props.on_select_tag.emit(name);
+                }
+                Suggestion::Domain(name, _) => {
+                    new_state.string = AttrValue::from(remaining);
+                    props.on_select_domain.emit(name);
+                }
+                Suggestion::Title(title) => {
+                    let string = format!("{}{}", remaining, title);
+                    props.onsearch.emit(string.clone());
+                    new_state.string = AttrValue::from(string);
+                }
+            }
+            new_state.suggestions = Vec::default();
+            new_state.selected_suggestion = None;
+            state.set(new_state);
+        })
+    };
+
+    let onkeydown = {
+        let state = state.clone();
+        let props = props.clone();
+        let select = select.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" {
+                e.prevent_default();
+                match state
+                    .selected_suggestion
+                    .and_then(|i| state.suggestions.get(i))
+                {
+                    Some(suggestion) => select.emit(suggestion.clone()),
+                    None => {
+                        let mut new_state = (*state).clone();
+                        new_state.suggestions = Vec::default();
+                        state.set(new_state);
+                        props
+                            .onsearch
+                            .emit(e.target_unchecked_into::<HtmlInputElement>().value());
+                    }
+                }
+            } else if e.key() == "Up" || e.key() == "ArrowUp" {
+                e.prevent_default();
+                if !state.suggestions.is_empty() {
+                    let mut new_state = (*state).clone();
+                    new_state.selected_suggestion = Some(match state.selected_suggestion {
+                        Some(i) if i > 0 => i - 1,
+                        Some(_) | None => state.suggestions.len() - 1,
+                    });
+                    state.set(new_state);
+                }
+            } else if e.key() == "Down" || e.key() == "ArrowDown" {
+                e.prevent_default();
+                if !state.suggestions.is_empty() {
+                    let mut new_state = (*state).clone();
+                    new_state.selected_suggestion = Some(match state.selected_suggestion {
+                        Some(i) if i < state.suggestions.len() - 1 => i + 1,
+                        Some(_) | None => 0,
+                    });
+                    state.set(new_state);
+                }
+            } else if e.key() == "Esc" || e.key() == "Escape" {
+                e.prevent_default();
+                let mut new_state = (*state).clone();
+                new_state.selected_suggestion = None;
+                if state.selected_suggestion.is_none() {
+                    new_state.suggestions = Vec::default();
+                }
+                state.set(new_state);
+            }
+        })
+    };
+
+    let oninput = {
+        let state = state.clone();
+        let generation = generation.clone();
+        Callback::from(move |e: InputEvent| {
+            let value = e.target_unchecked_into::<HtmlInputElement>().value();
+            let prefix = value
+                .rsplit(' ')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+
+            *generation.borrow_mut() += 1;
+            let current_generation = *generation.borrow();
+
+            let mut new_state = (*state).clone();
+            new_state.string = AttrValue::from(value);
+            new_state.suggestions = Vec::default();
+            new_state.selected_suggestion = None;
+            state.set(new_state.clone());
+
+            if prefix.is_empty() {
+                return;
+            }
+
+            let state = state.clone();
+            let generation = generation.clone();
+            spawn_local(async move {
+                let suggestions = fetch_suggestions(&prefix).await;
+                if *generation.borrow() == current_generation {
+                    new_state.suggestions = suggestions;
+                    state.set(new_state);
+                }
+            });
+        })
+    };
+
+    let onfocus = {
+        let state = state.clone();
+        Callback::from(move |_| {
+            let mut new_state = (*state).clone();
+            new_state.focus = true;
+            state.set(new_state);
+        })
+    };
+
+    let onblur = {
+        let state = state.clone();
+        Callback::from(move |_| {
+            let mut new_state = (*state).clone();
+            new_state.focus = false;
+            new_state.selected_suggestion = None;
+            state.set(new_state);
+        })
+    };
+
+    html! {
+        <div class="input-search">
+            <input
+                placeholder={props.placeholder.clone()}
+                type="text"
+                value={state.string.clone()}
+                {onfocus} {onblur}
+                {oninput}
+                {onkeydown}
+            />
+            { if state.focus && !state.suggestions.is_empty() { html! {
+            <ul class="input-tag__autocomplete">
+                {
+                    state.suggestions.iter().enumerate().map(|(i, suggestion)| html! {
+                        <li
+                            class={
+                                if state.selected_suggestion.eq(&Some(i)) {
+                                    "input-tag__autocomplete--selected"
+                                } else {
+                                    ""
+                                }
+                            }
+                            onmousedown={
+                                let select = select.clone();
+                                let suggestion = suggestion.clone();
+                                move |e: MouseEvent| {
+                                    // prevents the blur that would hide the suggestions
+                                    e.prevent_default();
+                                    select.emit(suggestion.clone())
+                                }
+                            }
+                        >
+                            <span class="material-icons-outlined md-14">{suggestion.icon()}</span>
+                            {"\u{00a0}"}
+                            {suggestion.text().clone()}
+                            {
+                                match suggestion {
+                                    Suggestion::Tag(_, count) | Suggestion::Domain(_, count) => html! {
+                                        <span class="input-search__count">{count}</span>
+                                    },
+                                    Suggestion::Title(_) => html! {},
+                                }
+                            }
+                        </li>
+                    }).collect::<Html>()
+                }
+            </ul>
+            } } else { html! { <></> } } }
+        </div>
+    }
+}
+
+/// Returns the string without its last word, keeping the trailing space if any.
+fn without_last_word(string: &str) -> String {
+    match string.rfind(' ') {
+        None => String::new(),
+        Some(i) => string[..=i].to_string(),
+    }
+}
+
+async fn fetch_suggestions(prefix: &str) -> Vec<Suggestion> {
+    match GetSuggestionsResult::from(
+        Request::get(URL_SUGGESTIONS)
+            .query([("prefix", prefix)])
+            .send()
+            .await,
+    )
+    .await
+    {
+        Some(GetSuggestionsResult::Success(response)) => response
+            .tags
+            .into_iter()
+            .map(|t| Suggestion::Tag(AttrValue::from(t.name), t.count))
+            .chain(
+                response
+                    .titles
+                    .into_iter()
+                    .map(|t| Suggestion::Title(AttrValue::from(t))),
+            )
+            .chain(
+                response
+                    .domains
+                    .into_iter()
+                    .map(|d| Suggestion::Domain(AttrValue::from(d.name), d.count)),
+            )
+            .collect(),
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::data::{TagAlias, TagAliases};
 use std::rc::Rc;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
@@ -16,12 +17,15 @@ struct Match {
     matched: AttrValue,
     suffix: AttrValue,
     full: AttrValue,
+    /// Set when the match is an alias: `full` is then the tag it stands for.
+    alias_of: Option<AttrValue>,
 }
 
 #[derive(Default, Clone, PartialEq)]
 struct State {
     tags: Vec<AttrValue>,
     available_tags: Rc<Vec<AttrValue>>,
+    aliases: Rc<TagAliases>,
     matches: Vec<Match>,
     selected_match: Option<usize>,
     selected_tag: Option<usize>,
@@ -32,6 +36,7 @@ struct State {
 #[function_component(TagInput)]
 pub fn tag_input(props: &Props) -> Html {
     // gloo_console::info!("render tag_input ", props.tags.len());
+    let aliases = use_context::<Rc<TagAliases>>().unwrap_or_default();
     let state = use_state(|| {
         // gloo_console::info!("use_state tag_input");
         State {
@@ -40,6 +45,7 @@ pub fn tag_input(props: &Props) -> Html {
                 None => Rc::new(vec![]),
                 Some(tags) => tags.clone(),
             },
+            aliases,
             matches: Vec::default(),
             selected_match: Option::default(),
             selected_tag: Option::default(),
@@ -82,7 +88,7 @@ pub fn tag_input(props: &Props) -> Html {
                         .tags
                         .push(state.matches.get(selected_match).unwrap().full.clone())
                 } else if !value.is_empty() {
-                    new_state.tags.push(AttrValue::from(value.to_lowercase()));
+                    new_state.tags.push(AttrValue::from(value));
                 }
                 new_state.string = AttrValue::default();
                 new_state.matches = Vec::default();
@@ -108,6 +114,7 @@ pub fn tag_input(props: &Props) -> Html {
                     if let Some(tag) = new_state.tags.pop() {
                         let matches = compute_matches(
                             &state.available_tags,
+                            &state.aliases,
                             &new_state.tags,
                             &tag,
                             state.selected_match,
@@ -196,6 +203,7 @@ pub fn tag_input(props: &Props) -> Html {
             } else {
                 let matches = compute_matches(
                     &state.available_tags,
+                    &state.aliases,
                     &state.tags,
                     &value,
                     state.selected_match,
@@ -229,9 +237,7 @@ pub fn tag_input(props: &Props) -> Html {
             new_state.string = AttrValue::default();
             new_state.selected_match = None;
             if !input.value().is_empty() {
-                new_state
-                    .tags
-                    .push(AttrValue::from(input.value().to_lowercase()));
+                new_state.tags.push(AttrValue::from(input.value()));
             }
             state.set(new_state);
         })
@@ -248,6 +254,7 @@ pub fn tag_input(props: &Props) -> Html {
             new_state.selected_match = None;
             new_state.matches = compute_matches(
                 &state.available_tags,
+                &state.aliases,
                 &new_state.tags,
                 state.string.as_str(),
                 state.selected_match,
@@ -292,6 +299,7 @@ pub fn tag_input(props: &Props) -> Html {
 
     fn compute_matches(
         tags: &[AttrValue],
+        aliases: &[TagAlias],
         used_tags: &[AttrValue],
         pattern: &str,
         current_selection: Option<usize>,
@@ -299,20 +307,43 @@ pub fn tag_input(props: &Props) -> Html {
         if pattern.is_empty() {
             return (None, vec![]);
         }
-        let matches = tags
+        // tags are matched regardless of their case
+        let pattern = pattern.to_lowercase();
+        let is_used = |tag: &AttrValue| {
+            used_tags
+                .iter()
+                .any(|t| t.to_lowercase() == tag.to_lowercase())
+        };
+
+        let mut matches = tags
             .iter()
-            .map(|e| (e.find(&pattern.to_lowercase()), e))
-            .filter(|e| e.0.is_some())
-            .map(|e| (e.0.unwrap(), e.1))
-            .filter(|(_, e)| !used_tags.contains(e))
-            .map(|e| Match {
-                prefix: AttrValue::from((e.1[..e.0]).to_owned()),
-                matched: AttrValue::from((e.1[e.0..e.0 + pattern.bytes().len()]).to_owned()),
-                suffix: AttrValue::from((e.1[e.0 + pattern.bytes().len()..]).to_owned()),
-                full: e.1.clone(),
+            .filter_map(|e| e.to_lowercase().find(&pattern).map(|i| (i, e)))
+            // lowercasing may change the length of a few characters
+            .filter(|(i, e)| e.is_char_boundary(*i) && e.is_char_boundary(i + pattern.len()))
+            .filter(|(_, e)| !is_used(e))
+            .map(|(i, e)| Match {
+                prefix: AttrValue::from((e[..i]).to_owned()),
+                matched: AttrValue::from((e[i..i + pattern.len()]).to_owned()),
+                suffix: AttrValue::from((e[i + pattern.len()..]).to_owned()),
+                full: e.clone(),
+                alias_of: None,
             })
             .collect::<Vec<Match>>();
 
+        for alias in aliases {
+            if let Some(i) = alias.alias.find(&pattern) {
+                if !is_used(&alias.tag) && !matches.iter().any(|m| m.full == alias.tag) {
+                    matches.push(Match {
+                        prefix: AttrValue::from((alias.alias[..i]).to_owned()),
+                        matched: AttrValue::from((alias.alias[i..i + pattern.len()]).to_owned()),
+                        suffix: AttrValue::from((alias.alias[i + pattern.len()..]).to_owned()),
+                        full: alias.tag.clone(),
+                        alias_of: Some(alias.tag.clone()),
+                    });
+                }
+            }
+        }
+
         if matches.is_empty() {
             (None, matches)
         } else if let Some(current_selection) = current_selection {
@@ -388,7 +419,12 @@ pub fn tag_input(props: &Props) -> Html {
                                     let tag = tag.clone();
                                     move |_| add.emit(tag.full.clone())
                                 }
-                            >{tag.prefix.clone()}<span class="input-tag__autocomplete-match">{tag.matched.clone()}</span>{tag.suffix.clone()}</li>
+                            >
+                                {tag.prefix.clone()}<span class="input-tag__autocomplete-match">{tag.matched.clone()}</span>{tag.suffix.clone()}
+                                { tag.alias_of.as_ref().map(|t| html! {
+                                    <span class="input-tag__autocomplete-alias">{format!(" \u{2192} {}", t)}</span>
+                                }) }
+                            </li>
                         }).collect::<Html>()
                     }
                 </ul>
@@ -1,5 +1,6 @@
-use super::super::data::{Tag, Tags};
+use super::super::data::{Tag, TagAlias, TagAliases, Tags};
 use gloo_net::http::Request;
+use rest_api::tag_aliases::{GetTagAliasesResult, URL_TAG_ALIASES};
 use rest_api::tags::{GetTagsResult, URL_TAGS};
 use std::rc::Rc;
 use yew::platform::spawn_local;
@@ -31,6 +32,7 @@ pub struct Props {
 #[derive(Clone, PartialEq, Default)]
 struct State {
     tags: Option<Rc<Tags>>,
+    aliases: Rc<TagAliases>,
 }
 
 // todo move somewhere else
@@ -48,6 +50,7 @@ pub fn tags_provider(props: &Props) -> Html {
                         tags: Some(Rc::new(
                             fetch_tags(&props.order.clone().unwrap_or_default()).await,
                         )),
+                        aliases: Rc::new(fetch_aliases().await),
                     });
                 });
             }
@@ -59,7 +62,9 @@ pub fn tags_provider(props: &Props) -> Html {
     match &state.tags {
         Some(tags) => html! {
             <ContextProvider<Rc<Tags>> context={(*tags).clone()}>
-                { props.children.clone() }
+                <ContextProvider<Rc<TagAliases>> context={state.aliases.clone()}>
+                    { props.children.clone() }
+                </ContextProvider<Rc<TagAliases>>>
             </ContextProvider<Rc<Tags>>>
         },
         _ => html! {},
@@ -83,3 +88,14 @@ async fn fetch_tags(order: &Order) -> Tags {
         }
     }
 }
+
+async fn fetch_aliases() -> TagAliases {
+    // aliases are only available to logged in users
+    match GetTagAliasesResult::from(Request::get(URL_TAG_ALIASES).send().await).await {
+        Some(GetTagAliasesResult::Success(aliases)) => aliases
+            .into_iter()
+            .map(TagAlias::from)
+            .collect::<Vec<TagAlias>>(),
+        _ => vec![],
+    }
+}
@@ -2,7 +2,9 @@ use chrono::{DateTime, Local};
 use rest_api::bookmarks::create::CreateBookmarkRequest;
 use rest_api::bookmarks::get_one::GetBookmarkResponse;
 use rest_api::bookmarks::update::UpdateBookmarkRequest;
-use rest_api::bookmarks::Access;
+use rest_api::bookmarks::{Access, BookmarkMetadata as BookmarkMetadataResponse, MetadataStatus};
+use std::collections::HashMap;
+use std::rc::Rc;
 use yew::prelude::*;
 
 #[derive(Clone, PartialEq, Debug)]
@@ -12,11 +14,18 @@ pub struct Bookmark {
     pub title: Option<AttrValue>,
     pub description: Option<AttrValue>,
     pub tags: Vec<AttrValue>,
+    pub tags_metadata: Rc<HashMap<AttrValue, TagMetadata>>,
     pub creation_date: DateTime<Local>,
     pub update_date: Option<DateTime<Local>>,
     pub access: Access,
     pub private: bool,
     pub pinned: bool,
+    pub domain: Option<AttrValue>,
+    pub metadata: BookmarkMetadata,
+    pub metadata_status: Option<MetadataStatus>,
+    pub metadata_fetch_date: Option<DateTime<Local>>,
+    pub title_snippet: Option<Snippet>,
+    pub description_snippet: Option<Snippet>,
 }
 
 impl Default for Bookmark {
@@ -27,11 +36,18 @@ impl Default for Bookmark {
             title: None,
             description: None,
             tags: Vec::default(),
+            tags_metadata: Rc::default(),
             creation_date: DateTime::default(),
             update_date: None,
             access: Access::Read,
             private: true,
             pinned: false,
+            domain: None,
+            metadata: BookmarkMetadata::default(),
+            metadata_status: None,
+            metadata_fetch_date: None,
+            title_snippet: None,
+            description_snippet: None,
         }
     }
 }
@@ -48,15 +64,97 @@ impl From<GetBookmarkResponse> for Bookmark {
                 .into_iter()
                 .map(AttrValue::from)
                 .collect::<Vec<AttrValue>>(),
+            tags_metadata: Rc::new(
+                value
+                    .tags_metadata
+                    .into_iter()
+                    .map(|(name, metadata)| (AttrValue::from(name), TagMetadata::from(metadata)))
+                    .collect(),
+            ),
             creation_date: DateTime::from(value.creation_date),
             update_date: value.update_date.map(DateTime::from),
             access: value.access,
             private: value.private,
             pinned: value.pinned,
+            domain: value.domain.map(AttrValue::from),
+            metadata: BookmarkMetadata::from(value.metadata),
+            metadata_status: value.metadata_status,
+            metadata_fetch_date: value.metadata_fetch_date.map(DateTime::from),
+            title_snippet: value.title_snippet.map(Snippet::from),
+            description_snippet: value.description_snippet.map(Snippet::from),
         }
     }
 }
 
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct BookmarkMetadata {
+    pub site_name: Option<AttrValue>,
+    pub author: Option<AttrValue>,
+    pub published_date: Option<DateTime<Local>>,
+    pub language: Option<AttrValue>,
+    pub image_url: Option<AttrValue>,
+    pub favicon_url: Option<AttrValue>,
+}
+
+impl BookmarkMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.site_name.is_none()
+            && self.author.is_none()
+            && self.published_date.is_none()
+            && self.language.is_none()
+            && self.image_url.is_none()
+    }
+}
+
+impl From<BookmarkMetadataResponse> for BookmarkMetadata {
+    fn from(value: BookmarkMetadataResponse) -> Self {
+        Self {
+            site_name: value.site_name.map(AttrValue::from),
+            author: value.author.map(AttrValue::from),
+            published_date: value.published_date.map(DateTime::from),
+            language: value.language.map(AttrValue::from),
+            image_url: value.image_url.map(AttrValue::from),
+            favicon_url: value.favicon_url.map(AttrValue::from),
+        }
+    }
+}
+
+impl From<&BookmarkMetadata> for BookmarkMetadataResponse {
+    fn from(value: &BookmarkMetadata) -> Self {
+        Self {
+            site_name: value.site_name.as_ref().map(|v| v.to_string()),
+            author: value.author.as_ref().map(|v| v.to_string()),
+            published_date: value.published_date.map(DateTime::from),
+            language: value.language.as_ref().map(|v| v.to_string()),
+            image_url: value.image_url.as_ref().map(|v| v.to_string()),
+            favicon_url: value.favicon_url.as_ref().map(|v| v.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SnippetPart {
+    pub text: AttrValue,
+    pub highlight: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Snippet(pub Rc<Vec<SnippetPart>>);
+
+impl From<Vec<rest_api::bookmarks::get_one::SnippetPart>> for Snippet {
+    fn from(value: Vec<rest_api::bookmarks::get_one::SnippetPart>) -> Self {
+        Self(Rc::new(
+            value
+                .into_iter()
+                .map(|part| SnippetPart {
+                    text: AttrValue::from(part.text),
+                    highlight: part.highlight,
+                })
+                .collect::<Vec<SnippetPart>>(),
+        ))
+    }
+}
+
 impl From<&Bookmark> for UpdateBookmarkRequest {
     fn from(bookmark: &Bookmark) -> Self {
         UpdateBookmarkRequest {
@@ -88,6 +186,7 @@ impl From<&Bookmark> for CreateBookmarkRequest {
                     .collect::<Vec<String>>(),
             ),
             private: Some(bookmark.private),
+            metadata: BookmarkMetadataResponse::from(&bookmark.metadata),
         }
     }
 }
@@ -96,6 +195,9 @@ impl From<&Bookmark> for CreateBookmarkRequest {
 pub struct Tag {
     pub name: AttrValue,
     pub count: i32,
+    pub total_count: i32,
+    pub children: Rc<Vec<Tag>>,
+    pub metadata: TagMetadata,
 }
 
 impl From<rest_api::tags::Tag> for Tag {
@@ -103,8 +205,99 @@ impl From<rest_api::tags::Tag> for Tag {
         Self {
             name: AttrValue::from(value.name),
             count: value.count,
+            total_count: value.total_count.unwrap_or(value.count),
+            children: Rc::new(value.children.into_iter().map(Tag::from).collect()),
+            metadata: TagMetadata::from(value.metadata),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TagMetadata {
+    pub color: Option<AttrValue>,
+    pub description: Option<AttrValue>,
+    pub icon: Option<AttrValue>,
+}
+
+impl From<rest_api::tags::TagMetadata> for TagMetadata {
+    fn from(value: rest_api::tags::TagMetadata) -> Self {
+        Self {
+            color: value.color.map(AttrValue::from),
+            description: value.description.map(AttrValue::from),
+            icon: value.icon.map(AttrValue::from),
+        }
+    }
+}
+
+impl From<&TagMetadata> for rest_api::tags::TagMetadata {
+    fn from(value: &TagMetadata) -> Self {
+        Self {
+            color: value.color.as_ref().map(|v| v.to_string()),
+            description: value.description.as_ref().map(|v| v.to_string()),
+            icon: value.icon.as_ref().map(|v| v.to_string()),
         }
     }
 }
 
 pub type Tags = Vec<Tag>;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TagAlias {
+    pub alias: AttrValue,
+    pub tag: AttrValue,
+}
+
+impl From<rest_api::tag_aliases::TagAlias> for TagAlias {
+    fn from(value: rest_api::tag_aliases::TagAlias) -> Self {
+        Self {
+            alias: AttrValue::from(value.alias),
+            tag: AttrValue::from(value.tag),
+        }
+    }
+}
+
+pub type TagAliases = Vec<TagAlias>;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rule {
+    pub id: i32,
+    pub url_pattern: Option<AttrValue>,
+    pub url_regex: bool,
+    pub title_pattern: Option<AttrValue>,
+    pub tags: Vec<AttrValue>,
+    pub private: bool,
+    pub pin: bool,
+}
+
+impl From<rest_api::rules::Rule> for Rule {
+    fn from(value: rest_api::rules::Rule) -> Self {
+        Self {
+            id: value.id,
+            url_pattern: value.url_pattern.map(AttrValue::from),
+            url_regex: value.url_regex,
+            title_pattern: value.title_pattern.map(AttrValue::from),
+            tags: value.tags.into_iter().map(AttrValue::from).collect(),
+            private: value.private,
+            pin: value.pin,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedSearch {
+    pub id: i32,
+    pub name: AttrValue,
+    pub query: AttrValue,
+    pub is_default: bool,
+}
+
+impl From<rest_api::saved_searches::SavedSearch> for SavedSearch {
+    fn from(value: rest_api::saved_searches::SavedSearch) -> Self {
+        Self {
+            id: value.id,
+            name: AttrValue::from(value.name),
+            query: AttrValue::from(value.query),
+            is_default: value.is_default,
+        }
+    }
+}
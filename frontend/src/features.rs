@@ -1,6 +1,8 @@
 pub mod authentication;
 pub mod bookmarks;
+pub mod domains;
 pub mod profile;
+pub mod saved_searches;
 pub mod signup;
 pub mod tag_cloud;
 pub mod tools;
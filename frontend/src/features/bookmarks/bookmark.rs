@@ -1,11 +1,12 @@
 use super::super::super::data::Bookmark as BookmarkData;
 use super::qr_code::QrCode;
-use crate::components::highlight::Highlight;
+use crate::components::highlight::{Highlight, Snippet};
 use crate::Route;
 use chrono::{DateTime, Local};
 use gloo_net::http::Request;
+use rest_api::bookmarks::refresh::RefreshBookmarkResult;
 use rest_api::bookmarks::update::{UpdateBookmarkRequest, UpdateBookmarkResult};
-use rest_api::bookmarks::{Access, URL_BOOKMARK};
+use rest_api::bookmarks::{Access, MetadataStatus, URL_BOOKMARK, URL_BOOKMARK_REFRESH};
 use std::rc::Rc;
 use yew::platform::spawn_local;
 use yew::prelude::*;
@@ -17,6 +18,7 @@ pub struct Props {
     pub bookmark: Rc<BookmarkData>,
     pub highlight: Option<Rc<Vec<AttrValue>>>,
     pub on_select_tag_filter: Callback<AttrValue>,
+    pub on_select_domain_filter: Callback<AttrValue>,
 }
 
 #[function_component(Bookmark)]
@@ -24,6 +26,8 @@ pub fn bookmark(props: &Props) -> Html {
     let navigator = use_navigator().unwrap();
 
     let state = use_state(|| props.bookmark.pinned);
+    let refresh_pending =
+        use_state(|| props.bookmark.metadata_status == Some(MetadataStatus::Pending));
 
     let onclick_delete = {
         let navigator = navigator.clone();
@@ -77,6 +81,28 @@ pub fn bookmark(props: &Props) -> Html {
         })
     };
 
+    let onclick_refresh = {
+        let props = props.clone();
+        let refresh_pending = refresh_pending.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+
+            let bookmark = props.bookmark.clone();
+            let refresh_pending = refresh_pending.clone();
+            spawn_local(async move {
+                if let Some(RefreshBookmarkResult::Success) = RefreshBookmarkResult::from(
+                    Request::post(&URL_BOOKMARK_REFRESH.replace(":id", &bookmark.id.to_string()))
+                        .send()
+                        .await,
+                )
+                .await
+                {
+                    refresh_pending.set(true);
+                }
+            });
+        })
+    };
+
     let onclick_permalink = {
         let props = props.clone();
         Callback::from(move |e: MouseEvent| {
@@ -91,11 +117,19 @@ pub fn bookmark(props: &Props) -> Html {
         <li class="bookmark">
             <div class="bookmark__title">
                 <a href={props.bookmark.url.clone()}>
-                    <span class="material-icons-outlined bookmark__title-icon">{"open_in_new"}</span>
-                    <Highlight
-                        text={props.bookmark.title.clone().unwrap_or_else(|| props.bookmark.url.clone())}
-                        terms={props.highlight.clone()}
-                    />
+                    { match &props.bookmark.metadata.favicon_url {
+                        Some(favicon_url) => html! { <img class="bookmark__favicon" src={favicon_url.clone()} alt="" /> },
+                        None => html! { <span class="material-icons-outlined bookmark__title-icon">{"open_in_new"}</span> },
+                    } }
+                    { match &props.bookmark.title_snippet {
+                        Some(snippet) => html! { <Snippet snippet={snippet.clone()} /> },
+                        None => html! {
+                            <Highlight
+                                text={props.bookmark.title.clone().unwrap_or_else(|| props.bookmark.url.clone())}
+                                terms={props.highlight.clone()}
+                            />
+                        },
+                    } }
                 </a>
                 { if props.bookmark.private {
                     html! { <span class="material-icons-outlined bookmark__title-private-icon" title="private">{"lock"}</span> }
@@ -103,35 +137,69 @@ pub fn bookmark(props: &Props) -> Html {
                     html! { <></> }
                 } }
             </div>
+            {display_byline(&props.bookmark)}
             { props.bookmark.description.as_ref().map(|d| html! {
                 <div class="bookmark__description">
-                    <Highlight text={d.clone()} terms={props.highlight.clone()} />
+                    { match &props.bookmark.description_snippet {
+                        Some(snippet) => html! { <Snippet snippet={snippet.clone()} /> },
+                        None => html! { <Highlight text={d.clone()} terms={props.highlight.clone()} /> },
+                    } }
                 </div>
             })}
             <ul class="bookmark__tags-list">
                 {
-                    props.bookmark.tags.as_slice().iter().map(|t| html! {
-                        <li>
+                    props.bookmark.tags.as_slice().iter().map(|t| {
+                        let metadata = props.bookmark.tags_metadata.get(t);
+                        html! {
+                            <li>
+                                <a
+                                    href="#"
+                                    style={metadata
+                                        .and_then(|m| m.color.as_ref())
+                                        .map(|c| format!("color: {}", c))}
+                                    title={metadata.and_then(|m| m.description.clone())}
+                                    onclick={
+                                        let t = t.clone();
+                                        let on_select_tag_filter = props.on_select_tag_filter.clone();
+                                        Callback::from(move |e: MouseEvent| {
+                                            e.prevent_default();
+                                            on_select_tag_filter.emit(t.clone());
+                                        })
+                                    }
+                                >
+                                    { metadata.and_then(|m| m.icon.as_ref()).map(|i| html! {
+                                        <span class="material-icons-outlined bookmark__tag-icon">{i}</span>
+                                    }) }
+                                    {t}
+                                </a>
+                            </li>
+                        }
+                    }).collect::<Html>()
+                }
+            </ul>
+            <div class="bookmark__footer">
+                <div class="bookmark__actions">
+                    {display_date(&props.bookmark)}
+                    { props.bookmark.domain.as_ref().map(|domain| html! {
+                        <>
+                            {"\u{00a0}\u{00b7}\u{00a0}"}
                             <a
+                                class="bookmark__domain"
                                 href="#"
+                                title="show bookmarks from this site"
                                 onclick={
-                                    let t = t.clone();
-                                    let on_select_tag_filter = props.on_select_tag_filter.clone();
+                                    let domain = domain.clone();
+                                    let on_select_domain_filter = props.on_select_domain_filter.clone();
                                     Callback::from(move |e: MouseEvent| {
                                         e.prevent_default();
-                                        on_select_tag_filter.emit(t.clone());
+                                        on_select_domain_filter.emit(domain.clone());
                                     })
                                 }
                             >
-                                {t}
+                                {domain}
                             </a>
-                        </li>
-                    }).collect::<Html>()
-                }
-            </ul>
-            <div class="bookmark__footer">
-                <div class="bookmark__actions">
-                    {display_date(&props.bookmark)}
+                        </>
+                    })}
                     {"\u{00a0}\u{00b7}\u{00a0}"}
                     <a
                         class="material-icons-outlined md-16"
@@ -155,6 +223,20 @@ pub fn bookmark(props: &Props) -> Html {
                                 >
                                     {"push_pin"}
                                 </a>
+                                {"\u{00a0}"}
+                                <a
+                                    class={classes!("material-icons-outlined", "md-16",
+                                        refresh_pending.then_some("orange")
+                                    )}
+                                    onclick={onclick_refresh}
+                                    href="#refresh"
+                                    title={match *refresh_pending {
+                                        true => "metadata refresh pending",
+                                        false => "refresh metadata",
+                                    }}
+                                >
+                                    {"refresh"}
+                                </a>
                                 {"\u{00a0}"}
                                  <a
                                     class="material-icons-outlined md-16 blue"
@@ -207,3 +289,29 @@ fn display_date(bookmark: &BookmarkData) -> Html {
         }
     }
 }
+
+/// The site, author and publication date of the page, when it declares them.
+fn display_byline(bookmark: &BookmarkData) -> Html {
+    let parts = [
+        bookmark.metadata.site_name.as_ref().map(|s| s.to_string()),
+        bookmark
+            .metadata
+            .author
+            .as_ref()
+            .map(|a| format!("by {}", a)),
+        bookmark
+            .metadata
+            .published_date
+            .map(|d| d.format("%h %e, %Y").to_string()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<String>>();
+
+    match parts.is_empty() {
+        true => html! {},
+        false => html! {
+            <div class="bookmark__byline">{parts.join("\u{00a0}\u{00b7}\u{00a0}")}</div>
+        },
+    }
+}
@@ -54,7 +54,7 @@ async fn fetch_bookmark(id: i32) -> Option<Bookmark> {
     )
     .await
     {
-        Some(GetBookmarkResult::Success(payload)) => Some(Bookmark::from(payload)),
+        Some(GetBookmarkResult::Success(payload)) => Some(Bookmark::from(*payload)),
         _ => {
             // todo handle error
             None
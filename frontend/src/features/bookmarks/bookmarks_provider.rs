@@ -30,6 +30,25 @@ pub struct Params {
     pub search_terms: Option<Vec<AttrValue>>,
     pub order: Option<Order>,
     pub filter: Option<Filter>,
+    pub domain: Option<AttrValue>,
+    pub descendants: Option<bool>,
+    pub dates: DateRange,
+}
+
+/// Bounds on the creation and update dates, as accepted by the API (`YYYY-MM-DD` or RFC 3339).
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct DateRange {
+    pub created_after: Option<AttrValue>,
+    pub created_before: Option<AttrValue>,
+    pub updated_after: Option<AttrValue>,
+}
+
+impl DateRange {
+    pub fn is_empty(&self) -> bool {
+        self.created_after.is_none()
+            && self.created_before.is_none()
+            && self.updated_after.is_none()
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Default, Debug)]
@@ -71,6 +90,9 @@ pub struct State {
     tags: Rc<Vec<AttrValue>>,
     search_terms: Rc<Vec<AttrValue>>,
     filter: Option<Filter>,
+    domain: Option<AttrValue>,
+    descendants: bool,
+    dates: DateRange,
     data: Eventually<Data>,
     stats: Eventually<Stats>,
 }
@@ -84,6 +106,9 @@ impl Default for State {
             tags: Rc::new(Vec::new()),
             search_terms: Rc::new(Vec::new()),
             filter: None,
+            domain: None,
+            descendants: false,
+            dates: DateRange::default(),
             data: Eventually::None,
             stats: Eventually::None,
         }
@@ -112,6 +137,9 @@ impl From<&State> for Params {
                 Order::CreationDateAsc => Some(state.order),
             },
             filter: state.filter,
+            domain: state.domain.clone(),
+            descendants: state.descendants.then_some(true),
+            dates: state.dates.clone(),
         }
     }
 }
@@ -146,6 +174,9 @@ impl From<Option<&Params>> for State {
             state.order = order;
         }
         state.filter = value.filter;
+        state.domain = value.domain.clone();
+        state.descendants = value.descendants.unwrap_or_default();
+        state.dates = value.dates.clone();
 
         state
     }
@@ -356,6 +387,45 @@ pub fn bookmarks_provider(props: &Props) -> Html {
         })
     };
 
+    let on_change_domain = {
+        let state = state.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |domain: Option<AttrValue>| {
+            let mut new_state = (*state).clone();
+            new_state.domain = domain;
+            new_state.page = 0;
+            new_state.data = Eventually::None;
+
+            trigger_update(&on_change, &state, new_state);
+        })
+    };
+
+    let on_toggle_descendants = {
+        let state = state.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |_| {
+            let mut new_state = (*state).clone();
+            new_state.descendants = !state.descendants;
+            new_state.page = 0;
+            new_state.data = Eventually::None;
+
+            trigger_update(&on_change, &state, new_state);
+        })
+    };
+
+    let on_clear_dates = {
+        let state = state.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |_| {
+            let mut new_state = (*state).clone();
+            new_state.dates = DateRange::default();
+            new_state.page = 0;
+            new_state.data = Eventually::None;
+
+            trigger_update(&on_change, &state, new_state);
+        })
+    };
+
     match state.data.as_ref() {
         Eventually::Some(data) => {
             let context = BookmarksContext {
@@ -367,6 +437,9 @@ pub fn bookmarks_provider(props: &Props) -> Html {
                 selected_tags: state.tags.clone(),
                 search_terms: state.search_terms.clone(),
                 filter: state.filter,
+                domain: state.domain.clone(),
+                descendants: state.descendants,
+                dates: state.dates.clone(),
                 page_count: data.pages_count,
                 on_change_order,
                 on_previous,
@@ -376,6 +449,9 @@ pub fn bookmarks_provider(props: &Props) -> Html {
                 on_change_tags,
                 on_change_search_terms,
                 on_change_filter,
+                on_change_domain,
+                on_toggle_descendants,
+                on_clear_dates,
                 links: state
                     .stats
                     .as_ref()
@@ -411,15 +487,13 @@ fn trigger_update(
     }
 }
 
-async fn fetch_bookmarks(state: &State) -> (Vec<Bookmark>, u64) {
+/// Builds the query parameters selecting bookmarks, shared by all the bookmarks endpoints.
+pub fn criteria_query_params(params: &Params) -> Vec<(&'static str, String)> {
     // todo review query param serialization and struct shared with API
-    let params = vec![
-        ("order", state.order.query_param().to_string()),
-        ("page", state.page.to_string()),
-        ("count", state.page_size.to_string()),
+    let mut query_params = vec![
         (
             "filter",
-            state
+            params
                 .filter
                 .as_ref()
                 .map(|f| f.query_param())
@@ -428,9 +502,10 @@ async fn fetch_bookmarks(state: &State) -> (Vec<Bookmark>, u64) {
         ),
         (
             "tags",
-            state
+            params
                 .tags
                 .iter()
+                .flatten()
                 .map(|tag| encode(tag.as_str()))
                 .reduce(|a, b| Cow::Owned(format!("{}+{}", a, b)))
                 .unwrap_or_default()
@@ -438,15 +513,43 @@ async fn fetch_bookmarks(state: &State) -> (Vec<Bookmark>, u64) {
         ),
         (
             "search",
-            state
+            params
                 .search_terms
                 .iter()
+                .flatten()
                 .map(|term| encode(term.as_str()))
                 .reduce(|a, b| Cow::Owned(format!("{}+{}", a, b)))
                 .unwrap_or_default()
                 .to_string(),
         ),
     ];
+    if let Some(domain) = &params.domain {
+        query_params.push(("domain", domain.to_string()));
+    }
+    if let Some(true) = params.descendants {
+        query_params.push(("descendants", "true".to_string()));
+    }
+    if let Some(date) = &params.dates.created_after {
+        query_params.push(("created_after", date.to_string()));
+    }
+    if let Some(date) = &params.dates.created_before {
+        query_params.push(("created_before", date.to_string()));
+    }
+    if let Some(date) = &params.dates.updated_after {
+        query_params.push(("updated_after", date.to_string()));
+    }
+    query_params
+}
+
+async fn fetch_bookmarks(state: &State) -> (Vec<Bookmark>, u64) {
+    // todo review query param serialization and struct shared with API
+    let mut params = vec![
+        ("order", state.order.query_param().to_string()),
+        ("page", state.page.to_string()),
+        ("count", state.page_size.to_string()),
+        ("highlight", (!state.search_terms.is_empty()).to_string()),
+    ];
+    params.append(&mut criteria_query_params(&Params::from(state)));
 
     match GetBookmarksResult::from(Request::get(URL_BOOKMARKS).query(params).send().await).await {
         Some(GetBookmarksResult::Success(response)) => (
@@ -1,4 +1,6 @@
-use crate::features::bookmarks::bookmarks_provider::{BookmarksProvider, Filter, Order, Params};
+use crate::features::bookmarks::bookmarks_provider::{
+    BookmarksProvider, DateRange, Filter, Order, Params,
+};
 use crate::Route;
 use serde::{Deserialize, Serialize};
 use std::rc::Rc;
@@ -76,6 +78,11 @@ pub struct QueryParams {
     terms: Option<Vec<AttrValue>>,
     order: Option<QueryOrder>,
     filter: Option<QueryFilter>,
+    domain: Option<String>,
+    descendants: Option<bool>,
+    created_after: Option<String>,
+    created_before: Option<String>,
+    updated_after: Option<String>,
 }
 
 impl From<Params> for QueryParams {
@@ -87,6 +94,11 @@ impl From<Params> for QueryParams {
             terms: value.search_terms,
             order: value.order.map(QueryOrder::from),
             filter: value.filter.map(QueryFilter::from),
+            domain: value.domain.map(|d| d.to_string()),
+            descendants: value.descendants,
+            created_after: value.dates.created_after.map(|d| d.to_string()),
+            created_before: value.dates.created_before.map(|d| d.to_string()),
+            updated_after: value.dates.updated_after.map(|d| d.to_string()),
         }
     }
 }
@@ -100,6 +112,13 @@ impl From<&QueryParams> for Params {
             search_terms: value.terms.clone(),
             order: value.order.as_ref().map(Order::from),
             filter: value.filter.as_ref().map(Filter::from),
+            domain: value.domain.clone().map(AttrValue::from),
+            descendants: value.descendants,
+            dates: DateRange {
+                created_after: value.created_after.clone().map(AttrValue::from),
+                created_before: value.created_before.clone().map(AttrValue::from),
+                updated_after: value.updated_after.clone().map(AttrValue::from),
+            },
         }
     }
 }
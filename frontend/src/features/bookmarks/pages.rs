@@ -2,4 +2,5 @@ pub mod bookmarks;
 pub mod create_bookmark;
 pub mod delete_bookmark;
 pub mod edit_bookmark;
+pub mod timeline;
 pub mod view_bookmark;
@@ -1,13 +1,26 @@
 use super::super::super::super::data::Bookmark as BookmarkData;
 use super::super::super::super::data::Tags;
 use super::super::bookmark::Bookmark;
-use super::super::bookmarks_provider::{Filter, Order};
+use super::super::bookmarks_provider::{criteria_query_params, DateRange, Filter, Order, Params};
+use super::super::bookmarks_query::QueryParams;
 use crate::components::nav::Nav;
 use crate::components::page_size::PageSize;
+use crate::components::saved_searches_provider::SavedSearches;
+use crate::components::search_input::SearchInput;
 use crate::components::tag_input::TagInput;
+use crate::features::tag_cloud::related_tags::RelatedTags;
+use crate::Route;
+use gloo_net::http::Request;
+use rest_api::bookmarks::refresh::RefreshBookmarksResult;
+use rest_api::bookmarks::URL_BOOKMARKS_REFRESH;
+use rest_api::saved_searches::{
+    SaveSearchRequest, SaveSearchResult, URL_SAVED_SEARCH, URL_SAVED_SEARCHES,
+};
 use std::rc::Rc;
 use web_sys::HtmlInputElement;
+use yew::platform::spawn_local;
 use yew::prelude::*;
+use yew_router::prelude::*;
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct Props {
@@ -20,6 +33,9 @@ pub struct Props {
     pub selected_tags: Rc<Vec<AttrValue>>,
     pub search_terms: Rc<Vec<AttrValue>>,
     pub filter: Option<Filter>,
+    pub domain: Option<AttrValue>,
+    pub descendants: bool,
+    pub dates: DateRange,
     pub on_change_order: Callback<Order>,
     pub on_previous: Callback<()>,
     pub on_next: Callback<()>,
@@ -28,6 +44,9 @@ pub struct Props {
     pub on_change_tags: Callback<Vec<AttrValue>>,
     pub on_change_filter: Callback<Filter>,
     pub on_change_search_terms: Callback<Vec<AttrValue>>,
+    pub on_change_domain: Callback<Option<AttrValue>>,
+    pub on_toggle_descendants: Callback<()>,
+    pub on_clear_dates: Callback<()>,
     pub links: u64,
     pub private_links: u64,
 }
@@ -61,21 +80,107 @@ pub fn bookmarks(props: &Props) -> Html {
 
     let filter_by_text = {
         let props = props.clone();
+        Callback::from(move |search_text: String| {
+            let terms = search_text
+                .split(' ')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_owned())
+                .map(AttrValue::from)
+                .collect::<Vec<AttrValue>>();
+            props.on_change_search_terms.emit(terms);
+        })
+    };
+
+    let filter_by_domain = {
+        let props = props.clone();
+        Callback::from(move |domain: AttrValue| props.on_change_domain.emit(Some(domain)))
+    };
+
+    let onclick_timeline = {
+        let navigator = use_navigator().unwrap();
+        let props = props.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let _ = navigator.push_with_query(
+                &Route::Timeline,
+                &QueryParams::from(Params {
+                    order: None,
+                    page_size: None,
+                    ..current_params(&props)
+                }),
+            );
+        })
+    };
+
+    let saved_searches = use_context::<SavedSearches>().expect("no ctx found");
+    let save_search = {
+        let props = props.clone();
+        let saved_searches = saved_searches.clone();
         Callback::from(move |e: KeyboardEvent| {
             if e.key() == "Enter" {
                 e.prevent_default();
-                let search_text: String = e.target_unchecked_into::<HtmlInputElement>().value();
-                let terms = search_text
-                    .split(' ')
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.to_owned())
-                    .map(AttrValue::from)
-                    .collect::<Vec<AttrValue>>();
-                props.on_change_search_terms.emit(terms);
+                let input = e.target_unchecked_into::<HtmlInputElement>();
+                let name = input.value().trim().to_string();
+                if name.is_empty() {
+                    return;
+                }
+
+                let query = serde_urlencoded::to_string(QueryParams::from(current_params(&props)))
+                    .unwrap_or_default();
+                let request = match saved_searches.searches.iter().find(|s| s.name == name) {
+                    None => Request::post(URL_SAVED_SEARCHES).json(&SaveSearchRequest {
+                        name,
+                        query,
+                        is_default: false,
+                    }),
+                    Some(existing) => {
+                        Request::put(&URL_SAVED_SEARCH.replace(":id", &existing.id.to_string()))
+                            .json(&SaveSearchRequest {
+                                name,
+                                query,
+                                is_default: existing.is_default,
+                            })
+                    }
+                }
+                .expect("could not set json");
+
+                let reload = saved_searches.reload.clone();
+                spawn_local(async move {
+                    if let Some(SaveSearchResult::Success(_)) =
+                        SaveSearchResult::from(request.send().await).await
+                    {
+                        input.set_value("");
+                        reload.emit(());
+                    }
+                });
             }
         })
     };
 
+    let refresh_count = use_state(|| None::<u64>);
+    let onclick_refresh = {
+        let props = props.clone();
+        let refresh_count = refresh_count.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let query_params = criteria_query_params(&current_params(&props));
+            let refresh_count = refresh_count.clone();
+            spawn_local(async move {
+                if let Some(RefreshBookmarksResult::Success(response)) =
+                    RefreshBookmarksResult::from(
+                        Request::post(URL_BOOKMARKS_REFRESH)
+                            .query(query_params)
+                            .send()
+                            .await,
+                    )
+                    .await
+                {
+                    refresh_count.set(Some(response.count));
+                }
+            });
+        })
+    };
+
     html! {
         <div>
             <div class="bookmarks-header">
@@ -87,11 +192,12 @@ pub fn bookmarks(props: &Props) -> Html {
                 </div>
                 <div class="bookmarks__search">
                     // todo do not auto refresh after a new tag is added by typing
-                    <input
-                        type="text"
+                    <SearchInput
                         placeholder="filter by text"
-                        value={props.search_terms.join(" ")}
-                        onkeydown={filter_by_text}
+                        value={AttrValue::from(props.search_terms.join(" "))}
+                        onsearch={filter_by_text}
+                        on_select_tag={props.on_select_tag_filter.clone()}
+                        on_select_domain={filter_by_domain}
                     />
                 </div>
                 <div class="bookmarks__search">
@@ -103,8 +209,31 @@ pub fn bookmarks(props: &Props) -> Html {
                         onupdate={props.on_change_tags.clone()}
                     />
                 </div>
-                <div class="bookmarks__stats"></div>
+                <div class="bookmarks__stats">
+                    { if saved_searches.enabled {
+                        html! {
+                            <input
+                                type="text"
+                                placeholder="save search as"
+                                onkeydown={save_search}
+                            />
+                        }
+                    } else {
+                        html! {}
+                    } }
+                </div>
             </div>
+            { if props.selected_tags.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <RelatedTags
+                        tags={props.selected_tags.clone()}
+                        descendants={props.descendants}
+                        on_select={props.on_select_tag_filter.clone()}
+                    />
+                }
+            } }
             <div class="bookmarks-header">
                 <div class="bookmarks__filter">
                     <span
@@ -137,6 +266,86 @@ pub fn bookmarks(props: &Props) -> Html {
                     >
                         {"public"}
                     </span>
+                    <span
+                        class={classes!(
+                            "material-icons-outlined", "md-14",
+                            if props.descendants {
+                                "bookmarks__filter-item--selected"
+                            } else {
+                                "bookmarks__filter-item"
+                            }
+                        )}
+                        title="include sub-tags"
+                        onclick={
+                            let props = props.clone();
+                            move |_:MouseEvent| props.on_toggle_descendants.emit(())
+                        }
+                    >
+                        {"account_tree"}
+                    </span>
+                    <a
+                        class="material-icons-outlined md-14 bookmarks__filter-item"
+                        title="timeline"
+                        href={Route::Timeline.to_path()}
+                        onclick={onclick_timeline}
+                    >
+                        {"calendar_month"}
+                    </a>
+                    { if saved_searches.enabled {
+                        html! {
+                            <>
+                                <a
+                                    class="material-icons-outlined md-14 bookmarks__filter-item"
+                                    title="refresh the metadata of these bookmarks"
+                                    href="#refresh"
+                                    onclick={onclick_refresh}
+                                >
+                                    {"sync"}
+                                </a>
+                                { refresh_count.map(|count| html! {
+                                    <span class="bookmarks__filter-refresh">
+                                        {count}{" to refresh"}
+                                    </span>
+                                }) }
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    } }
+                    { props.domain.as_ref().map(|domain| html! {
+                        <span class="bookmarks__filter-domain">
+                            {domain}
+                            <span
+                                class="material-icons-outlined md-14 bookmarks__filter-item"
+                                title="clear site"
+                                onclick={
+                                    let props = props.clone();
+                                    move |_:MouseEvent| props.on_change_domain.emit(None)
+                                }
+                            >
+                                {"close"}
+                            </span>
+                        </span>
+                    }) }
+                    { if props.dates.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <span class="bookmarks__filter-dates">
+                                {display_dates(&props.dates)}
+                                <span
+                                    class="material-icons-outlined md-14 bookmarks__filter-item"
+                                    title="clear dates"
+                                    onclick={
+                                        let props = props.clone();
+                                        move |_:MouseEvent| props.on_clear_dates.emit(())
+                                    }
+                                >
+                                    {"close"}
+                                </span>
+                            </span>
+                        }
+                    } }
                 </div>
                 <Nav
                     page={props.page + 1}
@@ -163,6 +372,10 @@ pub fn bookmarks(props: &Props) -> Html {
                         bookmark={Rc::new(b.clone())}
                         highlight={Some(props.search_terms.clone())}
                         on_select_tag_filter={props.on_select_tag_filter.clone()}
+                        on_select_domain_filter={
+                            let on_change_domain = props.on_change_domain.clone();
+                            move |domain: AttrValue| on_change_domain.emit(Some(domain))
+                        }
                     />
                 }).collect::<Html>()
             }
@@ -187,6 +400,41 @@ pub fn bookmarks(props: &Props) -> Html {
     }
 }
 
+/// The parameters of the bookmarks list currently displayed, without the page.
+fn current_params(props: &Props) -> Params {
+    Params {
+        page: None,
+        page_size: match props.page_size {
+            20 => None,
+            x => Some(x),
+        },
+        tags: (!props.selected_tags.is_empty()).then(|| (*props.selected_tags).clone()),
+        search_terms: (!props.search_terms.is_empty()).then(|| (*props.search_terms).clone()),
+        order: match props.order {
+            Order::CreationDateDesc => None,
+            Order::CreationDateAsc => Some(props.order),
+        },
+        filter: props.filter,
+        domain: props.domain.clone(),
+        descendants: props.descendants.then_some(true),
+        dates: props.dates.clone(),
+    }
+}
+
+fn display_dates(dates: &DateRange) -> String {
+    let mut parts = Vec::new();
+    if let Some(date) = &dates.created_after {
+        parts.push(format!("created from {}", date));
+    }
+    if let Some(date) = &dates.created_before {
+        parts.push(format!("created before {}", date));
+    }
+    if let Some(date) = &dates.updated_after {
+        parts.push(format!("updated from {}", date));
+    }
+    parts.join(", ")
+}
+
 #[function_component(BookmarksHOC)]
 pub fn bookmarks_hoc() -> Html {
     let bookmarks = use_context::<Props>().expect("no ctx found");
@@ -1,4 +1,4 @@
-use super::super::super::super::data::{Bookmark, Tags};
+use super::super::super::super::data::{Bookmark, BookmarkMetadata, Tags};
 use crate::components::tag_input::TagInput;
 use crate::Route;
 use gloo_net::http::Request;
@@ -47,6 +47,7 @@ struct State {
     in_progress: bool,
     step: Step,
     bookmark: Bookmark,
+    suggested_tags: Vec<AttrValue>,
     error: Option<Error>,
 }
 
@@ -141,6 +142,9 @@ pub fn create_bookmark(props: &Props) -> Html {
                             Some(CreateBookmarkResult::Success(_)) => {
                                 navigator.push(&Route::Bookmarks)
                             }
+                            Some(CreateBookmarkResult::Conflict(payload)) => {
+                                navigator.push(&Route::EditBookmark { id: payload.id })
+                            }
                             Some(CreateBookmarkResult::Forbidden) => {
                                 let mut new_state = (*state).clone();
                                 new_state.error = Some(Error::Forbidden);
@@ -203,6 +207,28 @@ pub fn create_bookmark(props: &Props) -> Html {
             state.set(new_state);
         })
     };
+    let onclick_suggested_tag = |tag: &AttrValue| {
+        let state = state.clone();
+        let tag = tag.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let mut new_state = (*state).clone();
+            new_state.bookmark.tags.push(tag.clone());
+            state.set(new_state);
+        })
+    };
+    // the suggestions not added yet
+    let suggested_tags = state
+        .suggested_tags
+        .iter()
+        .filter(|s| {
+            !state
+                .bookmark
+                .tags
+                .iter()
+                .any(|t| t.to_lowercase() == s.to_lowercase())
+        })
+        .collect::<Vec<&AttrValue>>();
     let onclick_private = {
         let state = state.clone();
         Callback::from(move |_| {
@@ -260,7 +286,7 @@ pub fn create_bookmark(props: &Props) -> Html {
                 <p>
                     <TagInput
                         placeholder="tags"
-                        tags={vec![]}
+                        tags={state.bookmark.tags.clone()}
                         available_tags={Some(Rc::new(
                             props.tags
                                 .iter()
@@ -270,6 +296,18 @@ pub fn create_bookmark(props: &Props) -> Html {
                         onupdate={onupdate_tags}
                     />
                 </p>
+                if !suggested_tags.is_empty() {
+                <ul class="suggested-tags">
+                    <li class="suggested-tags__label">{"suggested:"}</li>
+                    {
+                        suggested_tags.iter().map(|t| html! {
+                            <li>
+                                <a href="#" onclick={onclick_suggested_tag(t)}>{"+"}{(*t).clone()}</a>
+                            </li>
+                        }).collect::<Html>()
+                    }
+                </ul>
+                }
                 <p>
                     <input
                         type="checkbox"
@@ -304,7 +342,7 @@ fn fetch_url_data(url: &str, state: UseStateHandle<State>, navigator: Navigator)
     let url = URL_URLS.replace(":url", encode(url).as_ref());
     spawn_local(async move {
         let payload = match GetUrlResult::from(Request::get(&url).send().await).await {
-            Some(GetUrlResult::Success(payload)) => Some(payload),
+            Some(GetUrlResult::Success(payload)) => Some(*payload),
             Some(GetUrlResult::Conflict(payload)) => {
                 navigator.push(&Route::EditBookmark { id: payload.id });
                 None
@@ -320,7 +358,13 @@ fn fetch_url_data(url: &str, state: UseStateHandle<State>, navigator: Navigator)
         if let Some(payload) = payload {
             new_state.bookmark.url = AttrValue::from(payload.url);
             new_state.bookmark.title = payload.title.map(AttrValue::from);
-            new_state.bookmark.description = payload.description.map(AttrValue::from)
+            new_state.bookmark.description = payload.description.map(AttrValue::from);
+            new_state.bookmark.metadata = BookmarkMetadata::from(payload.metadata);
+            new_state.suggested_tags = payload
+                .suggested_tags
+                .into_iter()
+                .map(AttrValue::from)
+                .collect();
         }
         state.set(new_state);
     });
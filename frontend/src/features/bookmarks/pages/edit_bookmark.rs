@@ -2,8 +2,9 @@ use super::super::super::super::data::{Bookmark, Tags};
 use crate::components::tag_input::TagInput;
 use crate::Route;
 use gloo_net::http::Request;
+use rest_api::bookmarks::merge::{MergeBookmarksRequest, MergeBookmarksResult};
 use rest_api::bookmarks::update::{UpdateBookmarkRequest, UpdateBookmarkResult};
-use rest_api::bookmarks::{Access, URL_BOOKMARK};
+use rest_api::bookmarks::{Access, URL_BOOKMARK, URL_BOOKMARK_MERGE};
 use std::rc::Rc;
 use web_sys::HtmlInputElement;
 use yew::platform::spawn_local;
@@ -18,6 +19,8 @@ pub struct Props {
 
 #[derive(Clone, PartialEq)]
 enum Error {
+    /// Another bookmark already points to the URL.
+    Conflict(i32),
     Forbidden,
     NotFound,
     Other,
@@ -60,25 +63,58 @@ pub fn edit_bookmark(props: &Props) -> Html {
         let navigator = navigator.clone();
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
+            update((*state).clone(), state.clone(), navigator.clone());
+        })
+    };
+    let onclick_merge = {
+        let state = state.clone();
+        let navigator = navigator.clone();
+        let original_tags = props.bookmark.tags.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let duplicate_id = match state.error {
+                Some(Error::Conflict(id)) => id,
+                _ => return,
+            };
             let state = state.clone();
             let navigator = navigator.clone();
+            let original_tags = original_tags.clone();
             spawn_local(async move {
-                match UpdateBookmarkResult::from(
-                    Request::put(&URL_BOOKMARK.replace(":id", &state.bookmark.id.to_string()))
-                        .json(&UpdateBookmarkRequest::from(&state.bookmark))
-                        .expect("could not set json")
-                        .send()
-                        .await,
+                match MergeBookmarksResult::from(
+                    Request::post(
+                        &URL_BOOKMARK_MERGE.replace(":id", &state.bookmark.id.to_string()),
+                    )
+                    .json(&MergeBookmarksRequest {
+                        ids: vec![duplicate_id],
+                    })
+                    .expect("could not set json")
+                    .send()
+                    .await,
                 )
                 .await
                 {
-                    Some(UpdateBookmarkResult::Success(_)) => navigator.push(&Route::Bookmarks),
-                    Some(UpdateBookmarkResult::Forbidden) => {
+                    Some(MergeBookmarksResult::Success(merged)) => {
+                        // the tags of the duplicate are kept, the ones removed in the form are not
+                        // brought back
+                        let mut new_state = (*state).clone();
+                        for tag in merged.tags {
+                            let tag = AttrValue::from(tag);
+                            if !original_tags.contains(&tag)
+                                && !new_state.bookmark.tags.contains(&tag)
+                            {
+                                new_state.bookmark.tags.push(tag);
+                            }
+                        }
+                        new_state.error = None;
+                        state.set(new_state.clone());
+                        update(new_state, state, navigator);
+                    }
+                    Some(MergeBookmarksResult::Forbidden) => {
                         let mut new_state = (*state).clone();
                         new_state.error = Some(Error::Forbidden);
                         state.set(new_state);
                     }
-                    Some(UpdateBookmarkResult::NotFound(_, _)) => {
+                    Some(MergeBookmarksResult::NotFound(_, _)) => {
                         let mut new_state = (*state).clone();
                         new_state.error = Some(Error::NotFound);
                         state.set(new_state);
@@ -164,6 +200,14 @@ pub fn edit_bookmark(props: &Props) -> Html {
             <div class="centered-box">
                 <h1 class="centered-box__title">{"Edit bookmark"}</h1>
                 { match state.error {
+                    Some(Error::Conflict(id)) => html! {
+                        <div class="centered-box__error">
+                            {"You already bookmarked this URL in "}
+                            <Link<Route> to={Route::ViewBookmark { id }}>{"this bookmark"}</Link<Route>>
+                            {"; merge it into this one? "}
+                            <button type="button" onclick={onclick_merge} class="button--action">{"Merge"}</button>
+                        </div>
+                    },
                     Some(Error::Forbidden) => html! {
                         <div class="centered-box__error">
                             {"You don't have the right to update this bookmark"}
@@ -241,6 +285,44 @@ pub fn edit_bookmark(props: &Props) -> Html {
     }
 }
 
+/// Saves the bookmark of `current`; when another bookmark points to the same URL, the error
+/// offers to merge it into this one.
+fn update(current: State, state: UseStateHandle<State>, navigator: Navigator) {
+    spawn_local(async move {
+        match UpdateBookmarkResult::from(
+            Request::put(&URL_BOOKMARK.replace(":id", &current.bookmark.id.to_string()))
+                .json(&UpdateBookmarkRequest::from(&current.bookmark))
+                .expect("could not set json")
+                .send()
+                .await,
+        )
+        .await
+        {
+            Some(UpdateBookmarkResult::Success(_)) => navigator.push(&Route::Bookmarks),
+            Some(UpdateBookmarkResult::Conflict(payload)) => {
+                let mut new_state = current;
+                new_state.error = Some(Error::Conflict(payload.id));
+                state.set(new_state);
+            }
+            Some(UpdateBookmarkResult::Forbidden) => {
+                let mut new_state = current;
+                new_state.error = Some(Error::Forbidden);
+                state.set(new_state);
+            }
+            Some(UpdateBookmarkResult::NotFound(_, _)) => {
+                let mut new_state = current;
+                new_state.error = Some(Error::NotFound);
+                state.set(new_state);
+            }
+            _ => {
+                let mut new_state = current;
+                new_state.error = Some(Error::Other);
+                state.set(new_state);
+            }
+        }
+    })
+}
+
 #[function_component(EditBookmarkHOC)]
 pub fn edit_bookmark_hoc() -> Html {
     let bookmark = use_context::<Rc<Bookmark>>().expect("no ctx found");
@@ -0,0 +1,248 @@
+use super::super::bookmarks_provider::{criteria_query_params, DateRange, Params};
+use super::super::bookmarks_query::QueryParams;
+use crate::Route;
+use chrono::{Datelike, Months, NaiveDate};
+use gloo_net::http::Request;
+use rest_api::bookmarks::timeline::{GetBookmarksTimelineResult, TimelineBucket};
+use rest_api::bookmarks::URL_BOOKMARKS_TIMELINE;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+#[function_component(Timeline)]
+pub fn timeline() -> Html {
+    let navigator = use_navigator().unwrap();
+    let params = Params::from(
+        &use_location()
+            .unwrap()
+            .query::<QueryParams>()
+            .ok()
+            .unwrap_or_default(),
+    );
+
+    let months = use_state(|| None::<Rc<Vec<TimelineBucket>>>);
+    let selected_month = use_state(|| None::<NaiveDate>);
+    let days = use_state(|| None::<Rc<Vec<TimelineBucket>>>);
+
+    {
+        let months = months.clone();
+        use_effect_with_deps(
+            move |params: &Params| {
+                let params = params.clone();
+                spawn_local(async move {
+                    months.set(Some(Rc::new(fetch_timeline(&params, "month").await)));
+                });
+                || {}
+            },
+            params.clone(),
+        );
+    }
+
+    {
+        let days = days.clone();
+        use_effect_with_deps(
+            move |(params, month): &(Params, Option<NaiveDate>)| {
+                days.set(None);
+                if let Some(month) = *month {
+                    let params = bucket_params(params, month, next_month(month));
+                    spawn_local(async move {
+                        days.set(Some(Rc::new(fetch_timeline(&params, "day").await)));
+                    });
+                }
+                || {}
+            },
+            (params.clone(), *selected_month),
+        );
+    }
+
+    let link = |from: NaiveDate, to: NaiveDate, content: Html| {
+        let query = QueryParams::from(bucket_params(&params, from, to));
+        html! {
+            <a
+                href={format!(
+                    "{}?{}",
+                    Route::Bookmarks.to_path(),
+                    serde_urlencoded::to_string(&query).unwrap_or_default()
+                )}
+                onclick={
+                    let navigator = navigator.clone();
+                    Callback::from(move |e: MouseEvent| {
+                        e.prevent_default();
+                        let _ = navigator.push_with_query(&Route::Bookmarks, &query);
+                    })
+                }
+            >
+                {content}
+            </a>
+        }
+    };
+
+    let years = {
+        let mut years = BTreeMap::<i32, [u64; 12]>::new();
+        for bucket in months.iter().flat_map(|m| m.iter()) {
+            years.entry(bucket.date.year()).or_default()[bucket.date.month0() as usize] =
+                bucket.count;
+        }
+        years
+    };
+
+    html! {
+        <div class="centered-box">
+            <h1 class="centered-box__title">{"Timeline"}</h1>
+            <table class="timeline">
+                <tr>
+                    <th></th>
+                    { MONTHS.iter().map(|m| html! { <th>{m}</th> }).collect::<Html>() }
+                </tr>
+                {
+                    years.iter().rev().map(|(year, counts)| html! {
+                        <tr>
+                            <th>
+                                {
+                                    match (NaiveDate::from_ymd_opt(*year, 1, 1), NaiveDate::from_ymd_opt(*year + 1, 1, 1)) {
+                                        (Some(from), Some(to)) => link(from, to, html! { {year} }),
+                                        _ => html! { {year} },
+                                    }
+                                }
+                            </th>
+                            {
+                                counts.iter().enumerate().map(|(month0, count)| {
+                                    let month = NaiveDate::from_ymd_opt(*year, month0 as u32 + 1, 1);
+                                    match (count, month) {
+                                        (0, _) | (_, None) => html! { <td></td> },
+                                        (count, Some(month)) => html! {
+                                            <td
+                                                class={classes!(
+                                                    "timeline__bucket",
+                                                    (*selected_month == Some(month)).then_some("timeline__bucket--selected")
+                                                )}
+                                                onclick={
+                                                    let selected_month = selected_month.clone();
+                                                    move |_: MouseEvent| {
+                                                        selected_month.set(
+                                                            (*selected_month != Some(month)).then_some(month)
+                                                        )
+                                                    }
+                                                }
+                                            >
+                                                {count}
+                                            </td>
+                                        },
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </tr>
+                    }).collect::<Html>()
+                }
+            </table>
+            {
+                match (*selected_month, days.as_ref()) {
+                    (Some(month), Some(days)) => html! {
+                        <>
+                            <h2>
+                                {link(
+                                    month,
+                                    next_month(month),
+                                    html! { {month.format("%B %Y").to_string()} }
+                                )}
+                            </h2>
+                            <table class="timeline">
+                                <tr>
+                                    { WEEKDAYS.iter().map(|d| html! { <th>{d}</th> }).collect::<Html>() }
+                                </tr>
+                                {
+                                    calendar(month).chunks(7).map(|week| html! {
+                                        <tr>
+                                            {
+                                                week.iter().map(|day| match day {
+                                                    None => html! { <td></td> },
+                                                    Some(day) => {
+                                                        let count = days
+                                                            .iter()
+                                                            .find(|b| b.date == *day)
+                                                            .map(|b| b.count)
+                                                            .unwrap_or_default();
+                                                        html! {
+                                                            <td class="timeline__day">
+                                                                <span class="timeline__day-number">{day.day()}</span>
+                                                                {
+                                                                    match (count, day.succ_opt()) {
+                                                                        (0, _) | (_, None) => html! {},
+                                                                        (count, Some(next_day)) => link(*day, next_day, html! { {count} }),
+                                                                    }
+                                                                }
+                                                            </td>
+                                                        }
+                                                    }
+                                                }).collect::<Html>()
+                                            }
+                                        </tr>
+                                    }).collect::<Html>()
+                                }
+                            </table>
+                        </>
+                    },
+                    _ => html! {},
+                }
+            }
+        </div>
+    }
+}
+
+fn next_month(month: NaiveDate) -> NaiveDate {
+    month
+        .checked_add_months(Months::new(1))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// Returns the days of the month, padded with `None` so that weeks start on monday.
+fn calendar(month: NaiveDate) -> Vec<Option<NaiveDate>> {
+    let mut days = vec![None; month.weekday().num_days_from_monday() as usize];
+    days.extend(
+        month
+            .iter_days()
+            .take_while(|d| d.month() == month.month())
+            .map(Some),
+    );
+    days
+}
+
+/// Restricts the creation date of the given params to `[from, to)`.
+fn bucket_params(params: &Params, from: NaiveDate, to: NaiveDate) -> Params {
+    Params {
+        page: None,
+        dates: DateRange {
+            created_after: Some(AttrValue::from(from.format("%Y-%m-%d").to_string())),
+            created_before: Some(AttrValue::from(to.format("%Y-%m-%d").to_string())),
+            updated_after: params.dates.updated_after.clone(),
+        },
+        ..params.clone()
+    }
+}
+
+async fn fetch_timeline(params: &Params, granularity: &str) -> Vec<TimelineBucket> {
+    let mut query_params = criteria_query_params(params);
+    query_params.push(("granularity", granularity.to_string()));
+
+    match GetBookmarksTimelineResult::from(
+        Request::get(URL_BOOKMARKS_TIMELINE)
+            .query(query_params)
+            .send()
+            .await,
+    )
+    .await
+    {
+        Some(GetBookmarksTimelineResult::Success(response)) => response.buckets,
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use super::super::super::super::data::Bookmark as BookmarkData;
 use super::super::bookmark::Bookmark;
 use crate::Route;
+use rest_api::bookmarks::MetadataStatus;
 use std::rc::Rc;
 use yew::prelude::*;
 use yew_router::hooks::use_navigator;
@@ -27,13 +28,63 @@ pub fn view_bookmark(props: &Props) -> Html {
             </div>
             <ul class="bookmarks">
                 <li>
-                    <Bookmark bookmark={props.bookmark.clone()} on_select_tag_filter={Callback::from(move |_| {})} />
+                    <Bookmark
+                        bookmark={props.bookmark.clone()}
+                        on_select_tag_filter={Callback::from(move |_| {})}
+                        on_select_domain_filter={Callback::from(move |_| {})}
+                    />
                 </li>
             </ul>
+            { (!props.bookmark.metadata.is_empty() || props.bookmark.metadata_status.is_some()).then(|| html! {
+                <div class="bookmark-details">
+                    { props.bookmark.metadata.image_url.as_ref().map(|image_url| html! {
+                        <a href={props.bookmark.url.clone()}>
+                            <img class="bookmark-details__image" src={image_url.clone()} alt="" />
+                        </a>
+                    }) }
+                    <dl class="bookmark-details__list">
+                        { detail("Site", props.bookmark.metadata.site_name.clone()) }
+                        { detail("Author", props.bookmark.metadata.author.clone()) }
+                        { detail("Published", props.bookmark.metadata.published_date.map(|d| {
+                            AttrValue::from(d.format("%h %e, %Y").to_string())
+                        })) }
+                        { detail("Language", props.bookmark.metadata.language.clone()) }
+                        { detail("Metadata", metadata_status(&props.bookmark)) }
+                    </dl>
+                </div>
+            }) }
         </div>
     }
 }
 
+fn detail(label: &'static str, value: Option<AttrValue>) -> Html {
+    value
+        .map(|value| {
+            html! {
+                <>
+                    <dt>{label}</dt>
+                    <dd>{value}</dd>
+                </>
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// When the metadata of the bookmark were last fetched, and how it went.
+fn metadata_status(bookmark: &BookmarkData) -> Option<AttrValue> {
+    let date = bookmark
+        .metadata_fetch_date
+        .map(|d| d.format("%h %e, %Y at %T %Z").to_string());
+    let status = match (bookmark.metadata_status?, date) {
+        (MetadataStatus::Pending, _) => "refresh pending".to_string(),
+        (MetadataStatus::Success, Some(date)) => format!("fetched on {}", date),
+        (MetadataStatus::Failure, Some(date)) => format!("could not be fetched on {}", date),
+        (_, None) => return None,
+    };
+
+    Some(AttrValue::from(status))
+}
+
 #[function_component(ViewBookmarkHOC)]
 pub fn view_bookmark_hoc() -> Html {
     let bookmark = use_context::<Rc<BookmarkData>>().expect("no ctx found");
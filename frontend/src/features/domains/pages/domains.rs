@@ -0,0 +1,69 @@
+use crate::Route;
+use gloo_net::http::Request;
+use rest_api::domains::{Domain, GetDomainsResult, URL_DOMAINS};
+use serde::Serialize;
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_hooks::use_effect_once;
+use yew_router::prelude::*;
+
+#[derive(Serialize)]
+struct QueryParams {
+    domain: String,
+}
+
+#[function_component(Domains)]
+pub fn domains() -> Html {
+    let navigator = use_navigator().unwrap();
+    let domains = use_state(|| Rc::new(Vec::<Domain>::new()));
+
+    {
+        let domains = domains.clone();
+        use_effect_once(move || {
+            spawn_local(async move {
+                match GetDomainsResult::from(Request::get(URL_DOMAINS).send().await).await {
+                    Some(GetDomainsResult::Success(payload)) => domains.set(Rc::new(payload)),
+                    _ => {
+                        // todo handle errors
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    html! {
+        <div class="centered-box">
+            <h1 class="centered-box__title">{"Sites"}</h1>
+            <ul class="domains">
+                {
+                    domains.iter().map(|d| {
+                        let query = QueryParams { domain: d.name.clone() };
+                        html! {
+                            <li>
+                                <a
+                                    href={format!(
+                                        "{}?{}",
+                                        Route::Bookmarks.to_path(),
+                                        serde_urlencoded::to_string(&query).unwrap_or_default()
+                                    )}
+                                    onclick={
+                                        let navigator = navigator.clone();
+                                        Callback::from(move |e: MouseEvent| {
+                                            e.prevent_default();
+                                            let _ = navigator.push_with_query(&Route::Bookmarks, &query);
+                                        })
+                                    }
+                                >
+                                    {d.name.clone()}
+                                </a>
+                                <span class="domains__count">{d.count}</span>
+                            </li>
+                        }
+                    }).collect::<Html>()
+                }
+            </ul>
+        </div>
+    }
+}
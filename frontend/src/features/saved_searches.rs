@@ -0,0 +1,9 @@
+pub mod default_search;
+pub mod pages;
+
+use crate::data::SavedSearch;
+use crate::features::bookmarks::bookmarks_query::QueryParams;
+
+pub fn query_params(search: &SavedSearch) -> QueryParams {
+    serde_urlencoded::from_str::<QueryParams>(search.query.as_str()).unwrap_or_default()
+}
@@ -0,0 +1,40 @@
+use super::query_params;
+use crate::components::saved_searches_provider::SavedSearches;
+use crate::Route;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct Props {
+    pub children: Children,
+}
+
+/// Redirects to the default saved search, if any; renders its children otherwise.
+#[function_component(DefaultSearch)]
+pub fn default_search(props: &Props) -> Html {
+    let navigator = use_navigator().unwrap();
+    let saved_searches = use_context::<SavedSearches>().expect("no ctx found");
+    let default_search = saved_searches.default_search().cloned();
+
+    {
+        let default_search = default_search.clone();
+        use_effect_with_deps(
+            move |default_search| {
+                if let Some(default_search) = default_search {
+                    let _ = navigator
+                        .replace_with_query(&Route::Bookmarks, &query_params(default_search));
+                }
+                || {}
+            },
+            default_search,
+        );
+    }
+
+    if !saved_searches.loaded || default_search.is_some() {
+        return html! {};
+    }
+
+    html! {
+        <>{ props.children.clone() }</>
+    }
+}
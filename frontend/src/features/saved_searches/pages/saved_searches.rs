@@ -0,0 +1,142 @@
+use super::super::super::super::data::SavedSearch;
+use super::super::query_params;
+use crate::components::saved_searches_provider::SavedSearches as SavedSearchesContext;
+use crate::Route;
+use gloo_net::http::Request;
+use rest_api::saved_searches::{
+    DeleteSavedSearchResult, SaveSearchRequest, SaveSearchResult, URL_SAVED_SEARCH,
+};
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[function_component(SavedSearches)]
+pub fn saved_searches() -> Html {
+    let navigator = use_navigator().unwrap();
+    let context = use_context::<SavedSearchesContext>().expect("no ctx found");
+    let error = use_state(|| None::<AttrValue>);
+
+    let onclick_default = |search: &SavedSearch| {
+        let search = search.clone();
+        let reload = context.reload.clone();
+        let error = error.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let search = search.clone();
+            let reload = reload.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match SaveSearchResult::from(
+                    Request::put(&URL_SAVED_SEARCH.replace(":id", &search.id.to_string()))
+                        .json(&SaveSearchRequest {
+                            name: search.name.to_string(),
+                            query: search.query.to_string(),
+                            is_default: !search.is_default,
+                        })
+                        .expect("could not set json")
+                        .send()
+                        .await,
+                )
+                .await
+                {
+                    Some(SaveSearchResult::Success(_)) => {
+                        error.set(None);
+                        reload.emit(());
+                    }
+                    _ => error.set(Some(AttrValue::from("An error has occurred"))),
+                }
+            });
+        })
+    };
+
+    let onclick_delete = |search: &SavedSearch| {
+        let id = search.id;
+        let reload = context.reload.clone();
+        let error = error.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let reload = reload.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match DeleteSavedSearchResult::from(
+                    Request::delete(&URL_SAVED_SEARCH.replace(":id", &id.to_string()))
+                        .send()
+                        .await,
+                )
+                .await
+                {
+                    Some(DeleteSavedSearchResult::Success) => {
+                        error.set(None);
+                        reload.emit(());
+                    }
+                    _ => error.set(Some(AttrValue::from("An error has occurred"))),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="centered-box">
+            <h1 class="centered-box__title">{"Saved searches"}</h1>
+            { error.as_ref().map(|e| html! {
+                <div class="centered-box__error">{e}</div>
+            }) }
+            { if context.searches.is_empty() {
+                html! {
+                    <p>{"Nothing saved yet: use the \"save search as\" field on the bookmarks page."}</p>
+                }
+            } else {
+                html! {
+                    <ul class="saved-searches">
+                        {
+                            context.searches.iter().map(|search| {
+                                let query = query_params(search);
+                                html! {
+                                    <li>
+                                        <a
+                                            href={format!(
+                                                "{}?{}",
+                                                Route::Bookmarks.to_path(),
+                                                search.query
+                                            )}
+                                            onclick={
+                                                let navigator = navigator.clone();
+                                                Callback::from(move |e: MouseEvent| {
+                                                    e.prevent_default();
+                                                    let _ = navigator.push_with_query(&Route::Bookmarks, &query);
+                                                })
+                                            }
+                                        >
+                                            {search.name.clone()}
+                                        </a>
+                                        {"\u{00a0}"}
+                                        <a
+                                            class={classes!(
+                                                "material-icons-outlined", "md-16",
+                                                search.is_default.then_some("orange")
+                                            )}
+                                            title={if search.is_default { "unset default" } else { "set as default" }}
+                                            href="#default"
+                                            onclick={onclick_default(search)}
+                                        >
+                                            {"home"}
+                                        </a>
+                                        {"\u{00a0}"}
+                                        <a
+                                            class="material-icons-outlined md-16 red"
+                                            title="delete"
+                                            href="#delete"
+                                            onclick={onclick_delete(search)}
+                                        >
+                                            {"delete"}
+                                        </a>
+                                    </li>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </ul>
+                }
+            } }
+        </div>
+    }
+}
@@ -1 +1,3 @@
 pub mod pages;
+pub mod related_tags;
+pub mod tag_tree;
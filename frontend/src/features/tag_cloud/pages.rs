@@ -1 +1,4 @@
+pub mod delete_tag;
+pub mod manage_tags;
+pub mod tag;
 pub mod tag_cloud;
@@ -0,0 +1,162 @@
+use crate::Route;
+use gloo_net::http::Request;
+use rest_api::tags::{DeleteTagResult, GetTagResponse, GetTagResult, URL_TAG};
+use urlencoding::encode;
+use web_sys::HtmlInputElement;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_router::hooks::use_navigator;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct Props {
+    pub name: AttrValue,
+}
+
+#[derive(Clone, PartialEq)]
+enum Error {
+    NotFound,
+    Other,
+}
+
+#[derive(Clone, PartialEq, Default)]
+struct State {
+    tag: Option<GetTagResponse>,
+    delete_bookmarks: bool,
+    error: Option<Error>,
+}
+
+#[function_component(DeleteTag)]
+pub fn delete_tag(props: &Props) -> Html {
+    let navigator = use_navigator().unwrap();
+    let state = use_state(State::default);
+
+    {
+        let state = state.clone();
+        use_effect_with_deps(
+            move |name: &AttrValue| {
+                let name = name.clone();
+                spawn_local(async move {
+                    let mut new_state = (*state).clone();
+                    match GetTagResult::from(
+                        Request::get(&URL_TAG.replace(":name", &encode(&name))).send().await,
+                    )
+                    .await
+                    {
+                        Some(GetTagResult::Success(tag)) => new_state.tag = Some(tag),
+                        Some(GetTagResult::NotFound) => new_state.error = Some(Error::NotFound),
+                        _ => new_state.error = Some(Error::Other),
+                    }
+                    state.set(new_state);
+                });
+                || {}
+            },
+            props.name.clone(),
+        );
+    }
+
+    let onchange_delete_bookmarks = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let mut new_state = (*state).clone();
+            new_state.delete_bookmarks = e.target_unchecked_into::<HtmlInputElement>().checked();
+            state.set(new_state);
+        })
+    };
+
+    let onclick_no = {
+        let navigator = navigator.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            navigator.push(&Route::ManageTags);
+        })
+    };
+
+    let onclick_yes = {
+        let name = props.name.clone();
+        let state = state.clone();
+        Callback::from(move |e: MouseEvent| {
+            let navigator = navigator.clone();
+            let name = name.clone();
+            let state = state.clone();
+            e.prevent_default();
+            spawn_local(async move {
+                match DeleteTagResult::from(
+                    Request::delete(&URL_TAG.replace(":name", &encode(&name)))
+                        .query([("delete_bookmarks", state.delete_bookmarks.to_string())])
+                        .send()
+                        .await,
+                )
+                .await
+                {
+                    Some(DeleteTagResult::Success) => navigator.push(&Route::ManageTags),
+                    Some(DeleteTagResult::NotFound) => {
+                        let mut new_state = (*state).clone();
+                        new_state.error = Some(Error::NotFound);
+                        state.set(new_state);
+                    }
+                    _ => {
+                        let mut new_state = (*state).clone();
+                        new_state.error = Some(Error::Other);
+                        state.set(new_state);
+                    }
+                };
+            });
+        })
+    };
+
+    html! {
+        <div class="centered-box">
+            <h1 class="delete-bookmark__title">{"Delete tag?"}</h1>
+            { match state.error {
+                Some(Error::NotFound) => html! {
+                    <div class="centered-box__error">
+                        {"Tag not found"}
+                    </div>
+                },
+                Some(Error::Other) => html! {
+                    <div class="centered-box__error">
+                        {"An error has occurred"}
+                    </div>
+                },
+                None => html!{ <></> }
+            }}
+            { match &state.tag {
+                Some(tag) => html! {
+                    <>
+                        <p>
+                            {format!(
+                                "The tag \"{}\" will be removed from {} bookmark(s).",
+                                tag.name, tag.count
+                            )}
+                        </p>
+                        { if tag.exclusive_count > 0 {
+                            html! {
+                                <p>
+                                    <label>
+                                        <input
+                                            type="checkbox"
+                                            checked={state.delete_bookmarks}
+                                            onchange={onchange_delete_bookmarks}
+                                        />
+                                        {format!(
+                                            " Also delete the {} bookmark(s) having no other tag",
+                                            tag.exclusive_count
+                                        )}
+                                    </label>
+                                </p>
+                            }
+                        } else {
+                            html! {}
+                        } }
+                        <p class="centered-box__buttons">
+                            <button type="button" onclick={onclick_no} class="button--safe">{"Cancel"}</button>
+                            {" "}
+                            <button type="button" onclick={onclick_yes} class="button--danger">{"Delete"}</button>
+                        </p>
+                    </>
+                },
+                None => html! {},
+            }}
+        </div>
+    }
+}
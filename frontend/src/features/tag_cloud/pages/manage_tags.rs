@@ -0,0 +1,404 @@
+use super::super::super::super::data::{Tag, TagAlias, TagAliases, Tags};
+use crate::Route;
+use gloo_net::http::Request;
+use rest_api::tag_aliases::{
+    CreateTagAliasResult, DeleteTagAliasResult, GetTagAliasesResult, URL_TAG_ALIAS, URL_TAG_ALIASES,
+};
+use rest_api::tags::{
+    GetTagsResult, MergeTagsRequest, RenameTagRequest, UpdateTagsResult, URL_TAG, URL_TAGS,
+    URL_TAGS_MERGE,
+};
+use std::rc::Rc;
+use urlencoding::encode;
+use web_sys::HtmlInputElement;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Clone, PartialEq, Default)]
+struct State {
+    tags: Option<Rc<Tags>>,
+    aliases: Rc<TagAliases>,
+    selected: Vec<AttrValue>,
+    editing: Option<AttrValue>,
+    error: Option<AttrValue>,
+    generation: u32,
+}
+
+#[function_component(ManageTags)]
+pub fn manage_tags() -> Html {
+    let navigator = use_navigator().unwrap();
+    let state = use_state(State::default);
+    let merge_input_ref = use_node_ref();
+    let alias_input_ref = use_node_ref();
+    let alias_tag_input_ref = use_node_ref();
+
+    {
+        let generation = state.generation;
+        let state = state.clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let mut new_state = (*state).clone();
+                    new_state.tags = Some(Rc::new(fetch_tags().await));
+                    new_state.aliases = Rc::new(fetch_aliases().await);
+                    state.set(new_state);
+                });
+                || {}
+            },
+            generation,
+        );
+    }
+
+    // applies the result of a rename or merge, reloading the tags when it succeeded
+    let on_result = {
+        let state = state.clone();
+        Callback::from(move |result: Option<UpdateTagsResult>| {
+            let mut new_state = (*state).clone();
+            match result {
+                Some(UpdateTagsResult::Success) => {
+                    new_state.selected = vec![];
+                    new_state.editing = None;
+                    new_state.error = None;
+                    new_state.generation += 1;
+                }
+                Some(UpdateTagsResult::InvalidName) => {
+                    new_state.error =
+                        Some(AttrValue::from("Tags cannot be empty nor contain spaces"))
+                }
+                Some(UpdateTagsResult::NotFound) => {
+                    new_state.error = Some(AttrValue::from("Tag not found"));
+                    new_state.generation += 1;
+                }
+                _ => new_state.error = Some(AttrValue::from("An error has occurred")),
+            }
+            state.set(new_state);
+        })
+    };
+
+    let onkeydown_rename = |tag: &Tag| {
+        let state = state.clone();
+        let on_result = on_result.clone();
+        let name = tag.name.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" {
+                e.prevent_default();
+                let new_name = e.target_unchecked_into::<HtmlInputElement>().value();
+                let on_result = on_result.clone();
+                let name = name.clone();
+                spawn_local(async move {
+                    on_result.emit(
+                        UpdateTagsResult::from(
+                            Request::put(&URL_TAG.replace(":name", &encode(&name)))
+                                .json(&RenameTagRequest { name: new_name })
+                                .expect("could not set json")
+                                .send()
+                                .await,
+                        )
+                        .await,
+                    );
+                });
+            } else if e.key() == "Esc" || e.key() == "Escape" {
+                e.prevent_default();
+                let mut new_state = (*state).clone();
+                new_state.editing = None;
+                state.set(new_state);
+            }
+        })
+    };
+
+    let onclick_edit = |tag: &Tag| {
+        let state = state.clone();
+        let name = tag.name.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let mut new_state = (*state).clone();
+            new_state.editing = Some(name.clone());
+            state.set(new_state);
+        })
+    };
+
+    let onclick_tag = |tag: &Tag| {
+        let navigator = navigator.clone();
+        let name = encode(&tag.name).into_owned();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            navigator.push(&Route::Tag { name: name.clone() });
+        })
+    };
+
+    let onclick_delete = |tag: &Tag| {
+        let navigator = navigator.clone();
+        let name = encode(&tag.name).into_owned();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            navigator.push(&Route::DeleteTag { name: name.clone() });
+        })
+    };
+
+    let onchange_select = |tag: &Tag| {
+        let state = state.clone();
+        let name = tag.name.clone();
+        Callback::from(move |e: Event| {
+            let checked = e.target_unchecked_into::<HtmlInputElement>().checked();
+            let mut new_state = (*state).clone();
+            new_state.selected.retain(|t| t != &name);
+            if checked {
+                new_state.selected.push(name.clone());
+            }
+            state.set(new_state);
+        })
+    };
+
+    let onsubmit_merge = {
+        let state = state.clone();
+        let on_result = on_result.clone();
+        let merge_input_ref = merge_input_ref.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if state.selected.is_empty() {
+                return;
+            }
+            let input = merge_input_ref.cast::<HtmlInputElement>().unwrap();
+            let request = MergeTagsRequest {
+                tags: state.selected.iter().map(|t| t.to_string()).collect(),
+                into: input.value(),
+            };
+            let on_result = on_result.clone();
+            spawn_local(async move {
+                let result = UpdateTagsResult::from(
+                    Request::post(URL_TAGS_MERGE)
+                        .json(&request)
+                        .expect("could not set json")
+                        .send()
+                        .await,
+                )
+                .await;
+                if let Some(UpdateTagsResult::Success) = result {
+                    input.set_value("");
+                }
+                on_result.emit(result);
+            });
+        })
+    };
+
+    let onsubmit_alias = {
+        let state = state.clone();
+        let alias_input_ref = alias_input_ref.clone();
+        let alias_tag_input_ref = alias_tag_input_ref.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let alias_input = alias_input_ref.cast::<HtmlInputElement>().unwrap();
+            let tag_input = alias_tag_input_ref.cast::<HtmlInputElement>().unwrap();
+            let request = rest_api::tag_aliases::TagAlias {
+                alias: alias_input.value(),
+                tag: tag_input.value(),
+            };
+            let state = state.clone();
+            spawn_local(async move {
+                let mut new_state = (*state).clone();
+                match CreateTagAliasResult::from(
+                    Request::post(URL_TAG_ALIASES)
+                        .json(&request)
+                        .expect("could not set json")
+                        .send()
+                        .await,
+                )
+                .await
+                {
+                    Some(CreateTagAliasResult::Success(_)) => {
+                        alias_input.set_value("");
+                        tag_input.set_value("");
+                        new_state.error = None;
+                        new_state.generation += 1;
+                    }
+                    Some(CreateTagAliasResult::InvalidName) => {
+                        new_state.error = Some(AttrValue::from(
+                            "Aliases cannot be empty nor contain spaces",
+                        ))
+                    }
+                    Some(CreateTagAliasResult::NotFound) => {
+                        new_state.error = Some(AttrValue::from("Tag not found"))
+                    }
+                    Some(CreateTagAliasResult::Conflict) => {
+                        new_state.error =
+                            Some(AttrValue::from("This name is already a tag or an alias"))
+                    }
+                    _ => new_state.error = Some(AttrValue::from("An error has occurred")),
+                }
+                state.set(new_state);
+            });
+        })
+    };
+
+    let onclick_delete_alias = |alias: &TagAlias| {
+        let state = state.clone();
+        let alias = alias.alias.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let state = state.clone();
+            let alias = alias.clone();
+            spawn_local(async move {
+                let mut new_state = (*state).clone();
+                match DeleteTagAliasResult::from(
+                    Request::delete(&URL_TAG_ALIAS.replace(":alias", &encode(&alias)))
+                        .send()
+                        .await,
+                )
+                .await
+                {
+                    Some(DeleteTagAliasResult::Success) | Some(DeleteTagAliasResult::NotFound) => {
+                        new_state.error = None;
+                        new_state.generation += 1;
+                    }
+                    _ => new_state.error = Some(AttrValue::from("An error has occurred")),
+                }
+                state.set(new_state);
+            });
+        })
+    };
+
+    html! {
+        <div class="centered-box">
+            <h1 class="centered-box__title">{"Manage tags"}</h1>
+            { state.error.as_ref().map(|e| html! {
+                <div class="centered-box__error">{e}</div>
+            }) }
+            <ul class="manage-tags">
+                {
+                    state.tags.iter().flat_map(|t| t.iter()).map(|tag| html! {
+                        <li>
+                            <input
+                                type="checkbox"
+                                checked={state.selected.contains(&tag.name)}
+                                onchange={onchange_select(tag)}
+                            />
+                            { if state.editing.as_ref() == Some(&tag.name) {
+                                html! {
+                                    <input
+                                        type="text"
+                                        value={tag.name.clone()}
+                                        onkeydown={onkeydown_rename(tag)}
+                                    />
+                                }
+                            } else {
+                                html! {
+                                    <>
+                                        <a
+                                            href={Route::Tag { name: encode(&tag.name).into_owned() }.to_path()}
+                                            onclick={onclick_tag(tag)}
+                                        >
+                                            {tag.name.clone()}
+                                        </a>
+                                        <span class="manage-tags__count">{tag.count}</span>
+                                        {"\u{00a0}"}
+                                        <a
+                                            class="material-icons-outlined md-16"
+                                            title="rename"
+                                            href="#rename"
+                                            onclick={onclick_edit(tag)}
+                                        >
+                                            {"edit"}
+                                        </a>
+                                        {"\u{00a0}"}
+                                        <a
+                                            class="material-icons-outlined md-16 red"
+                                            title="delete"
+                                            href={Route::DeleteTag { name: encode(&tag.name).into_owned() }.to_path()}
+                                            onclick={onclick_delete(tag)}
+                                        >
+                                            {"delete"}
+                                        </a>
+                                    </>
+                                }
+                            } }
+                        </li>
+                    }).collect::<Html>()
+                }
+            </ul>
+            <form onsubmit={onsubmit_merge}>
+                <p>
+                    <input
+                        ref={merge_input_ref}
+                        type="text"
+                        placeholder="merge selected tags into"
+                    />
+                </p>
+                <div class="centered-box__buttons">
+                    <p>
+                        <button type="submit" class={if state.selected.is_empty() {
+                            "button--disabled"
+                        } else {
+                            "button--action"
+                        }}>
+                            {"Merge"}
+                        </button>
+                    </p>
+                </div>
+            </form>
+            <h2>{"Aliases"}</h2>
+            <ul class="manage-tags">
+                {
+                    state.aliases.iter().map(|alias| html! {
+                        <li>
+                            {alias.alias.clone()}
+                            {" \u{2192} "}
+                            {alias.tag.clone()}
+                            {"\u{00a0}"}
+                            <a
+                                class="material-icons-outlined md-16 red"
+                                title="delete"
+                                href="#delete"
+                                onclick={onclick_delete_alias(alias)}
+                            >
+                                {"delete"}
+                            </a>
+                        </li>
+                    }).collect::<Html>()
+                }
+            </ul>
+            <form onsubmit={onsubmit_alias}>
+                <p>
+                    <input ref={alias_input_ref} type="text" placeholder="alias" />
+                    {" \u{2192} "}
+                    <input ref={alias_tag_input_ref} type="text" placeholder="tag" />
+                </p>
+                <div class="centered-box__buttons">
+                    <p>
+                        <button type="submit" class="button--action">{"Add alias"}</button>
+                    </p>
+                </div>
+            </form>
+        </div>
+    }
+}
+
+async fn fetch_tags() -> Tags {
+    match GetTagsResult::from(
+        Request::get(URL_TAGS)
+            .query([("order", "name")])
+            .send()
+            .await,
+    )
+    .await
+    {
+        Some(GetTagsResult::Success(tags)) => tags.into_iter().map(Tag::from).collect::<Vec<Tag>>(),
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
+
+async fn fetch_aliases() -> TagAliases {
+    match GetTagAliasesResult::from(Request::get(URL_TAG_ALIASES).send().await).await {
+        Some(GetTagAliasesResult::Success(aliases)) => aliases
+            .into_iter()
+            .map(TagAlias::from)
+            .collect::<Vec<TagAlias>>(),
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use super::super::super::super::data::{Bookmark as BookmarkData, TagMetadata, Tags};
+use crate::features::bookmarks::bookmark::Bookmark;
+use crate::features::bookmarks::bookmarks_provider::{criteria_query_params, Params};
+use crate::features::bookmarks::bookmarks_query::QueryParams;
+use crate::features::tag_cloud::related_tags::RelatedTags;
+use crate::Route;
+use common::TAG_SEPARATOR;
+use gloo_net::http::Request;
+use rest_api::bookmarks::get_many::GetBookmarksResult;
+use rest_api::bookmarks::URL_BOOKMARKS;
+use rest_api::tags::{UpdateTagMetadataResult, URL_TAG_METADATA};
+use std::rc::Rc;
+use urlencoding::encode;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+const BOOKMARKS_COUNT: u64 = 20;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct Props {
+    pub name: AttrValue,
+    pub logged_in: bool,
+}
+
+#[derive(Clone, PartialEq, Default)]
+struct State {
+    bookmarks: Option<Rc<Vec<BookmarkData>>>,
+    /// The metadata saved from this page, more recent than the context's.
+    metadata: Option<TagMetadata>,
+    editing: bool,
+    error: Option<AttrValue>,
+}
+
+/// Landing page of a tag: its description, the tags related to it in the hierarchy and the
+/// latest bookmarks having it or one of its descendants.
+#[function_component(TagPage)]
+pub fn tag_page(props: &Props) -> Html {
+    let navigator = use_navigator().unwrap();
+    let tags = use_context::<Rc<Tags>>().expect("no ctx found");
+    let state = use_state(State::default);
+    let color_input_ref = use_node_ref();
+    let icon_input_ref = use_node_ref();
+    let description_input_ref = use_node_ref();
+
+    {
+        let state = state.clone();
+        use_effect_with_deps(
+            move |name: &AttrValue| {
+                let name = name.clone();
+                spawn_local(async move {
+                    state.set(State {
+                        bookmarks: Some(Rc::new(fetch_bookmarks(&name).await)),
+                        ..State::default()
+                    });
+                });
+                || {}
+            },
+            props.name.clone(),
+        );
+    }
+
+    // the tags are matched regardless of their case, as the backend does
+    let lower_name = props.name.to_lowercase();
+    let metadata = state.metadata.clone().unwrap_or_else(|| {
+        tags.iter()
+            .find(|t| t.name.to_lowercase() == lower_name)
+            .map(|t| t.metadata.clone())
+            .unwrap_or_default()
+    });
+
+    let parent = props
+        .name
+        .rsplit_once(TAG_SEPARATOR)
+        .map(|(parent, _)| AttrValue::from(parent.to_string()));
+    let children = tags
+        .iter()
+        .filter(|t| {
+            t.name
+                .to_lowercase()
+                .strip_prefix(lower_name.as_str())
+                .and_then(|rest| rest.strip_prefix(TAG_SEPARATOR))
+                .map(|rest| !rest.is_empty() && !rest.contains(TAG_SEPARATOR))
+                .unwrap_or_default()
+        })
+        .map(|t| t.name.clone())
+        .collect::<Vec<AttrValue>>();
+
+    let goto_tag = {
+        let navigator = navigator.clone();
+        Callback::from(move |name: AttrValue| {
+            navigator.push(&Route::Tag {
+                name: encode(&name).into_owned(),
+            });
+        })
+    };
+
+    let goto_domain = {
+        let navigator = navigator.clone();
+        Callback::from(move |domain: AttrValue| {
+            let _ = navigator.push_with_query(
+                &Route::Bookmarks,
+                &QueryParams::from(Params {
+                    domain: Some(domain),
+                    ..Params::default()
+                }),
+            );
+        })
+    };
+
+    // refines the bookmarks of the tag with a co-occurring one
+    let goto_related = {
+        let navigator = navigator.clone();
+        let name = props.name.clone();
+        Callback::from(move |related: AttrValue| {
+            let _ = navigator.push_with_query(
+                &Route::Bookmarks,
+                &QueryParams::from(Params {
+                    tags: Some(vec![name.clone(), related]),
+                    descendants: Some(true),
+                    ..Params::default()
+                }),
+            );
+        })
+    };
+
+    let onclick_edit = {
+        let state = state.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let mut new_state = (*state).clone();
+            new_state.editing = !state.editing;
+            new_state.error = None;
+            state.set(new_state);
+        })
+    };
+
+    let onsubmit = {
+        let state = state.clone();
+        let name = props.name.clone();
+        let color_input_ref = color_input_ref.clone();
+        let icon_input_ref = icon_input_ref.clone();
+        let description_input_ref = description_input_ref.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let request = rest_api::tags::TagMetadata {
+                color: Some(color_input_ref.cast::<HtmlInputElement>().unwrap().value()),
+                description: Some(
+                    description_input_ref
+                        .cast::<HtmlTextAreaElement>()
+                        .unwrap()
+                        .value(),
+                ),
+                icon: Some(icon_input_ref.cast::<HtmlInputElement>().unwrap().value()),
+            };
+            let state = state.clone();
+            let name = name.clone();
+            spawn_local(async move {
+                let mut new_state = (*state).clone();
+                match UpdateTagMetadataResult::from(
+                    Request::put(&URL_TAG_METADATA.replace(":name", &encode(&name)))
+                        .json(&request)
+                        .expect("could not set json")
+                        .send()
+                        .await,
+                )
+                .await
+                {
+                    Some(UpdateTagMetadataResult::Success(metadata)) => {
+                        new_state.metadata = Some(TagMetadata::from(metadata));
+                        new_state.editing = false;
+                        new_state.error = None;
+                    }
+                    Some(UpdateTagMetadataResult::InvalidParameter(message)) => {
+                        new_state.error = Some(AttrValue::from(message))
+                    }
+                    Some(UpdateTagMetadataResult::NotFound) => {
+                        new_state.error = Some(AttrValue::from("Tag not found"))
+                    }
+                    _ => new_state.error = Some(AttrValue::from("An error has occurred")),
+                }
+                state.set(new_state);
+            });
+        })
+    };
+
+    let tag_link = |name: &AttrValue| {
+        html! {
+            <li>
+                <a
+                    href={Route::Tag { name: encode(name).into_owned() }.to_path()}
+                    onclick={
+                        let goto_tag = goto_tag.clone();
+                        let name = name.clone();
+                        Callback::from(move |e: MouseEvent| {
+                            e.prevent_default();
+                            goto_tag.emit(name.clone());
+                        })
+                    }
+                >
+                    {name.clone()}
+                </a>
+            </li>
+        }
+    };
+
+    let all_bookmarks_query = QueryParams::from(Params {
+        tags: Some(vec![props.name.clone()]),
+        descendants: Some(true),
+        ..Params::default()
+    });
+
+    html! {
+        <div class="tag-page">
+            <div class="centered-box">
+                <h1 class="centered-box__title">
+                    { metadata.icon.as_ref().map(|i| html! {
+                        <span class="material-icons-outlined tag-page__icon">{i}</span>
+                    }) }
+                    <span style={metadata.color.as_ref().map(|c| format!("color: {}", c))}>
+                        {props.name.clone()}
+                    </span>
+                </h1>
+                { metadata.description.as_ref().map(|d| html! {
+                    <p class="tag-page__description">{d}</p>
+                }) }
+                { if parent.is_some() || !children.is_empty() {
+                    html! {
+                        <ul class="tag-page__related">
+                            { parent.iter().map(tag_link).collect::<Html>() }
+                            { children.iter().map(tag_link).collect::<Html>() }
+                        </ul>
+                    }
+                } else {
+                    html! {}
+                } }
+                <RelatedTags
+                    tags={Rc::new(vec![props.name.clone()])}
+                    descendants=true
+                    on_select={goto_related}
+                />
+                { state.error.as_ref().map(|e| html! {
+                    <div class="centered-box__error">{e}</div>
+                }) }
+                { if state.editing {
+                    html! {
+                        <form {onsubmit}>
+                            <p>
+                                <input
+                                    ref={color_input_ref}
+                                    type="text"
+                                    placeholder="color, e.g. #1e90ff"
+                                    value={metadata.color.clone()}
+                                />
+                            </p>
+                            <p>
+                                <input
+                                    ref={icon_input_ref}
+                                    type="text"
+                                    placeholder="icon, e.g. bookmark"
+                                    value={metadata.icon.clone()}
+                                />
+                            </p>
+                            <p>
+                                <textarea
+                                    ref={description_input_ref}
+                                    placeholder="description"
+                                    value={metadata.description.clone()}
+                                />
+                            </p>
+                            <div class="centered-box__buttons">
+                                <p>
+                                    <button type="button" class="button--safe" onclick={onclick_edit.clone()}>{"Cancel"}</button>
+                                    {" "}
+                                    <button type="submit" class="button--action">{"Save"}</button>
+                                </p>
+                            </div>
+                        </form>
+                    }
+                } else if props.logged_in {
+                    html! {
+                        <div class="centered-box__buttons">
+                            <a href="#edit" onclick={onclick_edit.clone()}>{"edit"}</a>
+                        </div>
+                    }
+                } else {
+                    html! {}
+                } }
+            </div>
+            <ul class="bookmarks">
+                {
+                    state.bookmarks.iter().flat_map(|b| b.iter()).map(|b| html! {
+                        <Bookmark
+                            key={b.id}
+                            bookmark={Rc::new(b.clone())}
+                            on_select_tag_filter={goto_tag.clone()}
+                            on_select_domain_filter={goto_domain.clone()}
+                        />
+                    }).collect::<Html>()
+                }
+            </ul>
+            <div class="centered-box__buttons">
+                <a
+                    href={format!(
+                        "{}?{}",
+                        Route::Bookmarks.to_path(),
+                        serde_urlencoded::to_string(&all_bookmarks_query).unwrap_or_default()
+                    )}
+                    onclick={
+                        Callback::from(move |e: MouseEvent| {
+                            e.prevent_default();
+                            let _ = navigator.push_with_query(&Route::Bookmarks, &all_bookmarks_query);
+                        })
+                    }
+                >
+                    {"all bookmarks"}
+                </a>
+            </div>
+        </div>
+    }
+}
+
+async fn fetch_bookmarks(name: &AttrValue) -> Vec<BookmarkData> {
+    let mut params = vec![("count", BOOKMARKS_COUNT.to_string())];
+    params.append(&mut criteria_query_params(&Params {
+        tags: Some(vec![name.clone()]),
+        descendants: Some(true),
+        ..Params::default()
+    }));
+
+    match GetBookmarksResult::from(Request::get(URL_BOOKMARKS).query(params).send().await).await {
+        Some(GetBookmarksResult::Success(response)) => response
+            .bookmarks
+            .into_iter()
+            .map(BookmarkData::from)
+            .collect::<Vec<BookmarkData>>(),
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
@@ -1,23 +1,45 @@
 use super::super::super::super::data::Tags;
+use super::super::related_tags::RelatedTags;
+use super::super::tag_tree::TagTree;
+use crate::components::tag_input::TagInput;
+use crate::features::bookmarks::bookmarks_provider::Params;
+use crate::features::bookmarks::bookmarks_query::QueryParams;
 use crate::Route;
-use serde::Serialize;
 use std::rc::Rc;
+use urlencoding::encode;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct Props {
     tags: Rc<Tags>,
-}
-
-#[derive(Serialize)]
-pub struct Tag {
-    pub tags: String,
+    manageable: bool,
 }
 
 #[function_component(TagCloud)]
 pub fn tag_cloud(props: &Props) -> Html {
     let navigator = use_navigator().unwrap();
+    let tree = use_state(|| false);
+    let refined = use_state(|| Rc::new(Vec::<AttrValue>::new()));
+
+    let on_refine = {
+        let refined = refined.clone();
+        Callback::from(move |tags: Vec<AttrValue>| refined.set(Rc::new(tags)))
+    };
+
+    let on_select_related = {
+        let refined = refined.clone();
+        Callback::from(move |tag: AttrValue| {
+            let mut tags = (**refined).clone();
+            tags.push(tag);
+            refined.set(Rc::new(tags));
+        })
+    };
+
+    let refined_query = QueryParams::from(Params {
+        tags: Some((**refined).clone()),
+        ..Params::default()
+    });
 
     let max_count = props.tags.iter().map(|t| t.count).max().unwrap_or_default() as f32;
     let min_count = props.tags.iter().map(|t| t.count).min().unwrap_or_default() as f32;
@@ -27,46 +49,122 @@ pub fn tag_cloud(props: &Props) -> Html {
     html! {
         <div class="centered-box">
             <h1 class="centered-box__title">{"Tag cloud"}</h1>
-            <ul class="tag-cloud">
-                {
-                    props.tags.iter().map(|t| {
-                        let size = (1f32 + (t.count as f32 - min_count) * font_step).round() as i32;
-                        html! {
-                            <li>
-                                <a
-                                    href={format!("{}?tags={}",Route::Bookmarks.to_path(), &t.name)}
-                                    // href={format!("/bookmarks/~search?tags={}", &t.name)}
-                                    data-weight={size.to_string()}
-                                    onclick={
-                                        let navigator = navigator.clone();
-                                        let tag = Tag { tags: t.name.to_string() };
-                                        Callback::from(move |e: MouseEvent| {
-                                            e.prevent_default();
-                                            let _ = navigator.push_with_query(
-                                                // &Route::BookmarksSearch,
-                                                &Route::Bookmarks,
-                                                &tag
-                                            );
-                                        })
-                                    }
-                                >
-                                    {t.name.clone()}
-                                    <span>{t.count}</span>
-                                </a>
-                            </li>
+            <div class="centered-box__buttons">
+                <a
+                    href="#tree"
+                    onclick={
+                        let tree = tree.clone();
+                        Callback::from(move |e: MouseEvent| {
+                            e.prevent_default();
+                            tree.set(!*tree);
+                        })
+                    }
+                >
+                    { if *tree { "show cloud" } else { "show tree" } }
+                </a>
+                { if props.manageable {
+                    html! {
+                        <>
+                            {" | "}
+                            <a
+                                href={Route::ManageTags.to_path()}
+                                onclick={
+                                    let navigator = navigator.clone();
+                                    Callback::from(move |e: MouseEvent| {
+                                        e.prevent_default();
+                                        navigator.push(&Route::ManageTags);
+                                    })
+                                }
+                            >
+                                {"manage tags"}
+                            </a>
+                        </>
+                    }
+                } else {
+                    html! {}
+                } }
+            </div>
+            <div class="tag-cloud__refine">
+                <TagInput
+                    placeholder="refine by tags"
+                    tags={(**refined).clone()}
+                    available_tags={Rc::new(props.tags.iter().map(|t| t.name.clone()).collect::<Vec<AttrValue>>())}
+                    onupdate={on_refine}
+                />
+                { if refined.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <>
+                            <RelatedTags tags={(*refined).clone()} on_select={on_select_related} />
+                            <a
+                                href={format!(
+                                    "{}?{}",
+                                    Route::Bookmarks.to_path(),
+                                    serde_urlencoded::to_string(&refined_query).unwrap_or_default()
+                                )}
+                                onclick={
+                                    let navigator = navigator.clone();
+                                    Callback::from(move |e: MouseEvent| {
+                                        e.prevent_default();
+                                        let _ = navigator.push_with_query(&Route::Bookmarks, &refined_query);
+                                    })
+                                }
+                            >
+                                {"show bookmarks"}
+                            </a>
+                        </>
+                    }
+                } }
+            </div>
+            { if *tree {
+                html! { <TagTree /> }
+            } else {
+                html! {
+                    <ul class="tag-cloud">
+                        {
+                            props.tags.iter().map(|t| {
+                                let size = (1f32 + (t.count as f32 - min_count) * font_step).round() as i32;
+                                html! {
+                                    <li>
+                                        <a
+                                            href={Route::Tag { name: encode(&t.name).into_owned() }.to_path()}
+                                            data-weight={size.to_string()}
+                                            style={t.metadata.color.as_ref().map(|c| format!("color: {}", c))}
+                                            title={t.metadata.description.clone()}
+                                            onclick={
+                                                let navigator = navigator.clone();
+                                                let name = encode(&t.name).into_owned();
+                                                Callback::from(move |e: MouseEvent| {
+                                                    e.prevent_default();
+                                                    navigator.push(&Route::Tag { name: name.clone() });
+                                                })
+                                            }
+                                        >
+                                            {t.name.clone()}
+                                            <span>{t.count}</span>
+                                        </a>
+                                    </li>
+                                }
+                            }).collect::<Html>()
                         }
-                    }).collect::<Html>()
+                    </ul>
                 }
-            </ul>
+            } }
         </div>
     }
 }
 
+#[derive(Properties, PartialEq, Clone)]
+pub struct HOCProps {
+    pub manageable: bool,
+}
+
 #[function_component(TagCloudHOC)]
-pub fn tag_cloud_hoc() -> Html {
+pub fn tag_cloud_hoc(props: &HOCProps) -> Html {
     let tags = use_context::<Rc<Tags>>().expect("no ctx found");
 
     html! {
-        <TagCloud {tags} />
+        <TagCloud {tags} manageable={props.manageable} />
     }
 }
@@ -0,0 +1,95 @@
+use super::super::super::data::{Tag, Tags};
+use gloo_net::http::Request;
+use rest_api::tags::{GetTagsResult, URL_RELATED_TAGS};
+use std::rc::Rc;
+use urlencoding::encode;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct Props {
+    pub tags: Rc<Vec<AttrValue>>,
+    #[prop_or_default]
+    pub descendants: bool,
+    pub on_select: Callback<AttrValue>,
+}
+
+/// Lists, as refine-further chips, the tags most often found on the bookmarks having all the
+/// given tags.
+#[function_component(RelatedTags)]
+pub fn related_tags(props: &Props) -> Html {
+    let related = use_state(|| Rc::new(Tags::new()));
+
+    {
+        let related = related.clone();
+        use_effect_with_deps(
+            move |(tags, descendants): &(Rc<Vec<AttrValue>>, bool)| {
+                let tags = tags.clone();
+                let descendants = *descendants;
+                spawn_local(async move {
+                    related.set(Rc::new(fetch_related(&tags, descendants).await));
+                });
+                || {}
+            },
+            (props.tags.clone(), props.descendants),
+        );
+    }
+
+    if related.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <ul class="related-tags">
+            <li class="related-tags__label">{"related:"}</li>
+            {
+                related.iter().map(|t| html! {
+                    <li>
+                        <a
+                            href="#"
+                            style={t.metadata.color.as_ref().map(|c| format!("color: {}", c))}
+                            onclick={
+                                let on_select = props.on_select.clone();
+                                let name = t.name.clone();
+                                Callback::from(move |e: MouseEvent| {
+                                    e.prevent_default();
+                                    on_select.emit(name.clone());
+                                })
+                            }
+                        >
+                            {t.name.clone()}
+                        </a>
+                        <span class="related-tags__count">{t.count}</span>
+                    </li>
+                }).collect::<Html>()
+            }
+        </ul>
+    }
+}
+
+async fn fetch_related(tags: &[AttrValue], descendants: bool) -> Tags {
+    if tags.is_empty() {
+        return vec![];
+    }
+
+    // todo review query param serialization and struct shared with API
+    let tags = tags
+        .iter()
+        .map(|tag| encode(tag.as_str()).to_string())
+        .collect::<Vec<String>>()
+        .join("+");
+    match GetTagsResult::from(
+        Request::get(URL_RELATED_TAGS)
+            .query([("tags", tags), ("descendants", descendants.to_string())])
+            .send()
+            .await,
+    )
+    .await
+    {
+        Some(GetTagsResult::Success(tags)) => tags.into_iter().map(Tag::from).collect::<Vec<Tag>>(),
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use super::super::super::data::{Tag, Tags};
+use crate::features::bookmarks::bookmarks_provider::Params;
+use crate::features::bookmarks::bookmarks_query::QueryParams;
+use crate::Route;
+use common::TAG_SEPARATOR;
+use gloo_net::http::Request;
+use rest_api::tags::{GetTagsResult, URL_TAGS};
+use std::collections::HashSet;
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+use yew_hooks::use_effect_once;
+use yew_router::prelude::*;
+
+#[derive(Clone, PartialEq, Default)]
+struct State {
+    tags: Option<Rc<Tags>>,
+    expanded: HashSet<AttrValue>,
+}
+
+/// Renders the tags as a collapsible tree, built from their hierarchical names.
+#[function_component(TagTree)]
+pub fn tag_tree() -> Html {
+    let navigator = use_navigator().unwrap();
+    let state = use_state(State::default);
+
+    {
+        let state = state.clone();
+        use_effect_once(move || {
+            spawn_local(async move {
+                let mut new_state = (*state).clone();
+                new_state.tags = Some(Rc::new(fetch_tree().await));
+                state.set(new_state);
+            });
+            || {}
+        });
+    }
+
+    let toggle = {
+        let state = state.clone();
+        Callback::from(move |name: AttrValue| {
+            let mut new_state = (*state).clone();
+            if !new_state.expanded.remove(&name) {
+                new_state.expanded.insert(name);
+            }
+            state.set(new_state);
+        })
+    };
+
+    fn view(
+        tags: &[Tag],
+        expanded: &HashSet<AttrValue>,
+        toggle: &Callback<AttrValue>,
+        navigator: &Navigator,
+    ) -> Html {
+        html! {
+            <ul class="tag-tree">
+                {
+                    tags.iter().map(|tag| {
+                        let query = QueryParams::from(Params {
+                            tags: Some(vec![tag.name.clone()]),
+                            descendants: Some(true),
+                            ..Params::default()
+                        });
+                        let is_expanded = expanded.contains(&tag.name);
+                        html! {
+                            <li>
+                                { if tag.children.is_empty() {
+                                    html! { <span class="tag-tree__toggle"></span> }
+                                } else {
+                                    html! {
+                                        <span
+                                            class="material-icons-outlined md-16 tag-tree__toggle"
+                                            onclick={
+                                                let toggle = toggle.clone();
+                                                let name = tag.name.clone();
+                                                move |_: MouseEvent| toggle.emit(name.clone())
+                                            }
+                                        >
+                                            { if is_expanded { "expand_more" } else { "chevron_right" } }
+                                        </span>
+                                    }
+                                } }
+                                <a
+                                    href={format!(
+                                        "{}?{}",
+                                        Route::Bookmarks.to_path(),
+                                        serde_urlencoded::to_string(&query).unwrap_or_default()
+                                    )}
+                                    onclick={
+                                        let navigator = navigator.clone();
+                                        Callback::from(move |e: MouseEvent| {
+                                            e.prevent_default();
+                                            let _ = navigator.push_with_query(&Route::Bookmarks, &query);
+                                        })
+                                    }
+                                >
+                                    { tag.name.rsplit(TAG_SEPARATOR).next().unwrap_or_default().to_string() }
+                                </a>
+                                <span class="tag-tree__count">{tag.total_count}</span>
+                                { if is_expanded {
+                                    view(&tag.children, expanded, toggle, navigator)
+                                } else {
+                                    html! {}
+                                } }
+                            </li>
+                        }
+                    }).collect::<Html>()
+                }
+            </ul>
+        }
+    }
+
+    match &state.tags {
+        Some(tags) => view(tags, &state.expanded, &toggle, &navigator),
+        None => html! {},
+    }
+}
+
+async fn fetch_tree() -> Tags {
+    match GetTagsResult::from(
+        Request::get(URL_TAGS)
+            .query([("order", "name"), ("tree", "true")])
+            .send()
+            .await,
+    )
+    .await
+    {
+        Some(GetTagsResult::Success(tags)) => tags.into_iter().map(Tag::from).collect::<Vec<Tag>>(),
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
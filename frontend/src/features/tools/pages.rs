@@ -1,2 +1,3 @@
 pub mod import_shaarli_api;
+pub mod rules;
 pub mod tools;
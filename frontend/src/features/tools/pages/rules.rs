@@ -0,0 +1,346 @@
+use super::super::super::super::data::Rule;
+use gloo_net::http::Request;
+use rest_api::rules::{
+    ApplyRulesResult, DeleteRuleResult, GetRulesResult, SaveRuleRequest, SaveRuleResult, URL_RULE,
+    URL_RULES, URL_RULES_APPLY,
+};
+use std::rc::Rc;
+use web_sys::HtmlInputElement;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Default)]
+struct State {
+    rules: Rc<Vec<Rule>>,
+    /// The rule being edited in the form; a new one is created when `None`.
+    editing: Option<Rule>,
+    message: Option<AttrValue>,
+    error: Option<AttrValue>,
+    in_progress: bool,
+    generation: u32,
+}
+
+/// Lists the rules tagging, making private or pinning the bookmarks when they are saved.
+#[function_component(ToolRules)]
+pub fn tool_rules() -> Html {
+    let state = use_state(State::default);
+    let url_input_ref = use_node_ref();
+    let url_regex_input_ref = use_node_ref();
+    let title_input_ref = use_node_ref();
+    let tags_input_ref = use_node_ref();
+    let private_input_ref = use_node_ref();
+    let pin_input_ref = use_node_ref();
+
+    {
+        let generation = state.generation;
+        let state = state.clone();
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    let mut new_state = (*state).clone();
+                    new_state.rules = Rc::new(fetch_rules().await);
+                    state.set(new_state);
+                });
+                || {}
+            },
+            generation,
+        );
+    }
+
+    let onclick_edit = |rule: &Rule| {
+        let state = state.clone();
+        let rule = rule.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let mut new_state = (*state).clone();
+            new_state.editing = Some(rule.clone());
+            new_state.error = None;
+            state.set(new_state);
+        })
+    };
+
+    let onclick_cancel = {
+        let state = state.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let mut new_state = (*state).clone();
+            new_state.editing = None;
+            new_state.error = None;
+            state.set(new_state);
+        })
+    };
+
+    let onclick_delete = |rule: &Rule| {
+        let state = state.clone();
+        let id = rule.id;
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            let state = state.clone();
+            spawn_local(async move {
+                let mut new_state = (*state).clone();
+                match DeleteRuleResult::from(
+                    Request::delete(&URL_RULE.replace(":id", &id.to_string()))
+                        .send()
+                        .await,
+                )
+                .await
+                {
+                    Some(DeleteRuleResult::Success) | Some(DeleteRuleResult::NotFound) => {
+                        if new_state.editing.as_ref().map(|r| r.id) == Some(id) {
+                            new_state.editing = None;
+                        }
+                        new_state.error = None;
+                        new_state.generation += 1;
+                    }
+                    _ => new_state.error = Some(AttrValue::from("An error has occurred")),
+                }
+                state.set(new_state);
+            });
+        })
+    };
+
+    let onsubmit = {
+        let state = state.clone();
+        let url_input_ref = url_input_ref.clone();
+        let url_regex_input_ref = url_regex_input_ref.clone();
+        let title_input_ref = title_input_ref.clone();
+        let tags_input_ref = tags_input_ref.clone();
+        let private_input_ref = private_input_ref.clone();
+        let pin_input_ref = pin_input_ref.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let value = |node_ref: &NodeRef| {
+                Some(node_ref.cast::<HtmlInputElement>().unwrap().value())
+                    .filter(|v| !v.trim().is_empty())
+            };
+            let checked =
+                |node_ref: &NodeRef| node_ref.cast::<HtmlInputElement>().unwrap().checked();
+            let request = SaveRuleRequest {
+                url_pattern: value(&url_input_ref),
+                url_regex: checked(&url_regex_input_ref),
+                title_pattern: value(&title_input_ref),
+                tags: value(&tags_input_ref)
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(|t| t.to_string())
+                    .collect(),
+                private: checked(&private_input_ref),
+                pin: checked(&pin_input_ref),
+            };
+            let request = match &state.editing {
+                None => Request::post(URL_RULES),
+                Some(rule) => Request::put(&URL_RULE.replace(":id", &rule.id.to_string())),
+            }
+            .json(&request)
+            .expect("could not set json");
+            let text_inputs = [&url_input_ref, &title_input_ref, &tags_input_ref]
+                .map(|node_ref| node_ref.cast::<HtmlInputElement>().unwrap());
+            let checkboxes = [&url_regex_input_ref, &private_input_ref, &pin_input_ref]
+                .map(|node_ref| node_ref.cast::<HtmlInputElement>().unwrap());
+            let state = state.clone();
+            spawn_local(async move {
+                let mut new_state = (*state).clone();
+                match SaveRuleResult::from(request.send().await).await {
+                    Some(SaveRuleResult::Success(_)) => {
+                        // the form is empty again, ready for a new rule
+                        text_inputs.iter().for_each(|input| input.set_value(""));
+                        checkboxes.iter().for_each(|input| input.set_checked(false));
+                        new_state.editing = None;
+                        new_state.error = None;
+                        new_state.generation += 1;
+                    }
+                    Some(SaveRuleResult::InvalidParameter(message)) => {
+                        new_state.error = Some(AttrValue::from(message))
+                    }
+                    Some(SaveRuleResult::NotFound) => {
+                        new_state.editing = None;
+                        new_state.error = Some(AttrValue::from("Rule not found"));
+                        new_state.generation += 1;
+                    }
+                    _ => new_state.error = Some(AttrValue::from("An error has occurred")),
+                }
+                state.set(new_state);
+            });
+        })
+    };
+
+    let onclick_apply = {
+        let state = state.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            if state.in_progress {
+                return;
+            }
+            {
+                let mut new_state = (*state).clone();
+                new_state.in_progress = true;
+                new_state.message = None;
+                state.set(new_state);
+            }
+            let state = state.clone();
+            spawn_local(async move {
+                let mut new_state = (*state).clone();
+                new_state.in_progress = false;
+                match ApplyRulesResult::from(Request::post(URL_RULES_APPLY).send().await).await {
+                    Some(ApplyRulesResult::Success(response)) => {
+                        new_state.error = None;
+                        new_state.message = Some(AttrValue::from(format!(
+                            "The rules matched {} bookmark(s)",
+                            response.count
+                        )));
+                    }
+                    _ => new_state.error = Some(AttrValue::from("An error has occurred")),
+                }
+                state.set(new_state);
+            });
+        })
+    };
+
+    let editing = state.editing.clone();
+
+    html! {
+        <div class="centered-box">
+            <h1 class="centered-box__title">{"Rules"}</h1>
+            <p>{"Rules add tags to, make private or pin the bookmarks matching them when they are created, edited or imported."}</p>
+            { state.error.as_ref().map(|e| html! {
+                <div class="centered-box__error">{e}</div>
+            }) }
+            { state.message.as_ref().map(|m| html! {
+                <div class="centered-box__ok">{m}</div>
+            }) }
+            <ul class="rules">
+                {
+                    state.rules.iter().map(|rule| html! {
+                        <li>
+                            { rule.url_pattern.as_ref().map(|p| html! {
+                                <span class="rules__condition">
+                                    { if rule.url_regex { "URL matches " } else { "URL is " } }
+                                    <code>{p}</code>
+                                </span>
+                            }) }
+                            { rule.title_pattern.as_ref().map(|p| html! {
+                                <span class="rules__condition">
+                                    {"title matches "}
+                                    <code>{p}</code>
+                                </span>
+                            }) }
+                            {" \u{2192} "}
+                            { rule.tags.iter().map(|t| html! {
+                                <span class="rules__action">{"+"}{t}</span>
+                            }).collect::<Html>() }
+                            { rule.private.then(|| html! {
+                                <span class="rules__action">{"private"}</span>
+                            }) }
+                            { rule.pin.then(|| html! {
+                                <span class="rules__action">{"pinned"}</span>
+                            }) }
+                            {"\u{00a0}"}
+                            <a
+                                class="material-icons-outlined md-16"
+                                title="edit"
+                                href="#edit"
+                                onclick={onclick_edit(rule)}
+                            >
+                                {"edit"}
+                            </a>
+                            {"\u{00a0}"}
+                            <a
+                                class="material-icons-outlined md-16 red"
+                                title="delete"
+                                href="#delete"
+                                onclick={onclick_delete(rule)}
+                            >
+                                {"delete"}
+                            </a>
+                        </li>
+                    }).collect::<Html>()
+                }
+            </ul>
+            <h2>{ if editing.is_some() { "Edit rule" } else { "Add rule" } }</h2>
+            <form {onsubmit}>
+                <p>
+                    <input
+                        ref={url_input_ref}
+                        type="text"
+                        placeholder="URL, e.g. *youtube.com/*"
+                        value={editing.as_ref().and_then(|r| r.url_pattern.clone())}
+                    />
+                </p>
+                <p>
+                    <input
+                        ref={url_regex_input_ref}
+                        type="checkbox"
+                        id="url_regex"
+                        checked={editing.as_ref().map(|r| r.url_regex).unwrap_or_default()}
+                    />
+                    <label for="url_regex">{"The URL is a regular expression"}</label>
+                </p>
+                <p>
+                    <input
+                        ref={title_input_ref}
+                        type="text"
+                        placeholder="title regular expression, e.g. RFC \\d+"
+                        value={editing.as_ref().and_then(|r| r.title_pattern.clone())}
+                    />
+                </p>
+                <p>
+                    <input
+                        ref={tags_input_ref}
+                        type="text"
+                        placeholder="tags to add, separated by spaces"
+                        value={editing.as_ref().map(|r| r.tags.join(" "))}
+                    />
+                </p>
+                <p>
+                    <input
+                        ref={private_input_ref}
+                        type="checkbox"
+                        id="rule_private"
+                        checked={editing.as_ref().map(|r| r.private).unwrap_or_default()}
+                    />
+                    <label for="rule_private">{"Make private"}</label>
+                    {" "}
+                    <input
+                        ref={pin_input_ref}
+                        type="checkbox"
+                        id="rule_pin"
+                        checked={editing.as_ref().map(|r| r.pin).unwrap_or_default()}
+                    />
+                    <label for="rule_pin">{"Pin"}</label>
+                </p>
+                <div class="centered-box__buttons">
+                    <p>
+                        if editing.is_some() {
+                            <button type="button" class="button--safe" onclick={onclick_cancel}>{"Cancel"}</button>
+                            {" "}
+                        }
+                        <button type="submit" class="button--action">{"Save"}</button>
+                    </p>
+                </div>
+            </form>
+            <div class="centered-box__buttons">
+                <p>
+                    <button
+                        type="button"
+                        class={if state.in_progress { "button--disabled" } else { "button--action" }}
+                        onclick={onclick_apply}
+                    >
+                        {"Re-apply rules to existing bookmarks"}
+                    </button>
+                </p>
+            </div>
+        </div>
+    }
+}
+
+async fn fetch_rules() -> Vec<Rule> {
+    match GetRulesResult::from(Request::get(URL_RULES).send().await).await {
+        Some(GetRulesResult::Success(rules)) => {
+            rules.into_iter().map(Rule::from).collect::<Vec<Rule>>()
+        }
+        _ => {
+            // todo handle errors
+            vec![]
+        }
+    }
+}
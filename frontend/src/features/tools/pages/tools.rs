@@ -25,6 +25,20 @@ pub fn tools() -> Html {
                         {"Import from Shaarli's API"}
                     </a>
                 </li>
+                <li>
+                    <a
+                        href={Route::ToolRules.to_path()}
+                        onclick={
+                            let navigator = navigator.clone();
+                            Callback::from(move |e: MouseEvent| {
+                                e.prevent_default();
+                                navigator.push(&Route::ToolRules)
+                            })
+                        }
+                    >
+                        {"Tagging and privacy rules"}
+                    </a>
+                </li>
             </ul>
         </div>
     }
@@ -5,6 +5,7 @@ mod features;
 mod menu;
 
 use crate::components::protected::Protected;
+use crate::components::saved_searches_provider::SavedSearchesProvider;
 use crate::components::tags_provider::{Order, TagsProvider};
 use crate::features::authentication::pages::login::Login;
 use crate::features::authentication::pages::logout::Logout;
@@ -19,18 +20,27 @@ use crate::features::bookmarks::pages::create_bookmark;
 use crate::features::bookmarks::pages::create_bookmark::CreateBookmarkHOC;
 use crate::features::bookmarks::pages::delete_bookmark::DeleteBookmarkHOC;
 use crate::features::bookmarks::pages::edit_bookmark::EditBookmarkHOC;
+use crate::features::bookmarks::pages::timeline::Timeline;
 use crate::features::bookmarks::pages::view_bookmark::ViewBookmarkHOC;
+use crate::features::domains::pages::domains::Domains;
 use crate::features::profile::pages::profile::Profile;
 use crate::features::profile::pages::validate_email::ValidateEmail;
+use crate::features::saved_searches::default_search::DefaultSearch;
+use crate::features::saved_searches::pages::saved_searches::SavedSearches;
 use crate::features::signup::pages::signup_form::SignupForm;
 use crate::features::signup::pages::signup_success::SignupSuccess;
+use crate::features::tag_cloud::pages::delete_tag::DeleteTag;
+use crate::features::tag_cloud::pages::manage_tags::ManageTags;
+use crate::features::tag_cloud::pages::tag::TagPage;
 use crate::features::tag_cloud::pages::tag_cloud::TagCloudHOC;
 use crate::features::tools::pages::import_shaarli_api::ToolImportShaarliApi;
+use crate::features::tools::pages::rules::ToolRules;
 use crate::features::tools::pages::tools::Tools;
 use crate::menu::Menu;
 use gloo_net::http::Request;
 use rest_api::application::{GetApplicationResult, URL_APPLICATION};
 use rest_api::sessions::{CreateSessionResult, URL_SESSIONS_CURRENT};
+use urlencoding::decode;
 use yew::platform::spawn_local;
 use yew::prelude::*;
 use yew_hooks::prelude::*;
@@ -51,6 +61,9 @@ pub enum Route {
     #[at("/bookmarks/~add")]
     AddBookmark,
 
+    #[at("/bookmarks/~timeline")]
+    Timeline,
+
     #[at("/bookmarks/:id")]
     ViewBookmark { id: i32 },
 
@@ -62,6 +75,18 @@ pub enum Route {
 
     #[at("/tags")]
     TagCloud,
+    #[at("/tags/~manage")]
+    ManageTags,
+    #[at("/tags/:name")]
+    Tag { name: String },
+    #[at("/tags/:name/~delete")]
+    DeleteTag { name: String },
+
+    #[at("/domains")]
+    Domains,
+
+    #[at("/saved-searches")]
+    SavedSearches,
 
     #[at("/tools")]
     Tools,
@@ -69,6 +94,9 @@ pub enum Route {
     #[at("/tools/shaarli-api-import")]
     ToolImportShaarliApi,
 
+    #[at("/tools/rules")]
+    ToolRules,
+
     #[at("/signup")]
     SignupForm,
 
@@ -116,6 +144,12 @@ impl Route {
                     .map(QueryParams::Bookmarks)
                     .unwrap_or_default()
             }
+            Route::Timeline => {
+                serde_urlencoded::from_str::<bookmarks_query::QueryParams>(query_string)
+                    .ok()
+                    .map(QueryParams::Bookmarks)
+                    .unwrap_or_default()
+            }
             Route::AddBookmark => {
                 serde_urlencoded::from_str::<create_bookmark::QueryParams>(query_string)
                     .ok()
@@ -126,8 +160,14 @@ impl Route {
             Route::DeleteBookmark { .. } => QueryParams::None,
             Route::EditBookmark { .. } => QueryParams::None,
             Route::TagCloud => QueryParams::None,
+            Route::ManageTags => QueryParams::None,
+            Route::Tag { .. } => QueryParams::None,
+            Route::DeleteTag { .. } => QueryParams::None,
+            Route::Domains => QueryParams::None,
+            Route::SavedSearches => QueryParams::None,
             Route::Tools => QueryParams::None,
             Route::ToolImportShaarliApi => QueryParams::None,
+            Route::ToolRules => QueryParams::None,
             Route::SignupForm => QueryParams::None,
             Route::SignupSuccess => QueryParams::None,
             Route::Login => QueryParams::None,
@@ -141,6 +181,25 @@ impl Route {
     }
 }
 
+/// Decodes a percent-encoded route parameter, yew-router leaving them as they are in the path.
+fn decode_param(param: &str) -> String {
+    decode(param)
+        .map(|param| param.into_owned())
+        .unwrap_or_else(|_| param.to_string())
+}
+
+fn bookmarks() -> Html {
+    html! {
+        <BookmarksQuery>
+            <BookmarksProvider>
+                <TagsProvider>
+                    <BookmarksHOC />
+                </TagsProvider>
+            </BookmarksProvider>
+        </BookmarksQuery>
+    }
+}
+
 #[derive(Clone, Default)]
 struct State {
     username: Option<AttrValue>,
@@ -200,137 +259,183 @@ fn app() -> Html {
         .then_some(html! {
             <>
                 <BrowserRouter>
-                    <Menu username={state.username.clone()} />
-                    <a
-                        class="github-fork-ribbon right-bottom fixed"
-                        href={env!("CARGO_PKG_REPOSITORY")}
-                        data-ribbon="Fork me on GitHub"
-                        title="Fork me on GitHub"
-                    >
-                        {"Fork me on GitHub"}
-                    </a>
-                    <div class="content">
-                        <Switch<Route> render={
-                            let logged_in = state.username.is_some();
-                            move |route| match route {
-                                Route::Index | Route::Bookmarks => {
-                                    html! {
-                                        <BookmarksQuery>
-                                            <BookmarksProvider>
+                    <SavedSearchesProvider logged_in={state.username.is_some()}>
+                        <Menu username={state.username.clone()} />
+                        <a
+                            class="github-fork-ribbon right-bottom fixed"
+                            href={env!("CARGO_PKG_REPOSITORY")}
+                            data-ribbon="Fork me on GitHub"
+                            title="Fork me on GitHub"
+                        >
+                            {"Fork me on GitHub"}
+                        </a>
+                        <div class="content">
+                            <Switch<Route> render={
+                                let logged_in = state.username.is_some();
+                                move |route| match route {
+                                    Route::Index => {
+                                        html! {
+                                            <DefaultSearch>
+                                                { bookmarks() }
+                                            </DefaultSearch>
+                                        }
+                                    }
+                                    Route::Bookmarks => bookmarks(),
+                                    Route::AddBookmark => {
+                                        html! {
+                                            <Protected {logged_in}>
                                                 <TagsProvider>
-                                                    <BookmarksHOC />
+                                                    <CreateBookmarkHOC />
                                                 </TagsProvider>
-                                            </BookmarksProvider>
-                                        </BookmarksQuery>
+                                            </Protected>
+                                        }
                                     }
-                                }
-                                Route::AddBookmark => {
-                                    html! {
-                                        <Protected {logged_in}>
-                                            <TagsProvider>
-                                                <CreateBookmarkHOC />
-                                            </TagsProvider>
-                                        </Protected>
-                                    }
-                                }
-                                Route::ViewBookmark { id } => {
-                                    html! {
-                                        <BookmarkProvider {id}>
-                                            <ViewBookmarkHOC />
-                                        </BookmarkProvider>
+                                    Route::Timeline => {
+                                        html! {
+                                            <Timeline />
+                                        }
                                     }
-                                }
-                                Route::DeleteBookmark { id } => {
-                                    html! {
-                                        <Protected {logged_in}>
+                                    Route::ViewBookmark { id } => {
+                                        html! {
                                             <BookmarkProvider {id}>
-                                                <DeleteBookmarkHOC />
+                                                <ViewBookmarkHOC />
                                             </BookmarkProvider>
-                                        </Protected>
+                                        }
                                     }
-                                }
-                                Route::EditBookmark { id } => {
-                                    html! {
-                                        <Protected {logged_in}>
-                                            <BookmarkProvider {id}>
-                                                <TagsProvider>
-                                                    <EditBookmarkHOC />
-                                                </TagsProvider>
-                                            </BookmarkProvider>
-                                        </Protected>
+                                    Route::DeleteBookmark { id } => {
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <BookmarkProvider {id}>
+                                                    <DeleteBookmarkHOC />
+                                                </BookmarkProvider>
+                                            </Protected>
+                                        }
                                     }
-                                }
-                                Route::TagCloud => {
-                                    html! {
-                                        <TagsProvider order={Order::Name}>
-                                            <TagCloudHOC />
-                                        </TagsProvider>
+                                    Route::EditBookmark { id } => {
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <BookmarkProvider {id}>
+                                                    <TagsProvider>
+                                                        <EditBookmarkHOC />
+                                                    </TagsProvider>
+                                                </BookmarkProvider>
+                                            </Protected>
+                                        }
                                     }
-                                }
-                                Route::Tools => {
-                                    html! {
-                                        <Protected {logged_in}>
-                                            <Tools />
-                                        </Protected>
+                                    Route::TagCloud => {
+                                        html! {
+                                            <TagsProvider order={Order::Name}>
+                                                <TagCloudHOC manageable={logged_in} />
+                                            </TagsProvider>
+                                        }
                                     }
-                                },
-                                Route::ToolImportShaarliApi => {
-                                    html! {
-                                        <Protected {logged_in}>
-                                            <ToolImportShaarliApi />
-                                        </Protected>
+                                    Route::ManageTags => {
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <ManageTags />
+                                            </Protected>
+                                        }
                                     }
-                                },
-                                Route::SignupForm => {
-                                    html! {
-                                        <SignupForm />
+                                    Route::Tag { name } => {
+                                        let name = decode_param(&name);
+                                        html! {
+                                            <TagsProvider order={Order::Name}>
+                                                <TagPage {name} {logged_in} />
+                                            </TagsProvider>
+                                        }
                                     }
-                                }
-                                Route::SignupSuccess => {
-                                    html! {
-                                        <SignupSuccess />
+                                    Route::DeleteTag { name } => {
+                                        let name = decode_param(&name);
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <DeleteTag {name} />
+                                            </Protected>
+                                        }
                                     }
-                                }
-                                Route::Login => {
-                                    html! {
-                                        <Login {logged_in} onlogin={onlogin.clone()} />
+                                    Route::Domains => {
+                                        html! {
+                                            <Domains />
+                                        }
                                     }
-                                }
-                                Route::RecoverPasswordStart => {
-                                    html! {
-                                        <RecoverPasswordStart />
+                                    Route::SavedSearches => {
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <SavedSearches />
+                                            </Protected>
+                                        }
+                                    },
+                                    Route::Tools => {
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <Tools />
+                                            </Protected>
+                                        }
+                                    },
+                                    Route::ToolImportShaarliApi => {
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <ToolImportShaarliApi />
+                                            </Protected>
+                                        }
+                                    },
+                                    Route::ToolRules => {
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <ToolRules />
+                                            </Protected>
+                                        }
+                                    },
+                                    Route::SignupForm => {
+                                        html! {
+                                            <SignupForm />
+                                        }
                                     }
-                                }
-                                Route::RecoverPasswordForm { id } => {
-                                    html! {
-                                        <RecoverPasswordFormHOC {id} />
+                                    Route::SignupSuccess => {
+                                        html! {
+                                            <SignupSuccess />
+                                        }
                                     }
-                                }
-                                Route::ValidateEmail { uuid } => {
-                                    html! {
-                                        <ValidateEmail {uuid} />
+                                    Route::Login => {
+                                        html! {
+                                            <Login {logged_in} onlogin={onlogin.clone()} />
+                                        }
                                     }
-                                }
-                                Route::Profile => {
-                                    html! {
-                                        <Protected {logged_in}>
-                                            <Profile />
-                                        </Protected>
+                                    Route::RecoverPasswordStart => {
+                                        html! {
+                                            <RecoverPasswordStart />
+                                        }
                                     }
-                                }
-                                Route::Logout => {
-                                    html! {
-                                        <Logout onlogout={onlogout.clone()} />
+                                    Route::RecoverPasswordForm { id } => {
+                                        html! {
+                                            <RecoverPasswordFormHOC {id} />
+                                        }
                                     }
-                                }
-                                Route::NotFound => {
-                                    html! {
-                                        <h1>{"404 Not Found"}</h1>
+                                    Route::ValidateEmail { uuid } => {
+                                        html! {
+                                            <ValidateEmail {uuid} />
+                                        }
+                                    }
+                                    Route::Profile => {
+                                        html! {
+                                            <Protected {logged_in}>
+                                                <Profile />
+                                            </Protected>
+                                        }
+                                    }
+                                    Route::Logout => {
+                                        html! {
+                                            <Logout onlogout={onlogout.clone()} />
+                                        }
+                                    }
+                                    Route::NotFound => {
+                                        html! {
+                                            <h1>{"404 Not Found"}</h1>
+                                        }
                                     }
                                 }
-                            }
-                        } />
-                    </div>
+                            } />
+                        </div>
+                    </SavedSearchesProvider>
                 </BrowserRouter>
                 <div class="footer">
                     <a
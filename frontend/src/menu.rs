@@ -1,4 +1,6 @@
+use crate::components::saved_searches_provider::SavedSearches;
 use crate::features::bookmarks::bookmarks_query::QueryParams;
+use crate::features::saved_searches::query_params;
 use crate::Route;
 use yew::prelude::*;
 use yew_router::hooks::use_navigator;
@@ -13,6 +15,7 @@ pub struct Props {
 pub fn menu(props: &Props) -> Html {
     let navigator = use_navigator().unwrap();
     let menu_expanded = use_state(|| false);
+    let saved_searches = use_context::<SavedSearches>().expect("no ctx found");
 
     html! {
         <div class="menu">
@@ -51,6 +54,28 @@ pub fn menu(props: &Props) -> Html {
                         }
                     }} class="menu__item" href={Route::TagCloud.to_path()}>{"Tag cloud"}</a>
                 </li>
+                <li>
+                    <a onclick={{
+                        let navigator = navigator.clone();
+                        let menu_expanded = menu_expanded.clone();
+                        move |e:MouseEvent| {
+                            e.prevent_default();
+                            menu_expanded.set(false);
+                            navigator.push(&Route::Timeline);
+                        }
+                    }} class="menu__item" href={Route::Timeline.to_path()}>{"Timeline"}</a>
+                </li>
+                <li>
+                    <a onclick={{
+                        let navigator = navigator.clone();
+                        let menu_expanded = menu_expanded.clone();
+                        move |e:MouseEvent| {
+                            e.prevent_default();
+                            menu_expanded.set(false);
+                            navigator.push(&Route::Domains);
+                        }
+                    }} class="menu__item" href={Route::Domains.to_path()}>{"Sites"}</a>
+                </li>
                 { if props.username.is_some() {
                     html! {
                         <>
@@ -76,6 +101,39 @@ pub fn menu(props: &Props) -> Html {
                                     }
                                 }} class="menu__item" href={Route::Tools.to_path()}>{"Tools"}</a>
                             </li>
+                            <li class="menu__dropdown">
+                                <a onclick={{
+                                    let navigator = navigator.clone();
+                                    let menu_expanded = menu_expanded.clone();
+                                    move |e:MouseEvent| {
+                                        e.prevent_default();
+                                        menu_expanded.set(false);
+                                        navigator.push(&Route::SavedSearches);
+                                    }
+                                }} class="menu__item" href={Route::SavedSearches.to_path()}>{"Searches"}</a>
+                                <ul class="menu__dropdown-list">
+                                    {
+                                        saved_searches.searches.iter().map(|search| {
+                                            let query = query_params(search);
+                                            html! {
+                                                <li>
+                                                    <a onclick={{
+                                                        let navigator = navigator.clone();
+                                                        let menu_expanded = menu_expanded.clone();
+                                                        move |e:MouseEvent| {
+                                                            e.prevent_default();
+                                                            menu_expanded.set(false);
+                                                            let _ = navigator.push_with_query(&Route::Bookmarks, &query);
+                                                        }
+                                                    }} class="menu__item" href={format!("{}?{}", Route::Bookmarks.to_path(), search.query)}>
+                                                        {search.name.clone()}
+                                                    </a>
+                                                </li>
+                                            }
+                                        }).collect::<Html>()
+                                    }
+                                </ul>
+                            </li>
                         </>
                     }
                 } else {
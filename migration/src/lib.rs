@@ -13,6 +13,17 @@ mod m20230314_225232_add_user_id_to_bookmark;
 mod m20230331_194725_create_tags_tables;
 mod m20230414_180807_add_private_to_bookmark;
 mod m20230506_102057_add_pin_table;
+mod m20230521_093012_add_domain_to_bookmark;
+mod m20230523_201544_create_saved_search_table;
+mod m20230525_190321_add_user_id_to_tag;
+mod m20230527_083344_create_tag_alias_table;
+mod m20230529_174210_add_metadata_to_tag;
+mod m20230531_201837_index_tag_lower_name;
+mod m20230602_184517_create_rule_table;
+mod m20230604_110342_add_metadata_to_bookmark;
+mod m20230607_201204_add_metadata_status_to_bookmark;
+mod m20230609_191538_create_query_param_rule_table;
+mod m20230611_094127_add_url_key_to_bookmark;
 
 pub struct Migrator;
 
@@ -33,6 +44,17 @@ impl MigratorTrait for Migrator {
             Box::new(m20230331_194725_create_tags_tables::Migration),
             Box::new(m20230414_180807_add_private_to_bookmark::Migration),
             Box::new(m20230506_102057_add_pin_table::Migration),
+            Box::new(m20230521_093012_add_domain_to_bookmark::Migration),
+            Box::new(m20230523_201544_create_saved_search_table::Migration),
+            Box::new(m20230525_190321_add_user_id_to_tag::Migration),
+            Box::new(m20230527_083344_create_tag_alias_table::Migration),
+            Box::new(m20230529_174210_add_metadata_to_tag::Migration),
+            Box::new(m20230531_201837_index_tag_lower_name::Migration),
+            Box::new(m20230602_184517_create_rule_table::Migration),
+            Box::new(m20230604_110342_add_metadata_to_bookmark::Migration),
+            Box::new(m20230607_201204_add_metadata_status_to_bookmark::Migration),
+            Box::new(m20230609_191538_create_query_param_rule_table::Migration),
+            Box::new(m20230611_094127_add_url_key_to_bookmark::Migration),
         ]
     }
 }
@@ -0,0 +1,89 @@
+use common::url::domain;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+/// The number of bookmarks whose domain is computed at once.
+const BACKFILL_PAGE_SIZE: u64 = 1000;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Bookmark::Domain).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        // the domains are computed the way the backend does, a page of bookmarks at a time
+        let connection = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let mut last_id = 0;
+        loop {
+            let rows = connection
+                .query_all(
+                    backend.build(
+                        Query::select()
+                            .columns([Bookmark::Id, Bookmark::Url])
+                            .from(Bookmark::Table)
+                            .and_where(Expr::col(Bookmark::Id).gt(last_id))
+                            .order_by(Bookmark::Id, Order::Asc)
+                            .limit(BACKFILL_PAGE_SIZE),
+                    ),
+                )
+                .await?;
+            if rows.is_empty() {
+                break;
+            }
+
+            for row in rows {
+                let id: i32 = row.try_get("", &Bookmark::Id.to_string())?;
+                let url: String = row.try_get("", &Bookmark::Url.to_string())?;
+                manager
+                    .exec_stmt(
+                        Query::update()
+                            .table(Bookmark::Table)
+                            .value(Bookmark::Domain, domain(&url))
+                            .and_where(Expr::col(Bookmark::Id).eq(id))
+                            .to_owned(),
+                    )
+                    .await?;
+                last_id = id;
+            }
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(Bookmark::Table)
+                    .col(Bookmark::Domain)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .drop_column(Bookmark::Domain)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Bookmark {
+    Table,
+    Id,
+    Url,
+    Domain,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SavedSearch::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SavedSearch::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SavedSearch::UserId).integer().not_null())
+                    .col(ColumnDef::new(SavedSearch::Name).string_len(255).not_null())
+                    .col(ColumnDef::new(SavedSearch::Query).text().not_null())
+                    .col(
+                        ColumnDef::new(SavedSearch::IsDefault)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(SavedSearch::CreationDate)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT NOW()".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .from(SavedSearch::Table, SavedSearch::UserId)
+                    .to(Account::Table, Account::Id)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(SavedSearch::Table)
+                    .col(SavedSearch::UserId)
+                    .col(SavedSearch::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SavedSearch::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Account {
+    Table,
+    Id,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum SavedSearch {
+    Table,
+    Id,
+    UserId,
+    Name,
+    Query,
+    IsDefault,
+    CreationDate,
+}
@@ -0,0 +1,136 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Tag::UserId).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        // split the shared tags: each account gets its own copy of the tags it uses, links are
+        // moved to the copies and the shared rows are removed.
+        for sql in [
+            r#"ALTER TABLE "tag" DROP CONSTRAINT IF EXISTS "tag_name_key""#,
+            r#"INSERT INTO "tag" ("name", "user_id")
+                SELECT DISTINCT "tag"."name", "bookmark"."user_id"
+                FROM "tag"
+                JOIN "bookmark_tag" ON "bookmark_tag"."tag_id" = "tag"."id"
+                JOIN "bookmark" ON "bookmark"."id" = "bookmark_tag"."bookmark_id"
+                WHERE "tag"."user_id" IS NULL"#,
+            r#"UPDATE "bookmark_tag" SET "tag_id" = "owned"."id"
+                FROM "tag" AS "shared", "bookmark", "tag" AS "owned"
+                WHERE "shared"."id" = "bookmark_tag"."tag_id"
+                AND "shared"."user_id" IS NULL
+                AND "bookmark"."id" = "bookmark_tag"."bookmark_id"
+                AND "owned"."name" = "shared"."name"
+                AND "owned"."user_id" = "bookmark"."user_id""#,
+            r#"DELETE FROM "tag" WHERE "user_id" IS NULL"#,
+        ] {
+            manager
+                .get_connection()
+                .execute(Statement::from_string(
+                    manager.get_database_backend(),
+                    sql.to_owned(),
+                ))
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .modify_column(ColumnDef::new(Tag::UserId).integer().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_tag_user_id")
+                    .from(Tag::Table, Tag::UserId)
+                    .to(Account::Table, Account::Id)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tag_user_id_name")
+                    .table(Tag::Table)
+                    .col(Tag::UserId)
+                    .col(Tag::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // merge back the tags sharing the same name into the oldest one
+        for sql in [
+            r#"UPDATE "bookmark_tag" SET "tag_id" = "kept"."id"
+                FROM "tag", (SELECT MIN("id") AS "id", "name" FROM "tag" GROUP BY "name") AS "kept"
+                WHERE "tag"."id" = "bookmark_tag"."tag_id"
+                AND "kept"."name" = "tag"."name""#,
+            r#"DELETE FROM "tag" WHERE "id" NOT IN (SELECT MIN("id") FROM "tag" GROUP BY "name")"#,
+        ] {
+            manager
+                .get_connection()
+                .execute(Statement::from_string(
+                    manager.get_database_backend(),
+                    sql.to_owned(),
+                ))
+                .await?;
+        }
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_tag_user_id_name")
+                    .table(Tag::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .drop_column(Tag::UserId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("tag_name_key")
+                    .table(Tag::Table)
+                    .col(Tag::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Tag {
+    Table,
+    UserId,
+    Name,
+}
+
+#[derive(Iden)]
+enum Account {
+    Table,
+    Id,
+}
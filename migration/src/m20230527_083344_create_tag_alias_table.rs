@@ -0,0 +1,83 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TagAlias::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TagAlias::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TagAlias::UserId).integer().not_null())
+                    .col(ColumnDef::new(TagAlias::Alias).string_len(255).not_null())
+                    .col(ColumnDef::new(TagAlias::TagId).integer().not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .from(TagAlias::Table, TagAlias::UserId)
+                    .to(Account::Table, Account::Id)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .from(TagAlias::Table, TagAlias::TagId)
+                    .to(Tag::Table, Tag::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .table(TagAlias::Table)
+                    .col(TagAlias::UserId)
+                    .col(TagAlias::Alias)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TagAlias::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Account {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Tag {
+    Table,
+    Id,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum TagAlias {
+    Table,
+    Id,
+    UserId,
+    Alias,
+    TagId,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Tag::Color).string_len(7))
+                    .add_column_if_not_exists(ColumnDef::new(Tag::Description).text())
+                    .add_column_if_not_exists(ColumnDef::new(Tag::Icon).string_len(64))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .drop_column(Tag::Color)
+                    .drop_column(Tag::Description)
+                    .drop_column(Tag::Icon)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Tag {
+    Table,
+    Color,
+    Description,
+    Icon,
+}
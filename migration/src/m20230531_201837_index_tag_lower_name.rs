@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_tag_user_id_name")
+                    .table(Tag::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        // tags keep the case they are written with but are unique regardless of it; the names
+        // stored so far are all lowercase hence cannot conflict.
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                manager.get_database_backend(),
+                r#"CREATE UNIQUE INDEX "idx_tag_user_id_lower_name" ON "tag" ("user_id", lower("name"))"#
+                    .to_owned(),
+            ))
+            .await
+            .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for sql in [
+            r#"DROP INDEX IF EXISTS "idx_tag_user_id_lower_name""#,
+            r#"UPDATE "tag" SET "name" = lower("name")"#,
+        ] {
+            manager
+                .get_connection()
+                .execute(Statement::from_string(
+                    manager.get_database_backend(),
+                    sql.to_owned(),
+                ))
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tag_user_id_name")
+                    .table(Tag::Table)
+                    .col(Tag::UserId)
+                    .col(Tag::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Tag {
+    Table,
+    UserId,
+    Name,
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Rule::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Rule::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Rule::UserId).integer().not_null())
+                    .col(ColumnDef::new(Rule::UrlPattern).text().null())
+                    .col(
+                        ColumnDef::new(Rule::UrlRegex)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(Rule::TitlePattern).text().null())
+                    .col(ColumnDef::new(Rule::Tags).text().not_null().default(""))
+                    .col(
+                        ColumnDef::new(Rule::Private)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Rule::Pin)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(Rule::CreationDate)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT NOW()".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .from(Rule::Table, Rule::UserId)
+                    .to(Account::Table, Account::Id)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_rule_user_id")
+                    .table(Rule::Table)
+                    .col(Rule::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Rule::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Account {
+    Table,
+    Id,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Rule {
+    Table,
+    Id,
+    UserId,
+    UrlPattern,
+    UrlRegex,
+    TitlePattern,
+    Tags,
+    Private,
+    Pin,
+    CreationDate,
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Bookmark::SiteName).text())
+                    .add_column_if_not_exists(ColumnDef::new(Bookmark::Author).text())
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Bookmark::PublishedDate).timestamp_with_time_zone(),
+                    )
+                    .add_column_if_not_exists(ColumnDef::new(Bookmark::Language).text())
+                    .add_column_if_not_exists(ColumnDef::new(Bookmark::ImageUrl).text())
+                    .add_column_if_not_exists(ColumnDef::new(Bookmark::FaviconUrl).text())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .drop_column(Bookmark::SiteName)
+                    .drop_column(Bookmark::Author)
+                    .drop_column(Bookmark::PublishedDate)
+                    .drop_column(Bookmark::Language)
+                    .drop_column(Bookmark::ImageUrl)
+                    .drop_column(Bookmark::FaviconUrl)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Bookmark {
+    Table,
+    SiteName,
+    Author,
+    PublishedDate,
+    Language,
+    ImageUrl,
+    FaviconUrl,
+}
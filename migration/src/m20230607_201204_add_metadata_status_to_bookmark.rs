@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Bookmark::MetadataStatus).string_len(16),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Bookmark::MetadataFetchDate).timestamp_with_time_zone(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .drop_column(Bookmark::MetadataStatus)
+                    .drop_column(Bookmark::MetadataFetchDate)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Bookmark {
+    Table,
+    MetadataStatus,
+    MetadataFetchDate,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(QueryParamRule::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(QueryParamRule::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    // the rules of the instance have no user
+                    .col(ColumnDef::new(QueryParamRule::UserId).integer().null())
+                    .col(ColumnDef::new(QueryParamRule::Pattern).text().not_null())
+                    .col(ColumnDef::new(QueryParamRule::Domain).text().null())
+                    .col(
+                        ColumnDef::new(QueryParamRule::Ignored)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(QueryParamRule::CreationDate)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .extra("DEFAULT NOW()".to_string()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .from(QueryParamRule::Table, QueryParamRule::UserId)
+                    .to(Account::Table, Account::Id)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_query_param_rule_user_id")
+                    .table(QueryParamRule::Table)
+                    .col(QueryParamRule::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(QueryParamRule::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Account {
+    Table,
+    Id,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum QueryParamRule {
+    Table,
+    Id,
+    UserId,
+    Pattern,
+    Domain,
+    Ignored,
+    CreationDate,
+}
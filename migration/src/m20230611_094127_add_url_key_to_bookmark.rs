@@ -0,0 +1,101 @@
+use common::url::key;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+
+/// The number of bookmarks whose key is computed at once.
+const BACKFILL_PAGE_SIZE: u64 = 1000;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .add_column_if_not_exists(ColumnDef::new(Bookmark::UrlKey).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        // the keys are computed the way the backend does, a page of bookmarks at a time
+        let connection = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let mut last_id = 0;
+        loop {
+            let rows = connection
+                .query_all(
+                    backend.build(
+                        Query::select()
+                            .columns([Bookmark::Id, Bookmark::Url])
+                            .from(Bookmark::Table)
+                            .and_where(Expr::col(Bookmark::Id).gt(last_id))
+                            .order_by(Bookmark::Id, Order::Asc)
+                            .limit(BACKFILL_PAGE_SIZE),
+                    ),
+                )
+                .await?;
+            if rows.is_empty() {
+                break;
+            }
+
+            for row in rows {
+                let id: i32 = row.try_get("", &Bookmark::Id.to_string())?;
+                let url: String = row.try_get("", &Bookmark::Url.to_string())?;
+                manager
+                    .exec_stmt(
+                        Query::update()
+                            .table(Bookmark::Table)
+                            .value(Bookmark::UrlKey, key(&url))
+                            .and_where(Expr::col(Bookmark::Id).eq(id))
+                            .to_owned(),
+                    )
+                    .await?;
+                last_id = id;
+            }
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .modify_column(ColumnDef::new(Bookmark::UrlKey).text().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_bookmark_user_id_url_key")
+                    .table(Bookmark::Table)
+                    .col(Bookmark::UserId)
+                    .col(Bookmark::UrlKey)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Bookmark::Table)
+                    .drop_column(Bookmark::UrlKey)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Bookmark {
+    Table,
+    Id,
+    UserId,
+    Url,
+    UrlKey,
+}
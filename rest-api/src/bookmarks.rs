@@ -1,16 +1,29 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 pub mod create;
 pub mod delete;
+pub mod duplicates;
 pub mod get_many;
 pub mod get_one;
+pub mod merge;
+pub mod refresh;
+pub mod timeline;
 pub mod update;
 
 pub const URL_BOOKMARKS: &str = "/api/bookmarks";
 pub const URL_BOOKMARK: &str = "/api/bookmarks/:id";
 pub const URL_BOOKMARK_QRCODE: &str = "/api/bookmarks/:id/qrcode";
+pub const URL_BOOKMARK_REFRESH: &str = "/api/bookmarks/:id/refresh";
+pub const URL_BOOKMARK_MERGE: &str = "/api/bookmarks/:id/merge";
 // todo merge with URL_BOOKMARKS
 pub const URL_BOOKMARKS_STATS: &str = "/api/bookmarks-stats";
+// todo merge with URL_BOOKMARKS
+pub const URL_BOOKMARKS_TIMELINE: &str = "/api/bookmarks-timeline";
+// todo merge with URL_BOOKMARKS
+pub const URL_BOOKMARKS_REFRESH: &str = "/api/bookmarks-refresh";
+// todo merge with URL_BOOKMARKS
+pub const URL_BOOKMARKS_DUPLICATES: &str = "/api/bookmarks-duplicates";
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum Access {
@@ -26,6 +39,36 @@ impl Default for Access {
     }
 }
 
+/// Where a bookmark stands regarding the refresh of its metadata.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum MetadataStatus {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "success")]
+    Success,
+    #[serde(rename = "failure")]
+    Failure,
+}
+
+/// What the page of a bookmark says about itself, as last found.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct BookmarkMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_date: Option<DateTime<Utc>>,
+    /// The language of the page, as a BCP 47 tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// The URL of the preview image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favicon_url: Option<String>,
+}
+
 pub enum GetBookmarksStatsResult {
     Success(GetBookmarksStatsResponse),
     ServerError,
@@ -1,4 +1,6 @@
 use crate::bookmarks::get_one::GetBookmarkResponse;
+use crate::bookmarks::BookmarkMetadata;
+use crate::urls::GetUrlConflictResponse;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -8,12 +10,16 @@ pub struct CreateBookmarkRequest {
     pub description: Option<String>,
     pub tags: Option<Vec<String>>,
     pub private: Option<bool>,
+    #[serde(flatten)]
+    pub metadata: BookmarkMetadata,
 }
 
 pub type CreateBookmarkResponse = GetBookmarkResponse;
 
 pub enum CreateBookmarkResult {
-    Success(CreateBookmarkResponse),
+    Success(Box<CreateBookmarkResponse>),
+    /// The user already bookmarked the page.
+    Conflict(GetUrlConflictResponse),
     Forbidden,
     ServerError,
 
@@ -31,9 +37,13 @@ impl CreateBookmarkResult {
             Ok(response) => match response.status() {
                 201 => match response.json::<CreateBookmarkResponse>().await {
                     Err(_) => Some(CreateBookmarkResult::DeserializationError),
-                    Ok(payload) => Some(CreateBookmarkResult::Success(payload)),
+                    Ok(payload) => Some(CreateBookmarkResult::Success(Box::new(payload))),
                 },
                 403 => Some(CreateBookmarkResult::Forbidden),
+                409 => match response.json::<GetUrlConflictResponse>().await {
+                    Err(_) => Some(CreateBookmarkResult::DeserializationError),
+                    Ok(payload) => Some(CreateBookmarkResult::Conflict(payload)),
+                },
                 500 => Some(CreateBookmarkResult::ServerError),
                 _ => {
                     // todo add log
@@ -51,6 +61,9 @@ impl axum::response::IntoResponse for CreateBookmarkResult {
             CreateBookmarkResult::Success(payload) => {
                 (http::StatusCode::CREATED, axum::Json(payload)).into_response()
             }
+            CreateBookmarkResult::Conflict(payload) => {
+                (http::StatusCode::CONFLICT, axum::Json(payload)).into_response()
+            }
             CreateBookmarkResult::Forbidden => http::StatusCode::FORBIDDEN.into_response(),
             CreateBookmarkResult::ServerError => {
                 http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
@@ -0,0 +1,47 @@
+use crate::bookmarks::get_one::GetBookmarkResponse;
+
+/// The groups of bookmarks pointing to the same page, the oldest bookmark of each group first.
+pub type GetDuplicatesResponse = Vec<Vec<GetBookmarkResponse>>;
+
+pub enum GetDuplicatesResult {
+    Success(GetDuplicatesResponse),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetDuplicatesResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetDuplicatesResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetDuplicatesResponse>().await {
+                    Err(_) => Some(GetDuplicatesResult::DeserializationError),
+                    Ok(payload) => Some(GetDuplicatesResult::Success(payload)),
+                },
+                500 => Some(GetDuplicatesResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetDuplicatesResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetDuplicatesResult::Success(payload) => axum::Json(payload).into_response(),
+            GetDuplicatesResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -1,7 +1,9 @@
-use crate::bookmarks::Access;
+use crate::bookmarks::{Access, BookmarkMetadata, MetadataStatus};
 use crate::error_response::ErrorResponse;
+use crate::tags::TagMetadata;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 #[cfg(feature = "frontend")]
 use std::str::FromStr;
 
@@ -12,16 +14,38 @@ pub struct GetBookmarkResponse {
     pub title: Option<String>,
     pub description: Option<String>,
     pub tags: Vec<String>,
+    /// The metadata of the tags having some, by tag name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags_metadata: HashMap<String, TagMetadata>,
     pub creation_date: DateTime<Utc>,
     pub update_date: Option<DateTime<Utc>>,
     pub user_id: i32,
     pub access: Access,
     pub private: bool,
     pub pinned: bool,
+    pub domain: Option<String>,
+    #[serde(flatten)]
+    pub metadata: BookmarkMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_status: Option<MetadataStatus>,
+    /// When the metadata were last fetched, successfully or not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_fetch_date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_snippet: Option<Vec<SnippetPart>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description_snippet: Option<Vec<SnippetPart>>,
+}
+
+/// A fragment of a search snippet; `highlight` is set on fragments matching the search terms.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SnippetPart {
+    pub text: String,
+    pub highlight: bool,
 }
 
 pub enum GetBookmarkResult {
-    Success(GetBookmarkResponse),
+    Success(Box<GetBookmarkResponse>),
     NotFound(i32, String),
     ServerError,
 
@@ -39,7 +63,7 @@ impl GetBookmarkResult {
             Ok(response) => match response.status() {
                 200 => match response.json::<GetBookmarkResponse>().await {
                     Err(_) => Some(GetBookmarkResult::DeserializationError),
-                    Ok(payload) => Some(GetBookmarkResult::Success(payload)),
+                    Ok(payload) => Some(GetBookmarkResult::Success(Box::new(payload))),
                 },
                 404 => match response.json::<ErrorResponse>().await {
                     Err(_) => Some(GetBookmarkResult::DeserializationError),
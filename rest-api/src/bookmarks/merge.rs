@@ -0,0 +1,93 @@
+use crate::bookmarks::get_one::GetBookmarkResponse;
+use crate::error_response::ErrorResponse;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "frontend")]
+use std::str::FromStr;
+
+/// The bookmarks merged into the one of the URL and deleted: their tags and pins are moved to
+/// it and it keeps the oldest creation date.
+#[derive(Serialize, Deserialize)]
+pub struct MergeBookmarksRequest {
+    pub ids: Vec<i32>,
+}
+
+pub type MergeBookmarksResponse = GetBookmarkResponse;
+
+pub enum MergeBookmarksResult {
+    Success(Box<MergeBookmarksResponse>),
+    InvalidParameter(String),
+    Forbidden,
+    NotFound(i32, String),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl MergeBookmarksResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(MergeBookmarksResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<MergeBookmarksResponse>().await {
+                    Err(_) => Some(MergeBookmarksResult::DeserializationError),
+                    Ok(payload) => Some(MergeBookmarksResult::Success(Box::new(payload))),
+                },
+                400 => match response.json::<ErrorResponse>().await {
+                    Err(_) => Some(MergeBookmarksResult::DeserializationError),
+                    Ok(payload) => match payload.code() {
+                        "INVALID_PARAMETER" => Some(MergeBookmarksResult::InvalidParameter(
+                            payload.message().to_owned(),
+                        )),
+                        _ => Some(MergeBookmarksResult::DeserializationError),
+                    },
+                },
+                403 => Some(MergeBookmarksResult::Forbidden),
+                404 => match response.json::<ErrorResponse>().await {
+                    Err(_) => Some(MergeBookmarksResult::DeserializationError),
+                    Ok(payload) => match payload.data("id").and_then(|id| i32::from_str(id).ok()) {
+                        None => Some(MergeBookmarksResult::DeserializationError),
+                        Some(id) => Some(MergeBookmarksResult::NotFound(
+                            id,
+                            payload.message().to_string(),
+                        )),
+                    },
+                },
+                500 => Some(MergeBookmarksResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for MergeBookmarksResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            MergeBookmarksResult::Success(payload) => axum::Json(payload).into_response(),
+            MergeBookmarksResult::InvalidParameter(message) => (
+                http::StatusCode::BAD_REQUEST,
+                axum::Json(ErrorResponse::new("INVALID_PARAMETER", &message)),
+            )
+                .into_response(),
+            MergeBookmarksResult::Forbidden => http::StatusCode::FORBIDDEN.into_response(),
+            MergeBookmarksResult::NotFound(id, message) => (
+                http::StatusCode::NOT_FOUND,
+                axum::Json(
+                    ErrorResponse::new("NOT_FOUND", &message).with_data("id", &format!("{}", id)),
+                ),
+            )
+                .into_response(),
+            MergeBookmarksResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
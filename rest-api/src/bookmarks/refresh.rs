@@ -0,0 +1,131 @@
+use crate::error_response::ErrorResponse;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "frontend")]
+use std::str::FromStr;
+
+pub enum RefreshBookmarkResult {
+    Success,
+    Forbidden,
+    NotFound(i32, String),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl RefreshBookmarkResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(RefreshBookmarkResult::BrowserError),
+            Ok(response) => match response.status() {
+                202 => Some(RefreshBookmarkResult::Success),
+                403 => Some(RefreshBookmarkResult::Forbidden),
+                404 => match response.json::<ErrorResponse>().await {
+                    Err(_) => Some(RefreshBookmarkResult::DeserializationError),
+                    Ok(payload) => match payload.data("id").and_then(|id| i32::from_str(id).ok()) {
+                        None => Some(RefreshBookmarkResult::DeserializationError),
+                        Some(id) => Some(RefreshBookmarkResult::NotFound(
+                            id,
+                            payload.message().to_string(),
+                        )),
+                    },
+                },
+                500 => Some(RefreshBookmarkResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for RefreshBookmarkResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            RefreshBookmarkResult::Success => http::StatusCode::ACCEPTED.into_response(),
+            RefreshBookmarkResult::Forbidden => http::StatusCode::FORBIDDEN.into_response(),
+            RefreshBookmarkResult::NotFound(id, message) => (
+                http::StatusCode::NOT_FOUND,
+                axum::Json(
+                    ErrorResponse::new("NOT_FOUND", &message).with_data("id", &format!("{}", id)),
+                ),
+            )
+                .into_response(),
+            RefreshBookmarkResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RefreshBookmarksResponse {
+    /// The number of bookmarks whose metadata will be fetched again.
+    pub count: u64,
+}
+
+pub enum RefreshBookmarksResult {
+    Success(RefreshBookmarksResponse),
+    InvalidParameter(String),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl RefreshBookmarksResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(RefreshBookmarksResult::BrowserError),
+            Ok(response) => match response.status() {
+                202 => match response.json::<RefreshBookmarksResponse>().await {
+                    Err(_) => Some(RefreshBookmarksResult::DeserializationError),
+                    Ok(payload) => Some(RefreshBookmarksResult::Success(payload)),
+                },
+                400 => match response.json::<ErrorResponse>().await {
+                    Err(_) => Some(RefreshBookmarksResult::DeserializationError),
+                    Ok(payload) => match payload.code() {
+                        "INVALID_PARAMETER" => Some(RefreshBookmarksResult::InvalidParameter(
+                            payload.message().to_owned(),
+                        )),
+                        _ => Some(RefreshBookmarksResult::DeserializationError),
+                    },
+                },
+                500 => Some(RefreshBookmarksResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for RefreshBookmarksResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            RefreshBookmarksResult::Success(payload) => {
+                (http::StatusCode::ACCEPTED, axum::Json(payload)).into_response()
+            }
+            RefreshBookmarksResult::InvalidParameter(message) => (
+                http::StatusCode::BAD_REQUEST,
+                axum::Json(ErrorResponse::new("INVALID_PARAMETER", &message)),
+            )
+                .into_response(),
+            RefreshBookmarksResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
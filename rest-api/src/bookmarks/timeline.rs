@@ -0,0 +1,72 @@
+use crate::error_response::ErrorResponse;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TimelineBucket {
+    pub date: NaiveDate,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetBookmarksTimelineResponse {
+    pub buckets: Vec<TimelineBucket>,
+}
+
+pub enum GetBookmarksTimelineResult {
+    Success(GetBookmarksTimelineResponse),
+    InvalidParameter(String),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetBookmarksTimelineResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetBookmarksTimelineResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetBookmarksTimelineResponse>().await {
+                    Err(_) => Some(GetBookmarksTimelineResult::DeserializationError),
+                    Ok(payload) => Some(GetBookmarksTimelineResult::Success(payload)),
+                },
+                400 => match response.json::<ErrorResponse>().await {
+                    Err(_) => Some(GetBookmarksTimelineResult::DeserializationError),
+                    Ok(payload) => match payload.code() {
+                        "INVALID_PARAMETER" => Some(GetBookmarksTimelineResult::InvalidParameter(
+                            payload.message().to_owned(),
+                        )),
+                        _ => Some(GetBookmarksTimelineResult::DeserializationError),
+                    },
+                },
+                500 => Some(GetBookmarksTimelineResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetBookmarksTimelineResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetBookmarksTimelineResult::Success(payload) => axum::Json(payload).into_response(),
+            GetBookmarksTimelineResult::InvalidParameter(message) => (
+                http::StatusCode::BAD_REQUEST,
+                axum::Json(ErrorResponse::new("INVALID_PARAMETER", &message)),
+            )
+                .into_response(),
+            GetBookmarksTimelineResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::bookmarks::get_one::GetBookmarkResponse;
 use crate::error_response::ErrorResponse;
+use crate::urls::GetUrlConflictResponse;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "frontend")]
 use std::str::FromStr;
@@ -17,7 +18,9 @@ pub struct UpdateBookmarkRequest {
 pub type UpdateBookmarkResponse = GetBookmarkResponse;
 
 pub enum UpdateBookmarkResult {
-    Success(UpdateBookmarkResponse),
+    Success(Box<UpdateBookmarkResponse>),
+    /// Another bookmark of the user already points to the new URL.
+    Conflict(GetUrlConflictResponse),
     Forbidden,
     NotFound(i32, String),
     ServerError,
@@ -36,9 +39,13 @@ impl UpdateBookmarkResult {
             Ok(response) => match response.status() {
                 200 => match response.json::<UpdateBookmarkResponse>().await {
                     Err(_) => Some(UpdateBookmarkResult::DeserializationError),
-                    Ok(payload) => Some(UpdateBookmarkResult::Success(payload)),
+                    Ok(payload) => Some(UpdateBookmarkResult::Success(Box::new(payload))),
                 },
                 403 => Some(UpdateBookmarkResult::Forbidden),
+                409 => match response.json::<GetUrlConflictResponse>().await {
+                    Err(_) => Some(UpdateBookmarkResult::DeserializationError),
+                    Ok(payload) => Some(UpdateBookmarkResult::Conflict(payload)),
+                },
                 404 => match response.json::<ErrorResponse>().await {
                     Err(_) => Some(UpdateBookmarkResult::DeserializationError),
                     Ok(payload) => match payload.data("id").and_then(|id| i32::from_str(id).ok()) {
@@ -64,6 +71,9 @@ impl axum::response::IntoResponse for UpdateBookmarkResult {
     fn into_response(self) -> axum::response::Response {
         match self {
             UpdateBookmarkResult::Success(payload) => axum::Json(payload).into_response(),
+            UpdateBookmarkResult::Conflict(payload) => {
+                (http::StatusCode::CONFLICT, axum::Json(payload)).into_response()
+            }
             UpdateBookmarkResult::Forbidden => http::StatusCode::FORBIDDEN.into_response(),
             UpdateBookmarkResult::NotFound(id, message) => (
                 http::StatusCode::NOT_FOUND,
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+pub const URL_DOMAINS: &str = "/api/domains";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Domain {
+    pub name: String,
+    pub count: i32,
+}
+
+pub type GetDomainsResponse = Vec<Domain>;
+
+pub enum GetDomainsResult {
+    Success(GetDomainsResponse),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetDomainsResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetDomainsResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetDomainsResponse>().await {
+                    Err(_) => Some(GetDomainsResult::DeserializationError),
+                    Ok(payload) => Some(GetDomainsResult::Success(payload)),
+                },
+                500 => Some(GetDomainsResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetDomainsResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetDomainsResult::Success(payload) => axum::Json(payload).into_response(),
+            GetDomainsResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
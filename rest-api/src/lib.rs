@@ -3,10 +3,16 @@ use serde::{Deserialize, Serialize};
 
 pub mod application;
 pub mod bookmarks;
+pub mod domains;
 pub mod error_response;
 pub mod import_shaarli_api;
 pub mod password_recoveries;
+pub mod query_params;
+pub mod rules;
+pub mod saved_searches;
 pub mod sessions;
+pub mod suggestions;
+pub mod tag_aliases;
 pub mod tags;
 pub mod urls;
 pub mod users;
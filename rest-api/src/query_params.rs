@@ -0,0 +1,178 @@
+use crate::error_response::ErrorResponse;
+use serde::{Deserialize, Serialize};
+
+pub const URL_QUERY_PARAM_RULES: &str = "/api/query-param-rules";
+pub const URL_QUERY_PARAM_RULE: &str = "/api/query-param-rules/:id";
+
+/// Removes the query parameters matching the pattern from the URLs being bookmarked or, when
+/// `ignored` is not set, keeps them even though the registry would remove them.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct QueryParamRule {
+    pub id: i32,
+    /// A query parameter name, where `*` matches anything, e.g. `utm_*`.
+    pub pattern: String,
+    /// The domain, and its subdomains, the rule is restricted to.
+    pub domain: Option<String>,
+    pub ignored: bool,
+    /// Whether the rule applies to all the users of the instance.
+    pub instance: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveQueryParamRuleRequest {
+    pub pattern: String,
+    pub domain: Option<String>,
+    pub ignored: bool,
+    /// Only the administrators of the instance can create rules for all the users.
+    pub instance: bool,
+}
+
+/// The rules of the instance, then the ones of the user.
+pub type GetQueryParamRulesResponse = Vec<QueryParamRule>;
+
+pub enum GetQueryParamRulesResult {
+    Success(GetQueryParamRulesResponse),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetQueryParamRulesResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetQueryParamRulesResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetQueryParamRulesResponse>().await {
+                    Err(_) => Some(GetQueryParamRulesResult::DeserializationError),
+                    Ok(payload) => Some(GetQueryParamRulesResult::Success(payload)),
+                },
+                500 => Some(GetQueryParamRulesResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetQueryParamRulesResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetQueryParamRulesResult::Success(payload) => axum::Json(payload).into_response(),
+            GetQueryParamRulesResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum SaveQueryParamRuleResult {
+    Success(QueryParamRule),
+    Forbidden,
+    InvalidParameter(String),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl SaveQueryParamRuleResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(SaveQueryParamRuleResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<QueryParamRule>().await {
+                    Err(_) => Some(SaveQueryParamRuleResult::DeserializationError),
+                    Ok(payload) => Some(SaveQueryParamRuleResult::Success(payload)),
+                },
+                400 => match response.json::<ErrorResponse>().await {
+                    Err(_) => Some(SaveQueryParamRuleResult::DeserializationError),
+                    Ok(payload) => match payload.code() {
+                        "INVALID_PARAMETER" => Some(SaveQueryParamRuleResult::InvalidParameter(
+                            payload.message().to_owned(),
+                        )),
+                        _ => Some(SaveQueryParamRuleResult::DeserializationError),
+                    },
+                },
+                403 => Some(SaveQueryParamRuleResult::Forbidden),
+                500 => Some(SaveQueryParamRuleResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for SaveQueryParamRuleResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            SaveQueryParamRuleResult::Success(payload) => axum::Json(payload).into_response(),
+            SaveQueryParamRuleResult::Forbidden => http::StatusCode::FORBIDDEN.into_response(),
+            SaveQueryParamRuleResult::InvalidParameter(message) => (
+                http::StatusCode::BAD_REQUEST,
+                axum::Json(ErrorResponse::new("INVALID_PARAMETER", &message)),
+            )
+                .into_response(),
+            SaveQueryParamRuleResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum DeleteQueryParamRuleResult {
+    Success,
+    NotFound,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl DeleteQueryParamRuleResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(DeleteQueryParamRuleResult::BrowserError),
+            Ok(response) => match response.status() {
+                204 => Some(DeleteQueryParamRuleResult::Success),
+                404 => Some(DeleteQueryParamRuleResult::NotFound),
+                500 => Some(DeleteQueryParamRuleResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for DeleteQueryParamRuleResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            DeleteQueryParamRuleResult::Success => http::StatusCode::NO_CONTENT.into_response(),
+            DeleteQueryParamRuleResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            DeleteQueryParamRuleResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::error_response::ErrorResponse;
+use serde::{Deserialize, Serialize};
+
+pub const URL_RULES: &str = "/api/rules";
+pub const URL_RULE: &str = "/api/rules/:id";
+pub const URL_RULES_APPLY: &str = "/api/rules-apply";
+
+/// Tags, makes private or pins the bookmarks whose URL and title match the patterns.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Rule {
+    pub id: i32,
+    /// A glob, where `*` matches anything, or a regex when `url_regex` is set.
+    pub url_pattern: Option<String>,
+    pub url_regex: bool,
+    /// A regex.
+    pub title_pattern: Option<String>,
+    pub tags: Vec<String>,
+    pub private: bool,
+    pub pin: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveRuleRequest {
+    pub url_pattern: Option<String>,
+    pub url_regex: bool,
+    pub title_pattern: Option<String>,
+    pub tags: Vec<String>,
+    pub private: bool,
+    pub pin: bool,
+}
+
+pub type GetRulesResponse = Vec<Rule>;
+
+pub enum GetRulesResult {
+    Success(GetRulesResponse),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetRulesResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetRulesResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetRulesResponse>().await {
+                    Err(_) => Some(GetRulesResult::DeserializationError),
+                    Ok(payload) => Some(GetRulesResult::Success(payload)),
+                },
+                500 => Some(GetRulesResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetRulesResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetRulesResult::Success(payload) => axum::Json(payload).into_response(),
+            GetRulesResult::ServerError => http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum SaveRuleResult {
+    Success(Rule),
+    NotFound,
+    InvalidParameter(String),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl SaveRuleResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(SaveRuleResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<Rule>().await {
+                    Err(_) => Some(SaveRuleResult::DeserializationError),
+                    Ok(payload) => Some(SaveRuleResult::Success(payload)),
+                },
+                400 => match response.json::<ErrorResponse>().await {
+                    Err(_) => Some(SaveRuleResult::DeserializationError),
+                    Ok(payload) => match payload.code() {
+                        "INVALID_PARAMETER" => Some(SaveRuleResult::InvalidParameter(
+                            payload.message().to_owned(),
+                        )),
+                        _ => Some(SaveRuleResult::DeserializationError),
+                    },
+                },
+                404 => Some(SaveRuleResult::NotFound),
+                500 => Some(SaveRuleResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for SaveRuleResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            SaveRuleResult::Success(payload) => axum::Json(payload).into_response(),
+            SaveRuleResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            SaveRuleResult::InvalidParameter(message) => (
+                http::StatusCode::BAD_REQUEST,
+                axum::Json(ErrorResponse::new("INVALID_PARAMETER", &message)),
+            )
+                .into_response(),
+            SaveRuleResult::ServerError => http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum DeleteRuleResult {
+    Success,
+    NotFound,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl DeleteRuleResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(DeleteRuleResult::BrowserError),
+            Ok(response) => match response.status() {
+                204 => Some(DeleteRuleResult::Success),
+                404 => Some(DeleteRuleResult::NotFound),
+                500 => Some(DeleteRuleResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for DeleteRuleResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            DeleteRuleResult::Success => http::StatusCode::NO_CONTENT.into_response(),
+            DeleteRuleResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            DeleteRuleResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApplyRulesResponse {
+    /// The number of bookmarks matched by at least one rule.
+    pub count: u64,
+}
+
+pub enum ApplyRulesResult {
+    Success(ApplyRulesResponse),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl ApplyRulesResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(ApplyRulesResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<ApplyRulesResponse>().await {
+                    Err(_) => Some(ApplyRulesResult::DeserializationError),
+                    Ok(payload) => Some(ApplyRulesResult::Success(payload)),
+                },
+                500 => Some(ApplyRulesResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for ApplyRulesResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            ApplyRulesResult::Success(payload) => axum::Json(payload).into_response(),
+            ApplyRulesResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
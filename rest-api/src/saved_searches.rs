@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+
+pub const URL_SAVED_SEARCHES: &str = "/api/saved-searches";
+pub const URL_SAVED_SEARCH: &str = "/api/saved-searches/:id";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SavedSearch {
+    pub id: i32,
+    pub name: String,
+    /// The bookmarks list query string, as used by the frontend.
+    pub query: String,
+    pub is_default: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveSearchRequest {
+    pub name: String,
+    pub query: String,
+    pub is_default: bool,
+}
+
+pub type GetSavedSearchesResponse = Vec<SavedSearch>;
+
+pub enum GetSavedSearchesResult {
+    Success(GetSavedSearchesResponse),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetSavedSearchesResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetSavedSearchesResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetSavedSearchesResponse>().await {
+                    Err(_) => Some(GetSavedSearchesResult::DeserializationError),
+                    Ok(payload) => Some(GetSavedSearchesResult::Success(payload)),
+                },
+                500 => Some(GetSavedSearchesResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetSavedSearchesResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetSavedSearchesResult::Success(payload) => axum::Json(payload).into_response(),
+            GetSavedSearchesResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum SaveSearchResult {
+    Success(SavedSearch),
+    NotFound,
+    Conflict,
+    InvalidName,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl SaveSearchResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(SaveSearchResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<SavedSearch>().await {
+                    Err(_) => Some(SaveSearchResult::DeserializationError),
+                    Ok(payload) => Some(SaveSearchResult::Success(payload)),
+                },
+                400 => Some(SaveSearchResult::InvalidName),
+                404 => Some(SaveSearchResult::NotFound),
+                409 => Some(SaveSearchResult::Conflict),
+                500 => Some(SaveSearchResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for SaveSearchResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            SaveSearchResult::Success(payload) => axum::Json(payload).into_response(),
+            SaveSearchResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            SaveSearchResult::Conflict => http::StatusCode::CONFLICT.into_response(),
+            SaveSearchResult::InvalidName => http::StatusCode::BAD_REQUEST.into_response(),
+            SaveSearchResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum DeleteSavedSearchResult {
+    Success,
+    NotFound,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl DeleteSavedSearchResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(DeleteSavedSearchResult::BrowserError),
+            Ok(response) => match response.status() {
+                204 => Some(DeleteSavedSearchResult::Success),
+                404 => Some(DeleteSavedSearchResult::NotFound),
+                500 => Some(DeleteSavedSearchResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for DeleteSavedSearchResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            DeleteSavedSearchResult::Success => http::StatusCode::NO_CONTENT.into_response(),
+            DeleteSavedSearchResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            DeleteSavedSearchResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
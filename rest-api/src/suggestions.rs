@@ -0,0 +1,55 @@
+use crate::domains::Domain;
+use crate::tags::Tag;
+use serde::{Deserialize, Serialize};
+
+pub const URL_SUGGESTIONS: &str = "/api/suggestions";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetSuggestionsResponse {
+    pub tags: Vec<Tag>,
+    pub titles: Vec<String>,
+    pub domains: Vec<Domain>,
+}
+
+pub enum GetSuggestionsResult {
+    Success(GetSuggestionsResponse),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetSuggestionsResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetSuggestionsResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetSuggestionsResponse>().await {
+                    Err(_) => Some(GetSuggestionsResult::DeserializationError),
+                    Ok(payload) => Some(GetSuggestionsResult::Success(payload)),
+                },
+                500 => Some(GetSuggestionsResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetSuggestionsResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetSuggestionsResult::Success(payload) => axum::Json(payload).into_response(),
+            GetSuggestionsResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+pub const URL_TAG_ALIASES: &str = "/api/tag-aliases";
+pub const URL_TAG_ALIAS: &str = "/api/tag-aliases/:alias";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TagAlias {
+    pub alias: String,
+    /// The name of the canonical tag.
+    pub tag: String,
+}
+
+pub type GetTagAliasesResponse = Vec<TagAlias>;
+
+pub enum GetTagAliasesResult {
+    Success(GetTagAliasesResponse),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetTagAliasesResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetTagAliasesResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetTagAliasesResponse>().await {
+                    Err(_) => Some(GetTagAliasesResult::DeserializationError),
+                    Ok(payload) => Some(GetTagAliasesResult::Success(payload)),
+                },
+                500 => Some(GetTagAliasesResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetTagAliasesResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetTagAliasesResult::Success(payload) => axum::Json(payload).into_response(),
+            GetTagAliasesResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum CreateTagAliasResult {
+    Success(TagAlias),
+    NotFound,
+    Conflict,
+    InvalidName,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl CreateTagAliasResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(CreateTagAliasResult::BrowserError),
+            Ok(response) => match response.status() {
+                201 => match response.json::<TagAlias>().await {
+                    Err(_) => Some(CreateTagAliasResult::DeserializationError),
+                    Ok(payload) => Some(CreateTagAliasResult::Success(payload)),
+                },
+                400 => Some(CreateTagAliasResult::InvalidName),
+                404 => Some(CreateTagAliasResult::NotFound),
+                409 => Some(CreateTagAliasResult::Conflict),
+                500 => Some(CreateTagAliasResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for CreateTagAliasResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            CreateTagAliasResult::Success(payload) => {
+                (http::StatusCode::CREATED, axum::Json(payload)).into_response()
+            }
+            CreateTagAliasResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            CreateTagAliasResult::Conflict => http::StatusCode::CONFLICT.into_response(),
+            CreateTagAliasResult::InvalidName => http::StatusCode::BAD_REQUEST.into_response(),
+            CreateTagAliasResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum DeleteTagAliasResult {
+    Success,
+    NotFound,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl DeleteTagAliasResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(DeleteTagAliasResult::BrowserError),
+            Ok(response) => match response.status() {
+                204 => Some(DeleteTagAliasResult::Success),
+                404 => Some(DeleteTagAliasResult::NotFound),
+                500 => Some(DeleteTagAliasResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for DeleteTagAliasResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            DeleteTagAliasResult::Success => http::StatusCode::NO_CONTENT.into_response(),
+            DeleteTagAliasResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            DeleteTagAliasResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
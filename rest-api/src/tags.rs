@@ -2,11 +2,43 @@ use crate::error_response::ErrorResponse;
 use serde::{Deserialize, Serialize};
 
 pub const URL_TAGS: &str = "/api/tags";
+pub const URL_TAG: &str = "/api/tags/:name";
+pub const URL_TAGS_MERGE: &str = "/api/tags-merge";
+pub const URL_TAG_METADATA: &str = "/api/tags/:name/metadata";
+/// Tags co-occurring with the ones given in the `tags` query parameter; answered with a
+/// `GetTagsResult`, counts being the number of bookmarks sharing the tags.
+pub const URL_RELATED_TAGS: &str = "/api/related-tags";
+
+/// How a tag is presented; every attribute is optional.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct TagMetadata {
+    /// A hex color such as `#1e90ff`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The name of a Material icon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+}
+
+impl TagMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none() && self.description.is_none() && self.icon.is_none()
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct Tag {
     pub name: String,
     pub count: i32,
+    /// Sum of the counts of the tag and of all its descendants; only set for trees.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Tag>,
+    #[serde(flatten)]
+    pub metadata: TagMetadata,
 }
 
 pub type GetTagsResponse = Vec<Tag>;
@@ -66,3 +98,216 @@ impl axum::response::IntoResponse for GetTagsResult {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct RenameTagRequest {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct MergeTagsRequest {
+    pub tags: Vec<String>,
+    pub into: String,
+}
+
+pub enum UpdateTagsResult {
+    Success,
+    NotFound,
+    InvalidName,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl UpdateTagsResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(UpdateTagsResult::BrowserError),
+            Ok(response) => match response.status() {
+                204 => Some(UpdateTagsResult::Success),
+                400 => Some(UpdateTagsResult::InvalidName),
+                404 => Some(UpdateTagsResult::NotFound),
+                500 => Some(UpdateTagsResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for UpdateTagsResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            UpdateTagsResult::Success => http::StatusCode::NO_CONTENT.into_response(),
+            UpdateTagsResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            UpdateTagsResult::InvalidName => http::StatusCode::BAD_REQUEST.into_response(),
+            UpdateTagsResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetTagResponse {
+    pub name: String,
+    pub count: i32,
+    /// Number of bookmarks having no other tag.
+    pub exclusive_count: i32,
+    #[serde(flatten)]
+    pub metadata: TagMetadata,
+}
+
+pub enum GetTagResult {
+    Success(GetTagResponse),
+    NotFound,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl GetTagResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(GetTagResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<GetTagResponse>().await {
+                    Err(_) => Some(GetTagResult::DeserializationError),
+                    Ok(payload) => Some(GetTagResult::Success(payload)),
+                },
+                404 => Some(GetTagResult::NotFound),
+                500 => Some(GetTagResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for GetTagResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            GetTagResult::Success(payload) => axum::Json(payload).into_response(),
+            GetTagResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            GetTagResult::ServerError => http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum DeleteTagResult {
+    Success,
+    NotFound,
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl DeleteTagResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(DeleteTagResult::BrowserError),
+            Ok(response) => match response.status() {
+                204 => Some(DeleteTagResult::Success),
+                404 => Some(DeleteTagResult::NotFound),
+                500 => Some(DeleteTagResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for DeleteTagResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            DeleteTagResult::Success => http::StatusCode::NO_CONTENT.into_response(),
+            DeleteTagResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            DeleteTagResult::ServerError => http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            _ => panic!(),
+        }
+    }
+}
+
+pub enum UpdateTagMetadataResult {
+    Success(TagMetadata),
+    NotFound,
+    InvalidParameter(String),
+    ServerError,
+
+    #[cfg(feature = "frontend")]
+    BrowserError,
+    #[cfg(feature = "frontend")]
+    DeserializationError,
+}
+
+#[cfg(feature = "frontend")]
+impl UpdateTagMetadataResult {
+    pub async fn from(value: Result<gloo_net::http::Response, gloo_net::Error>) -> Option<Self> {
+        match value {
+            Err(_) => Some(UpdateTagMetadataResult::BrowserError),
+            Ok(response) => match response.status() {
+                200 => match response.json::<TagMetadata>().await {
+                    Err(_) => Some(UpdateTagMetadataResult::DeserializationError),
+                    Ok(payload) => Some(UpdateTagMetadataResult::Success(payload)),
+                },
+                400 => match response.json::<ErrorResponse>().await {
+                    Err(_) => Some(UpdateTagMetadataResult::DeserializationError),
+                    Ok(payload) => match payload.code() {
+                        "INVALID_PARAMETER" => Some(UpdateTagMetadataResult::InvalidParameter(
+                            payload.message().to_owned(),
+                        )),
+                        _ => Some(UpdateTagMetadataResult::DeserializationError),
+                    },
+                },
+                404 => Some(UpdateTagMetadataResult::NotFound),
+                500 => Some(UpdateTagMetadataResult::ServerError),
+                _ => {
+                    // todo add log
+                    None
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl axum::response::IntoResponse for UpdateTagMetadataResult {
+    fn into_response(self) -> axum::response::Response {
+        match self {
+            UpdateTagMetadataResult::Success(payload) => axum::Json(payload).into_response(),
+            UpdateTagMetadataResult::NotFound => http::StatusCode::NOT_FOUND.into_response(),
+            UpdateTagMetadataResult::InvalidParameter(message) => (
+                http::StatusCode::BAD_REQUEST,
+                axum::Json(ErrorResponse::new("INVALID_PARAMETER", &message)),
+            )
+                .into_response(),
+            UpdateTagMetadataResult::ServerError => {
+                http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+            _ => panic!(),
+        }
+    }
+}
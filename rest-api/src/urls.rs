@@ -1,3 +1,4 @@
+use crate::bookmarks::BookmarkMetadata;
 use serde::{Deserialize, Serialize};
 
 pub const URL_URLS: &str = "/api/urls/:url";
@@ -7,6 +8,18 @@ pub struct GetUrlResponse {
     pub url: String,
     pub title: Option<String>,
     pub description: Option<String>,
+    /// The URL the page declares as its preferred one.
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// The OpenGraph type of the page, e.g. `article` or `video.other`.
+    #[serde(default)]
+    pub kind: Option<String>,
+    #[serde(flatten)]
+    pub metadata: BookmarkMetadata,
+    /// Tags for the page, from its keywords, its domain and the ones the user already put on
+    /// bookmarks of the same host.
+    #[serde(default)]
+    pub suggested_tags: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -15,7 +28,7 @@ pub struct GetUrlConflictResponse {
 }
 
 pub enum GetUrlResult {
-    Success(GetUrlResponse),
+    Success(Box<GetUrlResponse>),
     Conflict(GetUrlConflictResponse),
     Forbidden,
     ServerError,
@@ -35,7 +48,7 @@ impl GetUrlResult {
             Ok(response) => match response.status() {
                 200 => match response.json::<GetUrlResponse>().await {
                     Err(_) => Some(GetUrlResult::DeserializationError),
-                    Ok(payload) => Some(GetUrlResult::Success(payload)),
+                    Ok(payload) => Some(GetUrlResult::Success(Box::new(payload))),
                 },
                 400 => Some(GetUrlResult::InvalidUrl),
                 403 => Some(GetUrlResult::Forbidden),